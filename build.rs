@@ -0,0 +1,42 @@
+use std::{env, fs, path::Path};
+
+/// Record the versions of swc crates msrlint was built with, for `--env-info`
+fn main() {
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    println!("cargo:rerun-if-changed=Cargo.lock");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let manifest = fs::read_to_string(Path::new(&manifest_dir).join("Cargo.toml")).unwrap();
+    let lock = fs::read_to_string(Path::new(&manifest_dir).join("Cargo.lock")).unwrap_or_default();
+
+    // Only swc crates msrlint depends on directly are interesting
+    let dependencies: Vec<&str> = manifest
+        .lines()
+        .filter_map(|line| line.split_once(" = "))
+        .map(|(name, _)| name.trim())
+        .filter(|name| name.starts_with("swc"))
+        .collect();
+
+    // Packages in Cargo.lock start with a `name = "..."` line, followed by `version = "..."`
+    let mut versions = Vec::new();
+    let mut lines = lock.lines();
+    while let Some(line) = lines.next() {
+        let name = match line.strip_prefix("name = \"") {
+            Some(name) if dependencies.contains(&name.trim_end_matches('"')) => {
+                name.trim_end_matches('"')
+            }
+            _ => continue,
+        };
+        if let Some(version) = lines
+            .next()
+            .and_then(|line| line.strip_prefix("version = \""))
+        {
+            versions.push(format!("{} {}", name, version.trim_end_matches('"')));
+        }
+    }
+
+    println!(
+        "cargo:rustc-env=MSRLINT_SWC_VERSIONS={}",
+        versions.join(",")
+    );
+}
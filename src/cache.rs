@@ -0,0 +1,305 @@
+//! Caching of lint results between runs, for `--cache`
+
+use std::{
+    collections::HashMap,
+    fs,
+    hash::Hasher,
+    io,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use clap::ArgEnum;
+use serde::{Deserialize, Serialize};
+use siphasher::sip::SipHasher;
+
+use crate::{
+    debug,
+    linter::{config::LintConfig, LintResult},
+};
+
+/// Where the cache is stored when `--cache-location` isn't given
+pub const DEFAULT_CACHE_LOCATION: &str = ".msrlintcache";
+
+/// How to decide whether a file has changed since it was cached, from `--cache-strategy`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ArgEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheStrategy {
+    /// Compare the file's size and modification time
+    #[default]
+    Metadata,
+    /// Compare a hash of the file's contents
+    Content,
+}
+
+/// What is known about a file when its result was cached
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct FileDescriptor {
+    size: u64,
+    /// Modification time, in milliseconds since the Unix epoch
+    mtime: u64,
+    /// Hash of the file's contents, only used by the content strategy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    #[serde(flatten)]
+    file: FileDescriptor,
+    result: LintResult,
+}
+
+/// Results of files which had no errors when they were last linted
+///
+/// The cache is only valid for the msrlint version and configuration it was created with. If
+/// either changes, every file is linted again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintCache {
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(skip)]
+    strategy: CacheStrategy,
+    version: String,
+    hash_of_config: String,
+    files: HashMap<PathBuf, CacheEntry>,
+}
+
+impl LintCache {
+    /// Load the cache at `location`, or start an empty one if it doesn't exist or is outdated
+    ///
+    /// If `location` is a directory, the cache is stored in a file in that directory.
+    pub fn load(location: &Path, strategy: CacheStrategy, lint_config: &LintConfig) -> Self {
+        let path = cache_file_path(location);
+        let version = env!("CARGO_PKG_VERSION").to_string();
+        let hash_of_config = hash_of_config(lint_config);
+
+        let files = fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<LintCache>(&json).ok())
+            .filter(|cache| {
+                let valid = cache.version == version && cache.hash_of_config == hash_of_config;
+                if !valid {
+                    debug!(
+                        "cache",
+                        "Discarding cache for a different version or config"
+                    );
+                }
+                valid
+            })
+            .map(|cache| cache.files)
+            .unwrap_or_default();
+        debug!(
+            "cache",
+            "Loaded {} cached result(s) from {}",
+            files.len(),
+            path.display()
+        );
+
+        Self {
+            path,
+            strategy,
+            version,
+            hash_of_config,
+            files,
+        }
+    }
+
+    /// The cached result for a file, if it hasn't changed since it was cached
+    pub fn get(&self, file_path: &Path) -> Option<LintResult> {
+        let entry = self.files.get(file_path)?;
+        let file = self.describe(file_path, entry.file.hash.is_some()).ok()?;
+
+        let unchanged = match self.strategy {
+            CacheStrategy::Metadata => {
+                file.size == entry.file.size && file.mtime == entry.file.mtime
+            }
+            CacheStrategy::Content => file.hash.is_some() && file.hash == entry.file.hash,
+        };
+
+        if unchanged {
+            debug!("cache", "Using cached result for {}", file_path.display());
+        }
+        unchanged.then(|| entry.result.clone())
+    }
+
+    /// Remember a file's result, if it had no errors and wasn't fixed
+    ///
+    /// Files with errors are always linted again, so that fixing them is noticed.
+    pub fn set(&mut self, result: &LintResult) {
+        let cacheable = result.error_count == 0 && result.output.is_none();
+        let file = match self.describe(&result.file_path, true) {
+            Ok(file) if cacheable => file,
+            _ => {
+                self.files.remove(&result.file_path);
+                return;
+            }
+        };
+
+        let mut result = result.clone();
+        result.source_map = None;
+        self.files
+            .insert(result.file_path.clone(), CacheEntry { file, result });
+    }
+
+    /// Write the cache to disk
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        fs::write(&self.path, serde_json::to_string(self)?)
+    }
+
+    /// Delete the cache at `location`, e.g., when linting without `--cache`
+    pub fn delete(location: &Path) -> io::Result<()> {
+        match fs::remove_file(cache_file_path(location)) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    fn describe(&self, file_path: &Path, with_hash: bool) -> io::Result<FileDescriptor> {
+        let metadata = fs::metadata(file_path)?;
+        let mtime = metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default();
+
+        // Hashing is only worth reading the file for when using the content strategy
+        let hash = match self.strategy {
+            CacheStrategy::Content if with_hash => Some(hash(&fs::read(file_path)?)),
+            _ => None,
+        };
+
+        Ok(FileDescriptor {
+            size: metadata.len(),
+            mtime,
+            hash,
+        })
+    }
+}
+
+/// The file the cache is stored in, given by `--cache-location`
+fn cache_file_path(location: &Path) -> PathBuf {
+    let is_dir = location.is_dir() || location.to_string_lossy().ends_with(['/', '\\']);
+
+    if is_dir {
+        location.join(DEFAULT_CACHE_LOCATION)
+    } else {
+        location.to_path_buf()
+    }
+}
+
+fn hash_of_config(lint_config: &LintConfig) -> String {
+    // Converting to a value first sorts maps, so the hash doesn't depend on their order
+    let value = serde_json::to_value(lint_config).unwrap();
+    hash(value.to_string().as_bytes())
+}
+
+fn hash(bytes: &[u8]) -> String {
+    let mut hasher = SipHasher::new();
+    hasher.write(bytes);
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+
+    use crate::linter::diagnostic::{Diagnostic, Severity};
+
+    /// A temporary directory containing a file to lint
+    fn setup(name: &str, source: &str) -> (PathBuf, PathBuf) {
+        let dir = env::temp_dir().join(format!("msrlint-cache-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let file = dir.join("a.js");
+        fs::write(&file, source).unwrap();
+        (dir, file)
+    }
+
+    fn result(file_path: &Path, severity: Severity) -> LintResult {
+        let mut result = LintResult::new(file_path.to_path_buf());
+        let mut diagnostic = Diagnostic::fatal(String::from("problem"), None);
+        diagnostic.fatal = false;
+        diagnostic.severity = severity;
+        diagnostic.rule_id = Some(String::from("eqeqeq"));
+        result.add(diagnostic);
+        result
+    }
+
+    #[test]
+    fn replays_cached_warnings() {
+        let (dir, file) = setup("warnings", "a == b;\n");
+        let config = LintConfig::default();
+
+        let mut cache = LintCache::load(&dir, CacheStrategy::Metadata, &config);
+        assert!(cache.get(&file).is_none());
+
+        cache.set(&result(&file, Severity::Warn));
+        cache.save().unwrap();
+
+        let cache = LintCache::load(&dir, CacheStrategy::Metadata, &config);
+        let cached = cache.get(&file).unwrap();
+        assert_eq!(cached.warning_count, 1);
+        assert_eq!(cached.messages[0].rule_id.as_deref(), Some("eqeqeq"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn does_not_cache_errors() {
+        let (dir, file) = setup("errors", "a == b;\n");
+
+        let mut cache = LintCache::load(&dir, CacheStrategy::Metadata, &LintConfig::default());
+        cache.set(&result(&file, Severity::Error));
+        assert!(cache.get(&file).is_none());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn content_strategy_notices_changes() {
+        let (dir, file) = setup("content", "a === b;\n");
+
+        let mut cache = LintCache::load(&dir, CacheStrategy::Content, &LintConfig::default());
+        cache.set(&LintResult::new(file.clone()));
+        assert!(cache.get(&file).is_some());
+
+        fs::write(&file, "a === c;\n").unwrap();
+        assert!(cache.get(&file).is_none());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn config_changes_invalidate_cache() {
+        let (dir, file) = setup("config", "a === b;\n");
+        let config = LintConfig::default();
+
+        let mut cache = LintCache::load(&dir, CacheStrategy::Metadata, &config);
+        cache.set(&LintResult::new(file.clone()));
+        cache.save().unwrap();
+
+        let mut changed = config.clone();
+        changed.no_inline_config = true;
+        assert!(LintCache::load(&dir, CacheStrategy::Metadata, &changed)
+            .get(&file)
+            .is_none());
+        assert!(LintCache::load(&dir, CacheStrategy::Metadata, &config)
+            .get(&file)
+            .is_some());
+
+        LintCache::delete(&dir).unwrap();
+        assert!(!dir.join(DEFAULT_CACHE_LOCATION).exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,47 @@
+use std::{env, path::Path};
+
+use msrlint::files::find_config_file;
+
+/// Information about msrlint and the environment it's running in, for bug reports
+pub fn env_info(cwd: &Path) -> String {
+    let mut lines = vec![
+        String::from("Environment Info:"),
+        String::new(),
+        format!("msrlint version: v{}", env!("CARGO_PKG_VERSION")),
+    ];
+
+    for crate_version in env!("MSRLINT_SWC_VERSIONS")
+        .split(',')
+        .filter(|crate_version| !crate_version.is_empty())
+    {
+        let (name, version) = crate_version.split_once(' ').unwrap();
+        lines.push(format!("{} version: v{}", name, version));
+    }
+
+    lines.push(format!(
+        "Operating System: {} {}",
+        env::consts::OS,
+        env::consts::ARCH
+    ));
+
+    let config_file = find_config_file(cwd)
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|| String::from("Not found"));
+    lines.push(format!("Config file: {}", config_file));
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_info_lists_versions_and_config() {
+        let info = env_info(Path::new("./test/src"));
+
+        assert!(info.contains(&format!("msrlint version: v{}", env!("CARGO_PKG_VERSION"))));
+        assert!(info.contains("swc_ecma_parser version: v0."));
+        assert!(info.contains("Config file: ./test/.eslintrc.json"));
+    }
+}
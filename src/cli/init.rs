@@ -0,0 +1,502 @@
+//! The `--init` wizard, which writes a configuration file for a project
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, BufRead, IsTerminal, Write},
+    path::{Path, PathBuf},
+    process,
+};
+
+use clap::ArgEnum;
+use serde_json::{json, Value};
+
+use msrlint::{
+    files::get_all_files_to_lint,
+    linter::config::{LintConfig, ParserOptions},
+    Linter,
+};
+
+use super::{Cli, ConfigFormat, Framework, ModuleType, QuoteStyle, SemiStyle};
+
+/// The most source files sampled when inferring style, to keep large projects quick
+const MAX_SAMPLE_FILES: usize = 100;
+
+/// Answers to the wizard's questions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Answers {
+    pub module_type: ModuleType,
+    pub framework: Framework,
+    pub typescript: bool,
+    pub quotes: QuoteStyle,
+    pub semi: SemiStyle,
+    pub format: ConfigFormat,
+}
+
+/// What package.json says about a project, used as the default answers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ProjectInfo {
+    module_type: ModuleType,
+    framework: Framework,
+    typescript: bool,
+}
+
+/// Run the wizard in the current directory
+///
+/// Questions answered by command line flags aren't asked. When stdin isn't a terminal, other
+/// questions are given their default answers.
+pub fn run(cli: &Cli) {
+    let dir = Path::new(".");
+    let interactive = io::stdin().is_terminal();
+    let project = inspect_project(dir);
+
+    let module_type = cli.init_module_type.unwrap_or_else(|| {
+        ask(
+            "What type of modules does your project use?",
+            project.module_type,
+            interactive,
+        )
+    });
+    let framework = cli.init_framework.unwrap_or_else(|| {
+        ask(
+            "Which framework does your project use?",
+            project.framework,
+            interactive,
+        )
+    });
+    let typescript = cli.init_typescript.unwrap_or_else(|| {
+        ask_bool(
+            "Does your project use TypeScript?",
+            project.typescript,
+            interactive,
+        )
+    });
+
+    let infer_style = cli.init_infer_style
+        || cli.init_quotes.is_none()
+            && cli.init_semi.is_none()
+            && ask_bool(
+                "Infer quote and semicolon style from your existing source files?",
+                false,
+                interactive,
+            );
+    let (quotes, semi) = if infer_style {
+        let files = sample_files(dir);
+        println!("Inspecting {} file(s)...", files.len());
+        infer_style_from(&files, &parser_options(module_type, framework))
+    } else {
+        let quotes = cli
+            .init_quotes
+            .unwrap_or_else(|| ask("Which quotes do you use?", QuoteStyle::Double, interactive));
+        let semi = cli.init_semi.unwrap_or_else(|| {
+            ask(
+                "Do you end statements with semicolons?",
+                SemiStyle::Always,
+                interactive,
+            )
+        });
+        (quotes, semi)
+    };
+
+    let format = cli.init_format.unwrap_or_else(|| {
+        ask(
+            "What format do you want your config file to be in?",
+            ConfigFormat::Json,
+            interactive,
+        )
+    });
+
+    let answers = Answers {
+        module_type,
+        framework,
+        typescript,
+        quotes,
+        semi,
+        format,
+    };
+
+    match write_config(dir, &answers) {
+        Ok(path) => println!("Successfully created {} file.", path.display()),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(2);
+        }
+    }
+
+    if typescript || framework == Framework::Vue {
+        println!(
+            "Note: msrlint doesn't lint TypeScript or Vue files yet, so only .js and .jsx files will be linted."
+        );
+    }
+}
+
+/// Ask a question with a fixed set of answers, e.g., "(esm/commonjs/script) [esm]"
+fn ask<T: ArgEnum + Copy>(question: &str, default: T, interactive: bool) -> T {
+    let name = |value: &T| value.to_possible_value().unwrap().get_name().to_string();
+    let names: Vec<String> = T::value_variants().iter().map(name).collect();
+
+    prompt(
+        &format!("{} ({})", question, names.join("/")),
+        &name(&default),
+        interactive,
+        |answer| T::from_str(answer, true).ok(),
+    )
+    .unwrap_or(default)
+}
+
+fn ask_bool(question: &str, default: bool, interactive: bool) -> bool {
+    let default_name = if default { "y" } else { "n" };
+
+    prompt(
+        &format!("{} (y/n)", question),
+        default_name,
+        interactive,
+        |answer| match answer.to_lowercase().as_str() {
+            "y" | "yes" => Some(true),
+            "n" | "no" => Some(false),
+            _ => None,
+        },
+    )
+    .unwrap_or(default)
+}
+
+/// Ask until an answer can be parsed, returning None to use the default answer
+fn prompt<T, F>(question: &str, default: &str, interactive: bool, parse: F) -> Option<T>
+where
+    F: Fn(&str) -> Option<T>,
+{
+    if !interactive {
+        return None;
+    }
+
+    let stdin = io::stdin();
+    loop {
+        print!("? {} [{}]: ", question, default);
+        io::stdout().flush().ok()?;
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).ok()? == 0 {
+            return None;
+        }
+
+        match line.trim() {
+            "" => return None,
+            answer => match parse(answer) {
+                Some(value) => return Some(value),
+                None => println!("Please answer with one of the choices in brackets."),
+            },
+        }
+    }
+}
+
+/// Guess answers from the package.json in `dir`, if there is one
+fn inspect_project(dir: &Path) -> ProjectInfo {
+    let package = fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or(Value::Null);
+
+    inspect_package_json(&package)
+}
+
+fn inspect_package_json(package: &Value) -> ProjectInfo {
+    let depends_on = |name: &str| {
+        ["dependencies", "devDependencies", "peerDependencies"]
+            .iter()
+            .any(|key| package[key].get(name).is_some())
+    };
+
+    let module_type = match package["type"].as_str() {
+        Some("commonjs") => ModuleType::Commonjs,
+        Some("module") => ModuleType::Esm,
+        // Node treats packages without a type as CommonJS
+        _ if package.is_object() && !depends_on("react") && !depends_on("vue") => {
+            ModuleType::Commonjs
+        }
+        _ => ModuleType::Esm,
+    };
+
+    let framework = if depends_on("react") {
+        Framework::React
+    } else if depends_on("vue") {
+        Framework::Vue
+    } else {
+        Framework::None
+    };
+
+    ProjectInfo {
+        module_type,
+        framework,
+        typescript: depends_on("typescript"),
+    }
+}
+
+/// Source files to infer style from, skipping dependencies
+fn sample_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = get_all_files_to_lint(vec![dir.to_path_buf()])
+        .files
+        .into_iter()
+        .filter(|file| {
+            !file
+                .components()
+                .any(|component| component.as_os_str() == "node_modules")
+        })
+        .collect();
+    files.sort();
+    files.truncate(MAX_SAMPLE_FILES);
+    files
+}
+
+/// Pick the quote and semicolon style which would report the fewest problems in `files`
+fn infer_style_from(files: &[PathBuf], parser_options: &Value) -> (QuoteStyle, SemiStyle) {
+    let count_problems = |quotes: &str, semi: &str| {
+        let mut lint_config = LintConfig {
+            rules: vec![String::from("eslint:all")],
+            parser_options: serde_json::from_value::<ParserOptions>(parser_options.clone())
+                .unwrap(),
+            ..Default::default()
+        };
+        lint_config
+            .set_rule("quotes", &json!(["error", quotes]))
+            .unwrap();
+        lint_config
+            .set_rule("semi", &json!(["error", semi]))
+            .unwrap();
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for result in Linter::new(lint_config).lint_paths(files) {
+            for diagnostic in result.messages {
+                if let Some(rule_id) = diagnostic.rule_id {
+                    *counts.entry(rule_id).or_default() += 1;
+                }
+            }
+        }
+        counts
+    };
+
+    // Each rule is checked for both of its styles at once, as the rules are independent
+    let single_always = count_problems("single", "always");
+    let double_never = count_problems("double", "never");
+    let count = |counts: &HashMap<String, usize>, rule_id: &str| {
+        counts.get(rule_id).copied().unwrap_or_default()
+    };
+
+    let quotes = if count(&single_always, "quotes") < count(&double_never, "quotes") {
+        QuoteStyle::Single
+    } else {
+        QuoteStyle::Double
+    };
+    let semi = if count(&single_always, "semi") <= count(&double_never, "semi") {
+        SemiStyle::Always
+    } else {
+        SemiStyle::Never
+    };
+
+    (quotes, semi)
+}
+
+fn parser_options(module_type: ModuleType, framework: Framework) -> Value {
+    let source_type = match module_type {
+        ModuleType::Esm => "module",
+        ModuleType::Commonjs => "commonjs",
+        ModuleType::Script => "script",
+    };
+
+    let mut parser_options = json!({
+        "ecmaVersion": "latest",
+        "sourceType": source_type,
+    });
+    if framework == Framework::React {
+        parser_options["ecmaFeatures"] = json!({ "jsx": true });
+    }
+
+    parser_options
+}
+
+/// The configuration for a project, as written to its config file
+///
+/// msrlint only supports extending from eslint:all, so all rules are enabled.
+fn generate_config(answers: &Answers) -> Value {
+    let env = match answers.module_type {
+        ModuleType::Commonjs => json!({ "es2021": true, "node": true }),
+        _ => json!({ "browser": true, "es2021": true }),
+    };
+    let quotes = match answers.quotes {
+        QuoteStyle::Single => "single",
+        QuoteStyle::Double => "double",
+    };
+    let semi = match answers.semi {
+        SemiStyle::Always => "always",
+        SemiStyle::Never => "never",
+    };
+
+    let mut config = json!({
+        "env": env,
+        "extends": ["eslint:all"],
+        "parserOptions": parser_options(answers.module_type, answers.framework),
+        "rules": {
+            "quotes": ["error", quotes],
+            "semi": ["error", semi],
+        },
+    });
+
+    // These are used by ESLint, and ignored by msrlint
+    let mut plugins = Vec::new();
+    if answers.typescript {
+        config["parser"] = json!("@typescript-eslint/parser");
+        plugins.push("@typescript-eslint");
+    }
+    match answers.framework {
+        Framework::React => plugins.push("react"),
+        Framework::Vue => plugins.push("vue"),
+        Framework::None => {}
+    }
+    if !plugins.is_empty() {
+        config["plugins"] = json!(plugins);
+    }
+
+    config
+}
+
+/// Write the config file for `answers` into `dir`, refusing to if it already has one
+fn write_config(dir: &Path, answers: &Answers) -> Result<PathBuf, String> {
+    let config = generate_config(answers);
+    let (file_name, contents) = match answers.format {
+        ConfigFormat::Json => (
+            ".eslintrc.json",
+            serde_json::to_string_pretty(&config).unwrap() + "\n",
+        ),
+        ConfigFormat::Yaml => (".eslintrc.yaml", serde_yaml::to_string(&config).unwrap()),
+    };
+
+    // Another config file would take priority over, or be hidden by, the new one
+    let config_files = [
+        ".eslintrc.js",
+        ".eslintrc.cjs",
+        ".eslintrc.yaml",
+        ".eslintrc.yml",
+        ".eslintrc.json",
+    ];
+    if let Some(existing) = config_files
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.exists())
+    {
+        return Err(format!(
+            "{} already exists. Remove it to create a new configuration.",
+            existing.display()
+        ));
+    }
+
+    let path = dir.join(file_name);
+
+    fs::write(&path, contents)
+        .map_err(|err| format!("Failed to write {}: {}", path.display(), err))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+
+    fn answers(format: ConfigFormat) -> Answers {
+        Answers {
+            module_type: ModuleType::Esm,
+            framework: Framework::React,
+            typescript: false,
+            quotes: QuoteStyle::Single,
+            semi: SemiStyle::Never,
+            format,
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("msrlint-init-{}-{}", name, process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn inspects_package_json() {
+        let package = json!({
+            "type": "module",
+            "devDependencies": { "react": "^17.0.0", "typescript": "^4.5.0" },
+        });
+        assert_eq!(
+            inspect_package_json(&package),
+            ProjectInfo {
+                module_type: ModuleType::Esm,
+                framework: Framework::React,
+                typescript: true,
+            }
+        );
+
+        let package = json!({ "name": "server" });
+        assert_eq!(
+            inspect_package_json(&package).module_type,
+            ModuleType::Commonjs
+        );
+        assert_eq!(
+            inspect_package_json(&Value::Null).framework,
+            Framework::None
+        );
+    }
+
+    #[test]
+    fn generates_config() {
+        let config = generate_config(&answers(ConfigFormat::Json));
+
+        assert_eq!(config["extends"], json!(["eslint:all"]));
+        assert_eq!(config["parserOptions"]["sourceType"], "module");
+        assert_eq!(config["parserOptions"]["ecmaFeatures"]["jsx"], true);
+        assert_eq!(config["rules"]["quotes"], json!(["error", "single"]));
+        assert_eq!(config["plugins"], json!(["react"]));
+    }
+
+    #[test]
+    fn written_configs_can_be_loaded() {
+        for format in [ConfigFormat::Json, ConfigFormat::Yaml] {
+            let dir = temp_dir(&format!("{:?}", format));
+
+            let path = write_config(&dir, &answers(format)).unwrap();
+            let lint_config = LintConfig::try_from(path).unwrap();
+            assert_eq!(
+                lint_config.to_json()["rules"]["quotes"][1]["prefer"],
+                "single"
+            );
+            assert_eq!(
+                lint_config.to_json()["parserOptions"]["sourceType"],
+                "module"
+            );
+
+            // Existing configuration isn't overwritten or hidden, whatever its format
+            assert!(write_config(&dir, &answers(ConfigFormat::Json)).is_err());
+            assert!(write_config(&dir, &answers(ConfigFormat::Yaml)).is_err());
+
+            fs::remove_dir_all(dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn infers_style() {
+        let dir = temp_dir("infer");
+        fs::write(dir.join("a.js"), "var a = 'x';\nfoo('y');\nbar();\n").unwrap();
+
+        let files = sample_files(&dir);
+        let parser_options = parser_options(ModuleType::Esm, Framework::None);
+        assert_eq!(
+            infer_style_from(&files, &parser_options),
+            (QuoteStyle::Single, SemiStyle::Always)
+        );
+
+        fs::write(dir.join("a.js"), "foo(\"y\")\nbar()\n").unwrap();
+        assert_eq!(
+            infer_style_from(&files, &parser_options),
+            (QuoteStyle::Double, SemiStyle::Never)
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
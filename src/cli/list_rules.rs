@@ -0,0 +1,58 @@
+use msrlint::linter::RULES;
+use serde_json::{Map, Value};
+
+/// Every rule msrlint implements, as a table, or as JSON metadata keyed by rule id for the
+/// "json" format
+pub fn list_rules(format: Option<&str>) -> String {
+    if format == Some("json") {
+        let rules: Map<String, Value> = RULES
+            .iter()
+            .map(|meta| (meta.id.to_string(), meta.to_json()))
+            .collect();
+        return serde_json::to_string_pretty(&rules).unwrap();
+    }
+
+    let id_width = RULES.iter().map(|meta| meta.id.len()).max().unwrap_or(0);
+    let mut lines = vec![format!(
+        "{:id_width$}  {:10}  {:11}  {:7}  Description",
+        "Rule", "Type", "Recommended", "Fixable"
+    )];
+
+    for meta in RULES {
+        lines.push(format!(
+            "{:id_width$}  {:10}  {:11}  {:7}  {}",
+            meta.id,
+            meta.rule_type.name(),
+            if meta.recommended { "yes" } else { "" },
+            meta.fixable
+                .map(|fixable| fixable.name())
+                .unwrap_or_default(),
+            meta.description
+        ));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_rules_prints_table_or_json() {
+        let table = list_rules(None);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), RULES.len() + 1);
+        assert!(lines[0].starts_with("Rule "));
+        assert!(lines
+            .iter()
+            .any(|line| line.starts_with("eqeqeq ") && line.contains(" suggestion  ")));
+
+        let json: Value = serde_json::from_str(&list_rules(Some("json"))).unwrap();
+        assert_eq!(json["no-undef"]["type"], "problem");
+        assert_eq!(json["no-undef"]["docs"]["recommended"], true);
+        assert_eq!(json["semi"]["fixable"], "code");
+        assert_eq!(json["no-eval"]["fixable"], Value::Null);
+    }
+}
@@ -1,6 +1,12 @@
 use std::path::PathBuf;
 
-pub use clap::{ArgEnum, Parser};
+pub use clap::{ArgEnum, CommandFactory, ErrorKind, Parser};
+pub use msrlint::cache::CacheStrategy;
+use msrlint::{formatters, linter::fix};
+
+pub mod env_info;
+pub mod init;
+pub mod list_rules;
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ArgEnum)]
 pub enum FixType {
@@ -10,10 +16,50 @@ pub enum FixType {
     Layout,
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ArgEnum)]
-pub enum CacheStrategy {
-    Metadata,
-    Content,
+impl From<FixType> for fix::FixType {
+    fn from(fix_type: FixType) -> Self {
+        match fix_type {
+            FixType::Directive => fix::FixType::Directive,
+            FixType::Problem => fix::FixType::Problem,
+            FixType::Suggestion => fix::FixType::Suggestion,
+            FixType::Layout => fix::FixType::Layout,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ArgEnum)]
+pub enum ModuleType {
+    /// import/export
+    Esm,
+    /// require/exports
+    Commonjs,
+    /// Neither, e.g., scripts loaded by a <script> tag
+    Script,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ArgEnum)]
+pub enum Framework {
+    React,
+    Vue,
+    None,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ArgEnum)]
+pub enum QuoteStyle {
+    Single,
+    Double,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ArgEnum)]
+pub enum SemiStyle {
+    Always,
+    Never,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ArgEnum)]
+pub enum ConfigFormat {
+    Json,
+    Yaml,
 }
 
 /// Find and fix problems in your JavaScript code... but faster.
@@ -31,25 +77,26 @@ pub struct Cli {
     #[clap(short = 'c', long)]
     pub config: Option<String>,
 
-    /// [NYI] Specify environments
-    #[clap(long)]
+    /// Specify environments, e.g., `--env browser,node`
+    #[clap(long, value_delimiter = ',', multiple_occurrences = true)]
     pub env: Option<Vec<String>>,
 
     /// [NYI] Specify JavaScript file extensions
     #[clap(long)]
     pub ext: Option<Vec<String>>,
 
-    /// [NYI] Define global variables
-    #[clap(long)]
+    /// Define global variables, which are read-only unless followed by `:true`, e.g.,
+    /// `--global require,exports:true`
+    #[clap(long, value_delimiter = ',', multiple_occurrences = true)]
     pub global: Option<Vec<String>>,
 
     /// [NYI] Specify the parser to be used
     #[clap(long)]
     pub parser: Option<String>,
 
-    /// [NYI] Specify parser options
-    #[clap(long)]
-    pub parser_options: Option<String>,
+    /// Specify parser options, e.g., ecmaVersion:2018
+    #[clap(long, multiple_occurrences = true)]
+    pub parser_options: Option<Vec<String>>,
 
     /// [NYI] A folder where plugins should be resolved from, CWD by default
     #[clap(long)]
@@ -60,21 +107,21 @@ pub struct Cli {
     #[clap(long)]
     pub plugin: Option<Vec<String>>,
 
-    /// [NYI] Specify rules
-    #[clap(long)]
-    pub rule: Option<String>,
+    /// Specify rules, e.g., `--rule 'quotes: [error, single]'`
+    #[clap(long, multiple_occurrences = true)]
+    pub rule: Option<Vec<String>>,
 
     // ~~~ Fixing problems
-    /// [NYI] Automatically fix problems
+    /// Automatically fix problems
     #[clap(long)]
     pub fix: bool,
 
-    /// [NYI] Automatically fix problems without saving the changes to the file system
-    #[clap(long)]
+    /// Automatically fix problems without saving the changes to the file system
+    #[clap(long, conflicts_with = "fix")]
     pub fix_dry_run: bool,
 
-    /// [NYI] Specify the types of fixes to apply
-    #[clap(arg_enum, long)]
+    /// Specify the types of fixes to apply, e.g., directive,layout
+    #[clap(arg_enum, long, value_delimiter = ',', multiple_occurrences = true)]
     pub fix_type: Option<Vec<FixType>>,
 
     // ~~~ Ignoring files
@@ -113,58 +160,90 @@ pub struct Cli {
     #[clap(long, short = 'o')]
     pub output_file: Option<String>,
 
-    /// [NYI] Use a specific output format
-    #[clap(long, short = 'f')]
+    /// Use a specific output format: codeframe (default), stylish or json
+    #[clap(long, short = 'f', possible_values = formatters::FORMATS)]
     pub format: Option<String>,
 
-    /// [NYI] Force enabling of color
-    #[clap(long)]
+    /// Force enabling of color
+    #[clap(long, overrides_with = "no-color")]
     pub color: bool,
 
-    /// [NYI] Force disabling of color
-    #[clap(long)]
+    /// Force disabling of color
+    #[clap(long, overrides_with = "color")]
     pub no_color: bool,
 
     // ~~~ Inline configuration comments
-    /// [NYI] Prevent comments from changing config or rules
+    /// Prevent comments from changing config or rules
     #[clap(long)]
     pub no_inline_config: bool,
 
-    /// [NYI] Adds reported errors for unused eslint-disable directives
+    /// Adds reported errors for unused eslint-disable directives
     #[clap(long)]
     pub report_unused_disable_directives: bool,
 
     // ~~~ Caching
-    /// [NYI] Only check changed files
+    /// Only check changed files
     #[clap(long)]
     pub cache: bool,
 
-    /// [NYI] Path to the cache file or directory
+    /// Path to the cache file or directory, .msrlintcache by default
     #[clap(long)]
     pub cache_location: Option<String>,
 
-    /// [NYI] Strategy to use for detecting changed files in the cache
+    /// Strategy to use for detecting changed files in the cache, metadata by default
     #[clap(arg_enum, long)]
     pub cache_strategy: Option<CacheStrategy>,
 
     // ~~~ Miscellaneous
-    /// [NYI] Run config initialization wizard
+    /// Run config initialization wizard
     #[clap(long)]
     pub init: bool,
 
-    /// [NYI] Output execution environment information
+    /// Answer the --init wizard's question about which modules your code uses
+    #[clap(arg_enum, long, requires = "init")]
+    pub init_module_type: Option<ModuleType>,
+
+    /// Answer the --init wizard's question about which framework your project uses
+    #[clap(arg_enum, long, requires = "init")]
+    pub init_framework: Option<Framework>,
+
+    /// Answer the --init wizard's question about whether your project uses TypeScript
+    #[clap(long, requires = "init")]
+    pub init_typescript: Option<bool>,
+
+    /// Make the --init wizard infer quote and semicolon style from existing source files
+    #[clap(long, requires = "init", conflicts_with_all = &["init-quotes", "init-semi"])]
+    pub init_infer_style: bool,
+
+    /// Answer the --init wizard's question about which quotes to use
+    #[clap(arg_enum, long, requires = "init")]
+    pub init_quotes: Option<QuoteStyle>,
+
+    /// Answer the --init wizard's question about whether to use semicolons
+    #[clap(arg_enum, long, requires = "init")]
+    pub init_semi: Option<SemiStyle>,
+
+    /// Answer the --init wizard's question about which format to write configuration in
+    #[clap(arg_enum, long, requires = "init")]
+    pub init_format: Option<ConfigFormat>,
+
+    /// Output execution environment information
     #[clap(long)]
     pub env_info: bool,
 
+    /// List the rules msrlint implements, or their metadata with `--format json`
+    #[clap(long)]
+    pub list_rules: bool,
+
     /// [NYI] Prevent errors when pattern is unmatched
     #[clap(long)]
     pub no_error_on_unmatched_pattern: bool,
 
-    /// [NYI] Exit with exit code 2 in case of fatal error
+    /// Exit with exit code 2 in case of fatal error
     #[clap(long)]
     pub exit_on_fatal_error: bool,
 
-    /// [NYI] Output debugging information
+    /// Output debugging information
     #[clap(long)]
     pub debug: bool,
 
@@ -172,11 +251,18 @@ pub struct Cli {
     #[clap(short = 'v', long)]
     pub version: bool,
 
-    /// [NYI] Print the configuration for the given file
+    /// Number of threads to lint with, 0 to use one per CPU
+    #[clap(short = 'j', long, default_value_t = 0)]
+    pub threads: usize,
+
+    /// Print the configuration for the given file
     #[clap(long)]
     pub print_config: Option<String>,
 
     /// File(s) to lint
-    #[clap(required = true, parse(from_os_str))]
+    #[clap(
+        required_unless_present_any = &["print-config", "init", "env-info", "list-rules"],
+        parse(from_os_str)
+    )]
     pub files: Vec<PathBuf>,
 }
@@ -0,0 +1,31 @@
+//! Debugging output for `--debug`, written to stderr
+//!
+//! Messages are prefixed with a namespace for the part of msrlint they come from, e.g.,
+//! `msrlint:config Loading config file ./.eslintrc.json`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Start writing debugging output
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Write a debugging message to stderr, if `--debug` is enabled
+///
+/// ```
+/// msrlint::debug!("files", "Ignoring {}", "a.ts");
+/// ```
+#[macro_export]
+macro_rules! debug {
+    ($namespace:expr, $($arg:tt)*) => {
+        if $crate::debug::enabled() {
+            eprintln!("msrlint:{} {}", $namespace, format_args!($($arg)*));
+        }
+    };
+}
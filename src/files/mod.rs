@@ -1,7 +1,9 @@
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
+use crate::debug;
+
 pub struct Files {
     pub files: HashSet<PathBuf>,
     pub config: PathBuf,
@@ -27,16 +29,22 @@ pub fn get_all_files_to_lint(paths: Vec<PathBuf>) -> Files {
                     // We can't lint directories, so only lint files
                     if !path_buf.is_dir() {
                         // If extension is valid, use it
-                        if let Some(extension) = path_buf.extension() {
-                            if extension == "js" || extension == "jsx" {
+                        match path_buf.extension() {
+                            Some(extension) if extension == "js" || extension == "jsx" => {
                                 to_lint.insert(path_buf);
                             }
+                            _ => debug!(
+                                "files",
+                                "Ignoring {} (not a .js or .jsx file)",
+                                path_buf.display()
+                            ),
                         }
                     } else if cfg_file == PathBuf::new() {
                         // Obtain config file
                         let cf = get_config_file(None, path_buf);
 
                         if let Some(cfg) = cf {
+                            debug!("files", "Found config file {}", cfg.display());
                             cfg_file = cfg
                         }
                     }
@@ -52,6 +60,15 @@ pub fn get_all_files_to_lint(paths: Vec<PathBuf>) -> Files {
     }
 }
 
+/// Find the configuration file for a file, in its directory or the closest parent directory
+/// with one
+pub fn find_config_file(path: &Path) -> Option<PathBuf> {
+    let dir = if path.is_dir() { path } else { path.parent()? };
+
+    dir.ancestors()
+        .find_map(|dir| get_config_file(None, dir.to_path_buf()))
+}
+
 pub fn get_config_file(config: Option<String>, root: PathBuf) -> Option<PathBuf> {
     match config {
         Some(str) => {
@@ -124,6 +141,18 @@ mod tests {
         assert_eq!(get_all_files_to_lint(input).files, expected_output);
     }
 
+    #[test]
+    fn config_for_nested_file() {
+        assert_eq!(
+            find_config_file(Path::new("./test/src/myTestDir/another.js")),
+            Some(PathBuf::from("./test/.eslintrc.json"))
+        );
+        assert_eq!(
+            find_config_file(Path::new("./test")),
+            Some(PathBuf::from("./test/.eslintrc.json"))
+        );
+    }
+
     #[test]
     fn multiple_input() {
         let input = vec![
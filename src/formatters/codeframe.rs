@@ -0,0 +1,58 @@
+use swc_common::{
+    errors::{ColorConfig, DiagnosticId, Handler},
+    sync::Lrc,
+    SourceMapperDyn, DUMMY_SP,
+};
+
+use crate::linter::{
+    diagnostic::{Diagnostic, Severity},
+    LintResult,
+};
+
+/// Print each problem with the code it was found in, to stderr
+pub fn print(results: &[LintResult]) {
+    for result in results {
+        let source_map = result
+            .source_map
+            .clone()
+            .map(|cm| cm as Lrc<SourceMapperDyn>);
+        let color_config = match super::color() {
+            true => ColorConfig::Always,
+            false => ColorConfig::Never,
+        };
+        let handler = Handler::with_tty_emitter(color_config, true, false, source_map);
+
+        for diagnostic in &result.messages {
+            let message = diagnostic.message.as_str();
+
+            let mut builder = match (diagnostic.severity, diagnostic.span == DUMMY_SP) {
+                // Without a location there is no code to show, so name the file instead
+                (Severity::Warn, true) => handler.struct_warn(&located(result, diagnostic)),
+                (_, true) => handler.struct_err(&located(result, diagnostic)),
+                (Severity::Warn, false) => handler.struct_span_warn(diagnostic.span, message),
+                (_, false) => handler.struct_span_err(diagnostic.span, message),
+            };
+
+            // Shown as e.g., "error[eqeqeq]: ..."
+            if let Some(rule_id) = &diagnostic.rule_id {
+                builder.code(DiagnosticId::Error(rule_id.clone()));
+            }
+
+            builder.emit();
+        }
+    }
+}
+
+/// Prefix a message with its file, and line and column if known, e.g., for cached results
+fn located(result: &LintResult, diagnostic: &Diagnostic) -> String {
+    match diagnostic.line {
+        0 => format!("{}: {}", result.file_path.display(), diagnostic.message),
+        line => format!(
+            "{}:{}:{}: {}",
+            result.file_path.display(),
+            line,
+            diagnostic.column,
+            diagnostic.message
+        ),
+    }
+}
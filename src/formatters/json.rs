@@ -0,0 +1,6 @@
+use crate::linter::LintResult;
+
+/// Results as JSON, matching ESLint's `json` formatter
+pub fn format(results: &[LintResult]) -> String {
+    serde_json::to_string(results).unwrap()
+}
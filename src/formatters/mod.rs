@@ -0,0 +1,103 @@
+mod codeframe;
+mod json;
+mod stylish;
+
+use std::io::{self, IsTerminal};
+
+use crate::linter::LintResult;
+
+/// Names of the output formats accepted by `--format`
+pub const FORMATS: [&str; 3] = ["codeframe", "stylish", "json"];
+
+/// Decide whether output is colored, from `--color`/`--no-color`, then the `NO_COLOR` and
+/// `FORCE_COLOR` environment variables, then whether stdout is a terminal
+pub fn use_color(flag: Option<bool>) -> bool {
+    color_enabled(
+        flag,
+        |name| std::env::var(name).ok(),
+        io::stdout().is_terminal(),
+    )
+}
+
+fn color_enabled<F>(flag: Option<bool>, var: F, is_terminal: bool) -> bool
+where
+    F: Fn(&str) -> Option<String>,
+{
+    if let Some(enabled) = flag {
+        return enabled;
+    }
+
+    // See https://no-color.org and https://force-color.org
+    if var("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+        return false;
+    }
+    match var("FORCE_COLOR").as_deref() {
+        Some("0" | "false") => false,
+        Some(_) => true,
+        None => is_terminal,
+    }
+}
+
+/// Color or don't color all following output, including the summary
+pub fn set_color(enabled: bool) {
+    colored::control::set_override(enabled);
+}
+
+/// Whether output is currently colored, as set by [set_color]
+fn color() -> bool {
+    colored::control::SHOULD_COLORIZE.should_colorize()
+}
+
+/// Print results in the given format, defaulting to code frames
+///
+/// # Panics
+///
+/// Panics if the format isn't one of [FORMATS], which the CLI checks while parsing `--format`
+pub fn print_results(format: Option<&str>, results: &[LintResult]) {
+    match format {
+        None | Some("codeframe") => codeframe::print(results),
+        Some("stylish") => print!("{}", stylish::format(results)),
+        Some("json") => println!("{}", json::format(results)),
+        Some(format) => panic!(
+            "Unknown format {:?}. Please use one of {:?} instead.",
+            format, FORMATS
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn color_decision() {
+        assert!(color_enabled(None, env(&[]), true));
+        assert!(!color_enabled(None, env(&[]), false));
+
+        assert!(!color_enabled(None, env(&[("NO_COLOR", "1")]), true));
+        assert!(color_enabled(None, env(&[("NO_COLOR", "")]), true));
+        assert!(color_enabled(None, env(&[("FORCE_COLOR", "1")]), false));
+        assert!(!color_enabled(None, env(&[("FORCE_COLOR", "0")]), true));
+        assert!(!color_enabled(
+            None,
+            env(&[("NO_COLOR", "1"), ("FORCE_COLOR", "1")]),
+            true
+        ));
+
+        // Flags beat the environment
+        assert!(color_enabled(Some(true), env(&[("NO_COLOR", "1")]), false));
+        assert!(!color_enabled(
+            Some(false),
+            env(&[("FORCE_COLOR", "1")]),
+            true
+        ));
+    }
+}
@@ -0,0 +1,77 @@
+use colored::*;
+
+use crate::linter::{diagnostic::Severity, LintResult};
+
+/// A table of problems per file, matching ESLint's `stylish` formatter
+pub fn format(results: &[LintResult]) -> String {
+    let mut output = String::new();
+
+    for result in results.iter().filter(|result| !result.messages.is_empty()) {
+        output += &format!("\n{}\n", result.file_path.display().to_string().underline());
+
+        let rows: Vec<(String, ColoredString, &str, &str)> = result
+            .messages
+            .iter()
+            .map(|diagnostic| {
+                let severity = match diagnostic.severity {
+                    Severity::Warn => "warning".yellow(),
+                    _ => "error".red(),
+                };
+
+                (
+                    format!("{}:{}", diagnostic.line, diagnostic.column),
+                    severity,
+                    diagnostic.message.as_str(),
+                    diagnostic.rule_id.as_deref().unwrap_or(""),
+                )
+            })
+            .collect();
+
+        // Align columns to the widest value in each
+        let location_width = rows.iter().map(|row| row.0.len()).max().unwrap_or(0);
+        let severity_width = rows.iter().map(|row| row.1.len()).max().unwrap_or(0);
+        let message_width = rows.iter().map(|row| row.2.len()).max().unwrap_or(0);
+
+        for (location, severity, message, rule_id) in rows {
+            let row = format!(
+                "  {}  {}{}  {:message_width$}  {}",
+                format!("{:location_width$}", location).dimmed(),
+                severity,
+                " ".repeat(severity_width - severity.len()),
+                message,
+                rule_id.dimmed(),
+            );
+            output += row.trim_end();
+            output += "\n";
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{path::PathBuf, sync::Arc};
+
+    use swc_common::{FileName, SourceMap, Span};
+
+    use crate::linter::diagnostic::Diagnostic;
+
+    #[test]
+    fn stylish_table() {
+        colored::control::set_override(false);
+
+        let cm: Arc<SourceMap> = Default::default();
+        let file = cm.new_source_file(FileName::Anon, String::from("a == b"));
+        let span = Span::new(file.start_pos, file.end_pos, Default::default());
+
+        let mut result = LintResult::new(PathBuf::from("a.js"));
+        let mut diagnostic = Diagnostic::new(&cm, span, Severity::Warn, String::from("Bad"));
+        diagnostic.rule_id = Some(String::from("eqeqeq"));
+        result.add(diagnostic);
+
+        assert_eq!(format(&[result]), "\na.js\n  1:1  warning  Bad  eqeqeq\n");
+    }
+}
@@ -0,0 +1,29 @@
+//! ⏩ MSRLint, a (WIP) Rust-based replacement to ESLint.
+//!
+//! Resolve a configuration with [`LintConfig`](linter::config::LintConfig), find files with
+//! [`get_all_files_to_lint`](files::get_all_files_to_lint), then lint them with a [`Linter`].
+//!
+//! ```no_run
+//! use std::path::{Path, PathBuf};
+//!
+//! use msrlint::{files::get_all_files_to_lint, linter::config::LintConfig, Linter};
+//!
+//! let input = get_all_files_to_lint(vec![PathBuf::from("./src")]);
+//! let linter = Linter::new(LintConfig::try_from(input.config).unwrap());
+//!
+//! let result = linter.lint_text("eval(code)", Path::new("inline.js"));
+//! for diagnostic in &result.messages {
+//!     println!("{}:{} {}", diagnostic.line, diagnostic.column, diagnostic.message);
+//! }
+//! ```
+
+pub mod cache;
+pub mod debug;
+pub mod files;
+pub mod formatters;
+pub mod linter;
+
+pub use linter::{
+    diagnostic::{Diagnostic, Severity},
+    LintResult, Linter,
+};
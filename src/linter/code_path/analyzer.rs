@@ -0,0 +1,475 @@
+use swc_atoms::JsWord;
+use swc_common::{Span, Spanned};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+
+use super::{CodePath, CodePathAnalysis, CodePathId, CodePathKind, Segment, SegmentId};
+
+/// Split a program and its functions into segments
+pub(super) fn analyze(program: &Program) -> CodePathAnalysis {
+    let mut analyzer = Analyzer {
+        analysis: CodePathAnalysis::default(),
+        path: 0,
+        current: 0,
+        contexts: Vec::new(),
+    };
+
+    analyzer.start_path(CodePathKind::Program, program.span());
+    program.visit_children_with(&mut analyzer);
+    analyzer.end_path();
+
+    analyzer.analysis
+}
+
+/// A statement which `break` or `continue` can jump out of
+struct JumpContext {
+    label: Option<JsWord>,
+    /// Whether `break` and `continue` without a label jump out of it, i.e., a loop
+    is_loop: bool,
+    /// Whether unlabeled `break` jumps out of it, i.e., a loop or switch
+    is_breakable: bool,
+    /// Segments which end with a `break` to the end of the statement
+    breaks: Vec<SegmentId>,
+    /// The segment `continue` jumps to, if it has been created yet
+    continue_target: Option<SegmentId>,
+    /// Segments which end with a `continue`, when the target is created after them
+    continues: Vec<SegmentId>,
+}
+
+impl JumpContext {
+    fn new(label: Option<JsWord>, is_loop: bool, is_breakable: bool) -> Self {
+        Self {
+            label,
+            is_loop,
+            is_breakable,
+            breaks: Vec::new(),
+            continue_target: None,
+            continues: Vec::new(),
+        }
+    }
+}
+
+struct Analyzer {
+    analysis: CodePathAnalysis,
+    path: CodePathId,
+    /// The segment the statement being visited runs in
+    current: SegmentId,
+    /// Statements which can be jumped out of, innermost last
+    contexts: Vec<JumpContext>,
+}
+
+impl Analyzer {
+    /// Add a segment following some segments, reachable if any of them is
+    fn new_segment(&mut self, prev: &[SegmentId]) -> SegmentId {
+        let id = self.analysis.segments.len();
+        let reachable = prev
+            .iter()
+            .any(|prev| self.analysis.segments[*prev].reachable);
+
+        self.analysis.segments.push(Segment {
+            path: self.path,
+            prev: prev.to_vec(),
+            next: Vec::new(),
+            reachable,
+        });
+        for prev in prev {
+            self.analysis.segments[*prev].next.push(id);
+        }
+        id
+    }
+
+    /// Connect a segment to one created before it, e.g., the end of a loop to its start
+    ///
+    /// The segment's reachability doesn't change, since a loop can only be entered from before
+    /// it.
+    fn add_edge(&mut self, from: SegmentId, to: SegmentId) {
+        self.analysis.segments[from].next.push(to);
+        self.analysis.segments[to].prev.push(from);
+    }
+
+    /// Continue in a new segment which nothing leads to, e.g., after `return`
+    fn leave_unreachable(&mut self) {
+        self.current = self.new_segment(&[]);
+    }
+
+    /// Start a code path, returning the state to go back to once it ends
+    fn start_path(
+        &mut self,
+        kind: CodePathKind,
+        span: Span,
+    ) -> (CodePathId, SegmentId, Vec<JumpContext>) {
+        let id = self.analysis.paths.len();
+        let parent = (id > 0).then_some(self.path);
+        let previous = (
+            std::mem::replace(&mut self.path, id),
+            self.current,
+            std::mem::take(&mut self.contexts),
+        );
+
+        let initial_segment = self.analysis.segments.len();
+        self.analysis.segments.push(Segment {
+            path: id,
+            prev: Vec::new(),
+            next: Vec::new(),
+            reachable: true,
+        });
+        self.current = initial_segment;
+
+        self.analysis.paths.push(CodePath {
+            kind,
+            span,
+            parent,
+            initial_segment,
+            final_segment: initial_segment,
+            returned_segments: Vec::new(),
+            thrown_segments: Vec::new(),
+        });
+        self.analysis.path_at.insert(span.lo, id);
+        previous
+    }
+
+    fn end_path(&mut self) {
+        self.analysis.paths[self.path].final_segment = self.current;
+    }
+
+    /// Visit the body of a function-like node in a code path of its own
+    fn visit_path(&mut self, kind: CodePathKind, span: Span, visit: impl FnOnce(&mut Self)) {
+        let previous = self.start_path(kind, span);
+        visit(self);
+        self.end_path();
+
+        (self.path, self.current, self.contexts) = previous;
+    }
+
+    /// The jump context a `break` or `continue` statement leaves
+    fn jump_target(
+        &mut self,
+        label: &Option<Ident>,
+        is_continue: bool,
+    ) -> Option<&mut JumpContext> {
+        self.contexts.iter_mut().rev().find(|context| match label {
+            Some(label) => context.label.as_ref() == Some(&label.sym),
+            None if is_continue => context.is_loop,
+            None => context.is_breakable,
+        })
+    }
+
+    /// Visit a loop's body with a jump context, which is labeled if the loop is
+    fn visit_loop_body(
+        &mut self,
+        body: &Stmt,
+        label: Option<JsWord>,
+        continue_target: Option<SegmentId>,
+    ) -> JumpContext {
+        let mut context = JumpContext::new(label, true, true);
+        context.continue_target = continue_target;
+
+        self.contexts.push(context);
+        body.visit_with(self);
+        self.contexts.pop().unwrap()
+    }
+
+    /// Visit a loop, given the label of a labeled statement around it
+    fn visit_loop(&mut self, stmt: &Stmt, label: Option<JsWord>) {
+        match stmt {
+            Stmt::While(while_stmt) => {
+                let head = self.new_segment(&[self.current]);
+                self.current = head;
+                while_stmt.test.visit_with(self);
+                let test = self.current;
+
+                self.current = self.new_segment(&[test]);
+                let context = self.visit_loop_body(&while_stmt.body, label, Some(head));
+                self.add_edge(self.current, head);
+
+                self.exit_loop(
+                    context,
+                    (!is_constant_true(&while_stmt.test)).then_some(test),
+                );
+            }
+            Stmt::DoWhile(do_while) => {
+                let body = self.new_segment(&[self.current]);
+                self.current = body;
+                let context = self.visit_loop_body(&do_while.body, label, None);
+
+                let mut prev = vec![self.current];
+                prev.extend(&context.continues);
+                self.current = self.new_segment(&prev);
+                do_while.test.visit_with(self);
+                let test = self.current;
+                self.add_edge(test, body);
+
+                self.exit_loop(context, (!is_constant_true(&do_while.test)).then_some(test));
+            }
+            Stmt::For(for_stmt) => {
+                for_stmt.init.visit_with(self);
+
+                let head = self.new_segment(&[self.current]);
+                self.current = head;
+                for_stmt.test.visit_with(self);
+                let test = self.current;
+
+                self.current = self.new_segment(&[test]);
+                let context = self.visit_loop_body(&for_stmt.body, label, None);
+
+                let mut prev = vec![self.current];
+                prev.extend(&context.continues);
+                self.current = self.new_segment(&prev);
+                for_stmt.update.visit_with(self);
+                self.add_edge(self.current, head);
+
+                let exits = match &for_stmt.test {
+                    Some(test_expr) if !is_constant_true(test_expr) => Some(test),
+                    _ => None,
+                };
+                self.exit_loop(context, exits);
+            }
+            Stmt::ForIn(ForInStmt {
+                left, right, body, ..
+            })
+            | Stmt::ForOf(ForOfStmt {
+                left, right, body, ..
+            }) => {
+                right.visit_with(self);
+
+                let head = self.new_segment(&[self.current]);
+                self.current = head;
+                left.visit_with(self);
+
+                self.current = self.new_segment(&[head]);
+                let context = self.visit_loop_body(body, label, Some(head));
+                self.add_edge(self.current, head);
+
+                self.exit_loop(context, Some(head));
+            }
+            _ => unreachable!("not a loop"),
+        }
+    }
+
+    /// Continue after a loop, which ends when its test is false or it is broken out of
+    fn exit_loop(&mut self, context: JumpContext, test: Option<SegmentId>) {
+        let mut prev: Vec<_> = test.into_iter().collect();
+        prev.extend(&context.breaks);
+        self.current = self.new_segment(&prev);
+    }
+
+    fn visit_switch(&mut self, switch: &SwitchStmt, label: Option<JsWord>) {
+        switch.discriminant.visit_with(self);
+        let discriminant = self.current;
+
+        self.contexts.push(JumpContext::new(label, false, true));
+        // The end of the previous case, which falls through to the next one
+        let mut fallthrough = None;
+        for case in &switch.cases {
+            case.test.visit_with(self);
+
+            let mut prev = vec![discriminant];
+            prev.extend(fallthrough);
+            self.current = self.new_segment(&prev);
+            case.cons.visit_with(self);
+
+            self.analysis.node_end.insert(case.span, self.current);
+            fallthrough = Some(self.current);
+        }
+        let context = self.contexts.pop().unwrap();
+
+        let mut prev: Vec<_> = fallthrough.into_iter().collect();
+        prev.extend(context.breaks);
+        // Without a default case, no case may match
+        if !switch.cases.iter().any(|case| case.test.is_none()) {
+            prev.push(discriminant);
+        }
+        self.current = self.new_segment(&prev);
+    }
+
+    fn visit_try(&mut self, try_stmt: &TryStmt) {
+        let start = self.current;
+        let first_segment = self.analysis.segments.len();
+        try_stmt.block.visit_with(self);
+
+        // Any statement in the block may throw, so the handler may start after any segment
+        let mut ends = vec![self.current];
+        if let Some(handler) = &try_stmt.handler {
+            let mut prev = vec![start];
+            prev.extend(first_segment..self.analysis.segments.len());
+            self.current = self.new_segment(&prev);
+            handler.visit_with(self);
+            ends.push(self.current);
+        }
+
+        let completes = ends
+            .iter()
+            .any(|segment| self.analysis.segments[*segment].reachable);
+        match &try_stmt.finalizer {
+            Some(finalizer) => {
+                // The finalizer also runs after jumps and exceptions, which continue after it
+                let mut prev = ends;
+                prev.push(start);
+                self.current = self.new_segment(&prev);
+                finalizer.visit_with(self);
+
+                if !completes {
+                    self.leave_unreachable();
+                }
+            }
+            None => self.current = self.new_segment(&ends),
+        }
+    }
+}
+
+/// Whether a loop's test is always true, so it only ends by jumping out of it
+fn is_constant_true(test: &Expr) -> bool {
+    match test {
+        Expr::Lit(Lit::Bool(value)) => value.value,
+        Expr::Lit(Lit::Num(number)) => number.value != 0.0 && !number.value.is_nan(),
+        Expr::Lit(Lit::Str(string)) => !string.value.is_empty(),
+        Expr::Paren(paren) => is_constant_true(&paren.expr),
+        _ => false,
+    }
+}
+
+impl Visit for Analyzer {
+    noop_visit_type!();
+
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        self.analysis.stmt_start.insert(stmt.span(), self.current);
+
+        match stmt {
+            Stmt::Return(return_stmt) => {
+                return_stmt.arg.visit_with(self);
+                self.analysis.paths[self.path]
+                    .returned_segments
+                    .push(self.current);
+                self.leave_unreachable();
+            }
+            Stmt::Throw(throw) => {
+                throw.arg.visit_with(self);
+                self.analysis.paths[self.path]
+                    .thrown_segments
+                    .push(self.current);
+                self.leave_unreachable();
+            }
+            Stmt::Break(break_stmt) => {
+                let current = self.current;
+                if let Some(context) = self.jump_target(&break_stmt.label, false) {
+                    context.breaks.push(current);
+                }
+                self.leave_unreachable();
+            }
+            Stmt::Continue(continue_stmt) => {
+                let current = self.current;
+                let target = self.jump_target(&continue_stmt.label, true).map(|context| {
+                    match context.continue_target {
+                        Some(target) => Some(target),
+                        None => {
+                            context.continues.push(current);
+                            None
+                        }
+                    }
+                });
+                if let Some(Some(target)) = target {
+                    self.add_edge(current, target);
+                }
+                self.leave_unreachable();
+            }
+            Stmt::If(if_stmt) => {
+                if_stmt.test.visit_with(self);
+                let test = self.current;
+
+                self.current = self.new_segment(&[test]);
+                if_stmt.cons.visit_with(self);
+                let consequent = self.current;
+
+                let alternate = match &if_stmt.alt {
+                    Some(alt) => {
+                        self.current = self.new_segment(&[test]);
+                        alt.visit_with(self);
+                        self.current
+                    }
+                    None => test,
+                };
+                self.current = self.new_segment(&[consequent, alternate]);
+            }
+            Stmt::While(_) | Stmt::DoWhile(_) | Stmt::For(_) | Stmt::ForIn(_) | Stmt::ForOf(_) => {
+                self.visit_loop(stmt, None)
+            }
+            Stmt::Switch(switch) => self.visit_switch(switch, None),
+            Stmt::Try(try_stmt) => self.visit_try(try_stmt),
+            Stmt::Labeled(labeled) => {
+                let label = Some(labeled.label.sym.clone());
+                match &*labeled.body {
+                    body @ (Stmt::While(_)
+                    | Stmt::DoWhile(_)
+                    | Stmt::For(_)
+                    | Stmt::ForIn(_)
+                    | Stmt::ForOf(_)) => {
+                        self.analysis.stmt_start.insert(body.span(), self.current);
+                        self.visit_loop(body, label);
+                    }
+                    Stmt::Switch(switch) => {
+                        self.analysis.stmt_start.insert(switch.span, self.current);
+                        self.visit_switch(switch, label);
+                    }
+                    body => {
+                        self.contexts.push(JumpContext::new(label, false, false));
+                        body.visit_with(self);
+                        let context = self.contexts.pop().unwrap();
+
+                        let mut prev = vec![self.current];
+                        prev.extend(context.breaks);
+                        self.current = self.new_segment(&prev);
+                    }
+                }
+            }
+            stmt => stmt.visit_children_with(self),
+        }
+
+        self.analysis.node_end.insert(stmt.span(), self.current);
+    }
+
+    // ~~~ Code paths of their own
+
+    fn visit_function(&mut self, function: &Function) {
+        function.decorators.visit_with(self);
+        self.visit_path(CodePathKind::Function, function.span, |analyzer| {
+            function.params.visit_with(analyzer);
+            function.body.visit_with(analyzer);
+        });
+    }
+
+    fn visit_arrow_expr(&mut self, arrow: &ArrowExpr) {
+        self.visit_path(CodePathKind::Function, arrow.span, |analyzer| {
+            arrow.params.visit_with(analyzer);
+            arrow.body.visit_with(analyzer);
+        });
+    }
+
+    fn visit_constructor(&mut self, constructor: &Constructor) {
+        constructor.key.visit_with(self);
+        self.visit_path(CodePathKind::Function, constructor.span, |analyzer| {
+            constructor.params.visit_with(analyzer);
+            constructor.body.visit_with(analyzer);
+        });
+    }
+
+    fn visit_getter_prop(&mut self, getter: &GetterProp) {
+        getter.key.visit_with(self);
+        self.visit_path(CodePathKind::Function, getter.span, |analyzer| {
+            getter.body.visit_with(analyzer)
+        });
+    }
+
+    fn visit_setter_prop(&mut self, setter: &SetterProp) {
+        setter.key.visit_with(self);
+        self.visit_path(CodePathKind::Function, setter.span, |analyzer| {
+            setter.param.visit_with(analyzer);
+            setter.body.visit_with(analyzer);
+        });
+    }
+
+    fn visit_static_block(&mut self, block: &StaticBlock) {
+        self.visit_path(CodePathKind::ClassStaticBlock, block.span, |analyzer| {
+            block.body.visit_with(analyzer)
+        });
+    }
+}
@@ -0,0 +1,251 @@
+//! Code path analysis, shared with rules through [LintContext](super::rules::LintContext)
+//!
+//! Like ESLint's code path analysis, the statements of the program and of every function are
+//! split into segments, which run from start to end unless an exception is thrown. Segments are
+//! connected by branches, loops, `try` statements and jumps like `break`, and a segment which no
+//! path leads to, e.g., after a `return` statement, is unreachable.
+//!
+//! Segments are only split by statements, not by expressions like `a && b()`.
+
+use std::collections::HashMap;
+
+use swc_common::{BytePos, Span};
+use swc_ecma_ast::Program;
+
+mod analyzer;
+
+/// Index of a code path in [CodePathAnalysis::paths]
+pub type CodePathId = usize;
+/// Index of a segment in [CodePathAnalysis::segments]
+pub type SegmentId = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodePathKind {
+    /// The top level of the file
+    Program,
+    /// A function, arrow function, method, getter, setter or constructor
+    Function,
+    ClassStaticBlock,
+}
+
+#[derive(Debug, Clone)]
+pub struct CodePath {
+    pub kind: CodePathKind,
+    /// The span of the program, function or static block
+    pub span: Span,
+    /// The code path of the enclosing function or program
+    pub parent: Option<CodePathId>,
+    pub initial_segment: SegmentId,
+    /// The segment at the end of the body, which is reachable if the function can complete
+    /// without a `return` or `throw` statement
+    pub final_segment: SegmentId,
+    /// Segments which end with a `return` statement
+    pub returned_segments: Vec<SegmentId>,
+    /// Segments which end with a `throw` statement
+    pub thrown_segments: Vec<SegmentId>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Segment {
+    pub path: CodePathId,
+    /// Segments which may run immediately before this one
+    pub prev: Vec<SegmentId>,
+    /// Segments which may run immediately after this one
+    pub next: Vec<SegmentId>,
+    /// Whether any path from the start of the code path leads to the segment
+    pub reachable: bool,
+}
+
+/// The code paths and segments of a file
+#[derive(Debug, Clone, Default)]
+pub struct CodePathAnalysis {
+    /// Every code path, starting with the program's
+    pub paths: Vec<CodePath>,
+    pub segments: Vec<Segment>,
+    /// Code paths by the start of the function or static block they belong to
+    path_at: HashMap<BytePos, CodePathId>,
+    /// The segment each statement starts in
+    stmt_start: HashMap<Span, SegmentId>,
+    /// The segment each statement or switch case ends in, if it completes normally
+    node_end: HashMap<Span, SegmentId>,
+}
+
+impl CodePathAnalysis {
+    pub fn analyze(program: &Program) -> Self {
+        analyzer::analyze(program)
+    }
+
+    /// The code path of a function, arrow function, getter, setter, constructor or static block,
+    /// by its span
+    pub fn code_path(&self, span: Span) -> Option<&CodePath> {
+        self.path_at.get(&span.lo).map(|id| &self.paths[*id])
+    }
+
+    /// Whether a statement can be run, by its span
+    ///
+    /// Statements which weren't analyzed, e.g., in TypeScript declarations, are reachable.
+    pub fn is_reachable(&self, stmt: Span) -> bool {
+        self.stmt_start
+            .get(&stmt)
+            .is_none_or(|segment| self.segments[*segment].reachable)
+    }
+
+    /// Whether the end of a statement or switch case can be reached, so the code after it runs
+    /// next, e.g., a case which falls through to the next case
+    pub fn completes_normally(&self, node: Span) -> bool {
+        self.node_end
+            .get(&node)
+            .is_none_or(|segment| self.segments[*segment].reachable)
+    }
+
+    /// Whether a code path can reach the end of its body, e.g., a function which doesn't always
+    /// return a value
+    pub fn falls_through(&self, path: &CodePath) -> bool {
+        self.segments[path.final_segment].reachable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use swc_common::{FileName, SourceMap, Spanned};
+    use swc_ecma_ast::{EsVersion, Function, Stmt};
+    use swc_ecma_parser::{parse_file_as_program, Syntax};
+    use swc_ecma_visit::{Visit, VisitWith};
+
+    fn analyze(source: &str) -> (Program, CodePathAnalysis) {
+        let cm: Arc<SourceMap> = Default::default();
+        let file = cm.new_source_file(FileName::Anon, source.to_string());
+        let program = parse_file_as_program(
+            &file,
+            Syntax::Es(Default::default()),
+            EsVersion::latest(),
+            None,
+            &mut Vec::new(),
+        )
+        .unwrap();
+
+        let analysis = CodePathAnalysis::analyze(&program);
+        (program, analysis)
+    }
+
+    /// The source of every expression statement which can't be reached, in order
+    fn unreachable(source: &str) -> Vec<String> {
+        struct Statements(Vec<Stmt>);
+        impl Visit for Statements {
+            fn visit_stmt(&mut self, stmt: &Stmt) {
+                if let Stmt::Expr(_) = stmt {
+                    self.0.push(stmt.clone());
+                }
+                stmt.visit_children_with(self);
+            }
+        }
+
+        let (program, analysis) = analyze(source);
+        let mut statements = Statements(Vec::new());
+        program.visit_with(&mut statements);
+
+        statements
+            .0
+            .iter()
+            .filter(|stmt| !analysis.is_reachable(stmt.span()))
+            .map(|stmt| {
+                let span = stmt.span();
+                source[span.lo.0 as usize..span.hi.0 as usize].to_string()
+            })
+            .collect()
+    }
+
+    /// Whether each function in a program can reach the end of its body, in order
+    fn falls_through(source: &str) -> Vec<bool> {
+        struct Functions(Vec<Span>);
+        impl Visit for Functions {
+            fn visit_function(&mut self, function: &Function) {
+                self.0.push(function.span);
+                function.visit_children_with(self);
+            }
+        }
+
+        let (program, analysis) = analyze(source);
+        let mut functions = Functions(Vec::new());
+        program.visit_with(&mut functions);
+
+        functions
+            .0
+            .iter()
+            .map(|span| analysis.falls_through(analysis.code_path(*span).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn jumps() {
+        assert_eq!(
+            unreachable("function f() { a(); return; b(); } throw c; d();"),
+            ["b();", "d();"]
+        );
+        assert_eq!(
+            unreachable("for (;;) { if (a) { break; b(); } continue; c(); } d();"),
+            ["b();", "c();"]
+        );
+        assert_eq!(unreachable("while (true) { a(); } b();"), ["b();"]);
+        assert_eq!(
+            unreachable("do { continue; a(); } while (b); c();"),
+            ["a();"]
+        );
+        assert_eq!(
+            unreachable("outer: for (;;) { for (;;) { break outer; } a(); } b();"),
+            ["a();"]
+        );
+        assert_eq!(unreachable("block: { break block; a(); } b();"), ["a();"]);
+    }
+
+    #[test]
+    fn branches() {
+        assert_eq!(
+            unreachable("if (a) { return; } else { throw b; } c();"),
+            ["c();"]
+        );
+        assert_eq!(unreachable("if (a) { return; } b();"), Vec::<String>::new());
+        assert_eq!(
+            unreachable("switch (a) { case 1: return; case 2: b(); break; c(); } d();"),
+            ["c();"]
+        );
+        assert_eq!(
+            unreachable("switch (a) { case 1: return; default: throw b; } c();"),
+            ["c();"]
+        );
+    }
+
+    #[test]
+    fn try_statements() {
+        assert_eq!(
+            unreachable("try { return; } catch (e) { a(); } b();"),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            unreachable("try { return; } finally { a(); } b();"),
+            ["b();"]
+        );
+        assert_eq!(
+            unreachable("try { a(); } catch (e) { throw e; } finally { return; } b();"),
+            ["b();"]
+        );
+    }
+
+    #[test]
+    fn functions() {
+        assert_eq!(
+            falls_through(
+                "function f() { if (a) return 1; } function g() { if (a) return 1; else throw b; } function h() { return function () {}; }"
+            ),
+            [true, false, false, true]
+        );
+
+        let (_, analysis) = analyze("switch (a) { case 1: b(); case 2: break; }");
+        assert_eq!(analysis.paths.len(), 1);
+        assert_eq!(analysis.paths[0].kind, CodePathKind::Program);
+    }
+}
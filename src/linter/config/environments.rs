@@ -0,0 +1,467 @@
+use super::GlobalAccess;
+
+/// A named set of global variables, enabled by the "env" property of configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Environment {
+    pub name: &'static str,
+    /// Globals which can only be read
+    readonly: &'static [&'static [&'static str]],
+    /// Globals which can also be assigned to
+    writable: &'static [&'static str],
+}
+
+impl Environment {
+    /// The environment's globals, with whether code may assign to them
+    pub fn globals(&self) -> impl Iterator<Item = (&'static str, GlobalAccess)> {
+        let readonly = self.readonly.iter().flat_map(|names| names.iter());
+
+        readonly.map(|name| (*name, GlobalAccess::Readonly)).chain(
+            self.writable
+                .iter()
+                .map(|name| (*name, GlobalAccess::Writable)),
+        )
+    }
+}
+
+/// The environment with an id, e.g., "browser", if msrlint knows about it
+pub fn get_environment(name: &str) -> Option<&'static Environment> {
+    ENVIRONMENTS.iter().find(|env| env.name == name)
+}
+
+/// Globals of ES5, which are always available
+pub const BUILTIN: Environment = Environment {
+    name: "builtin",
+    readonly: &[ES5],
+    writable: &[],
+};
+
+/// Every environment which can be enabled, in the order ESLint documents them
+pub const ENVIRONMENTS: [Environment; 14] = [
+    Environment {
+        name: "browser",
+        readonly: &[BROWSER],
+        writable: BROWSER_WRITABLE,
+    },
+    Environment {
+        name: "node",
+        readonly: &[NODE],
+        writable: &["exports", "module"],
+    },
+    Environment {
+        name: "worker",
+        readonly: &[WORKER],
+        writable: &[
+            "onmessage",
+            "onerror",
+            "onlanguagechange",
+            "onoffline",
+            "ononline",
+        ],
+    },
+    Environment {
+        name: "serviceworker",
+        readonly: &[WORKER, SERVICE_WORKER],
+        writable: &[
+            "onactivate",
+            "onfetch",
+            "oninstall",
+            "onmessage",
+            "onnotificationclick",
+            "onnotificationclose",
+            "onpush",
+        ],
+    },
+    Environment {
+        name: "es6",
+        readonly: &[ES2015],
+        writable: &[],
+    },
+    Environment {
+        name: "es2015",
+        readonly: &[ES2015],
+        writable: &[],
+    },
+    Environment {
+        name: "es2016",
+        readonly: &[ES2015],
+        writable: &[],
+    },
+    Environment {
+        name: "es2017",
+        readonly: &[ES2015, ES2017],
+        writable: &[],
+    },
+    Environment {
+        name: "es2018",
+        readonly: &[ES2015, ES2017],
+        writable: &[],
+    },
+    Environment {
+        name: "es2019",
+        readonly: &[ES2015, ES2017],
+        writable: &[],
+    },
+    Environment {
+        name: "es2020",
+        readonly: &[ES2015, ES2017, ES2020],
+        writable: &[],
+    },
+    Environment {
+        name: "es2021",
+        readonly: &[ES2015, ES2017, ES2020, ES2021],
+        writable: &[],
+    },
+    Environment {
+        name: "jest",
+        readonly: &[JEST],
+        writable: &[],
+    },
+    Environment {
+        name: "mocha",
+        readonly: &[MOCHA],
+        writable: &[],
+    },
+];
+
+const ES5: &[&str] = &[
+    "Array",
+    "Boolean",
+    "constructor",
+    "Date",
+    "decodeURI",
+    "decodeURIComponent",
+    "encodeURI",
+    "encodeURIComponent",
+    "Error",
+    "escape",
+    "eval",
+    "EvalError",
+    "Function",
+    "hasOwnProperty",
+    "Infinity",
+    "isFinite",
+    "isNaN",
+    "isPrototypeOf",
+    "JSON",
+    "Math",
+    "NaN",
+    "Number",
+    "Object",
+    "parseFloat",
+    "parseInt",
+    "propertyIsEnumerable",
+    "RangeError",
+    "ReferenceError",
+    "RegExp",
+    "String",
+    "SyntaxError",
+    "toLocaleString",
+    "toString",
+    "TypeError",
+    "undefined",
+    "unescape",
+    "URIError",
+    "valueOf",
+];
+
+const ES2015: &[&str] = &[
+    "ArrayBuffer",
+    "DataView",
+    "Float32Array",
+    "Float64Array",
+    "Int16Array",
+    "Int32Array",
+    "Int8Array",
+    "Map",
+    "Promise",
+    "Proxy",
+    "Reflect",
+    "Set",
+    "Symbol",
+    "Uint16Array",
+    "Uint32Array",
+    "Uint8Array",
+    "Uint8ClampedArray",
+    "WeakMap",
+    "WeakSet",
+];
+
+const ES2017: &[&str] = &["Atomics", "SharedArrayBuffer"];
+
+const ES2020: &[&str] = &["BigInt", "BigInt64Array", "BigUint64Array", "globalThis"];
+
+const ES2021: &[&str] = &["AggregateError", "FinalizationRegistry", "WeakRef"];
+
+const BROWSER: &[&str] = &[
+    "AbortController",
+    "AbortSignal",
+    "addEventListener",
+    "alert",
+    "atob",
+    "Blob",
+    "blur",
+    "btoa",
+    "caches",
+    "cancelAnimationFrame",
+    "CanvasRenderingContext2D",
+    "clearInterval",
+    "clearTimeout",
+    "close",
+    "closed",
+    "confirm",
+    "console",
+    "crypto",
+    "CSS",
+    "customElements",
+    "CustomEvent",
+    "devicePixelRatio",
+    "dispatchEvent",
+    "document",
+    "Document",
+    "DOMParser",
+    "Element",
+    "Event",
+    "EventSource",
+    "EventTarget",
+    "fetch",
+    "File",
+    "FileList",
+    "FileReader",
+    "focus",
+    "FormData",
+    "frames",
+    "getComputedStyle",
+    "getSelection",
+    "Headers",
+    "history",
+    "HTMLElement",
+    "Image",
+    "indexedDB",
+    "innerHeight",
+    "innerWidth",
+    "IntersectionObserver",
+    "KeyboardEvent",
+    "localStorage",
+    "location",
+    "matchMedia",
+    "MessageChannel",
+    "MouseEvent",
+    "MutationObserver",
+    "navigator",
+    "Node",
+    "NodeList",
+    "Notification",
+    "open",
+    "outerHeight",
+    "outerWidth",
+    "pageXOffset",
+    "pageYOffset",
+    "parent",
+    "performance",
+    "postMessage",
+    "print",
+    "prompt",
+    "queueMicrotask",
+    "removeEventListener",
+    "Request",
+    "requestAnimationFrame",
+    "requestIdleCallback",
+    "ResizeObserver",
+    "Response",
+    "screen",
+    "screenX",
+    "screenY",
+    "scroll",
+    "scrollBy",
+    "scrollTo",
+    "scrollX",
+    "scrollY",
+    "self",
+    "sessionStorage",
+    "setInterval",
+    "setTimeout",
+    "structuredClone",
+    "SVGElement",
+    "TextDecoder",
+    "TextEncoder",
+    "top",
+    "URL",
+    "URLSearchParams",
+    "WebSocket",
+    "window",
+    "Window",
+    "Worker",
+    "XMLHttpRequest",
+];
+
+const BROWSER_WRITABLE: &[&str] = &[
+    "name",
+    "onblur",
+    "onchange",
+    "onclick",
+    "onerror",
+    "onfocus",
+    "onhashchange",
+    "oninput",
+    "onkeydown",
+    "onkeyup",
+    "onload",
+    "onmessage",
+    "onpopstate",
+    "onresize",
+    "onscroll",
+    "onstorage",
+    "onsubmit",
+    "onunload",
+    "status",
+];
+
+const NODE: &[&str] = &[
+    "__dirname",
+    "__filename",
+    "AbortController",
+    "AbortSignal",
+    "Buffer",
+    "clearImmediate",
+    "clearInterval",
+    "clearTimeout",
+    "console",
+    "Event",
+    "EventTarget",
+    "global",
+    "Intl",
+    "MessageChannel",
+    "performance",
+    "process",
+    "queueMicrotask",
+    "require",
+    "setImmediate",
+    "setInterval",
+    "setTimeout",
+    "structuredClone",
+    "TextDecoder",
+    "TextEncoder",
+    "URL",
+    "URLSearchParams",
+];
+
+const WORKER: &[&str] = &[
+    "addEventListener",
+    "atob",
+    "Blob",
+    "btoa",
+    "caches",
+    "clearInterval",
+    "clearTimeout",
+    "close",
+    "console",
+    "crypto",
+    "dispatchEvent",
+    "Event",
+    "EventTarget",
+    "fetch",
+    "FileReader",
+    "FormData",
+    "Headers",
+    "importScripts",
+    "indexedDB",
+    "location",
+    "navigator",
+    "Notification",
+    "performance",
+    "postMessage",
+    "queueMicrotask",
+    "removeEventListener",
+    "Request",
+    "Response",
+    "self",
+    "setInterval",
+    "setTimeout",
+    "TextDecoder",
+    "TextEncoder",
+    "URL",
+    "URLSearchParams",
+    "WebSocket",
+    "Worker",
+    "XMLHttpRequest",
+];
+
+const SERVICE_WORKER: &[&str] = &[
+    "Cache",
+    "CacheStorage",
+    "Client",
+    "clients",
+    "Clients",
+    "ExtendableEvent",
+    "ExtendableMessageEvent",
+    "FetchEvent",
+    "InstallEvent",
+    "NotificationEvent",
+    "PushEvent",
+    "registration",
+    "ServiceWorkerGlobalScope",
+    "ServiceWorkerRegistration",
+    "skipWaiting",
+    "WindowClient",
+];
+
+const JEST: &[&str] = &[
+    "afterAll",
+    "afterEach",
+    "beforeAll",
+    "beforeEach",
+    "describe",
+    "expect",
+    "fdescribe",
+    "fit",
+    "it",
+    "jest",
+    "pit",
+    "require",
+    "test",
+    "xdescribe",
+    "xit",
+    "xtest",
+];
+
+const MOCHA: &[&str] = &[
+    "after",
+    "afterEach",
+    "before",
+    "beforeEach",
+    "context",
+    "describe",
+    "it",
+    "mocha",
+    "run",
+    "setup",
+    "specify",
+    "suite",
+    "suiteSetup",
+    "suiteTeardown",
+    "teardown",
+    "test",
+    "xcontext",
+    "xdescribe",
+    "xit",
+    "xspecify",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn environment_globals() {
+        let node: Vec<_> = get_environment("node").unwrap().globals().collect();
+        assert!(node.contains(&("require", GlobalAccess::Readonly)));
+        assert!(node.contains(&("module", GlobalAccess::Writable)));
+
+        // Later ES versions include the globals of earlier ones
+        let es2021: Vec<_> = get_environment("es2021").unwrap().globals().collect();
+        assert!(es2021.contains(&("Promise", GlobalAccess::Readonly)));
+        assert!(es2021.contains(&("WeakRef", GlobalAccess::Readonly)));
+
+        assert!(get_environment("amd64").is_none());
+    }
+}
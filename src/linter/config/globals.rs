@@ -0,0 +1,65 @@
+use serde::{de, Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+/// Whether code may assign to a global variable, from the "globals" property of configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GlobalAccess {
+    /// "readonly", "readable" or false - the global can be read, but not assigned to
+    Readonly,
+    /// "writable", "writeable" or true - the global can be read and assigned to
+    Writable,
+    /// "off" - the global is not available
+    Off,
+}
+
+impl GlobalAccess {
+    /// Parse a value as written in a configuration file or `/* global */` comment
+    pub fn from_value(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::Bool(false) | Value::Null => Ok(GlobalAccess::Readonly),
+            Value::Bool(true) => Ok(GlobalAccess::Writable),
+            Value::String(name) => match name.as_str() {
+                "readonly" | "readable" | "false" => Ok(GlobalAccess::Readonly),
+                "writable" | "writeable" | "true" => Ok(GlobalAccess::Writable),
+                "off" => Ok(GlobalAccess::Off),
+                _ => Err(format!(
+                    "'{}' is not a valid configuration for a global (use 'readonly', 'writable', or 'off')",
+                    name
+                )),
+            },
+            value => Err(format!(
+                "'{}' is not a valid configuration for a global (use 'readonly', 'writable', or 'off')",
+                value
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for GlobalAccess {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        GlobalAccess::from_value(&Value::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn global_access_from_config_values() {
+        assert_eq!(
+            serde_json::from_str::<GlobalAccess>("true").unwrap(),
+            GlobalAccess::Writable
+        );
+        assert_eq!(
+            serde_json::from_str::<GlobalAccess>("\"readable\"").unwrap(),
+            GlobalAccess::Readonly
+        );
+        assert_eq!(
+            serde_json::from_str::<GlobalAccess>("\"off\"").unwrap(),
+            GlobalAccess::Off
+        );
+        assert!(serde_json::from_str::<GlobalAccess>("\"sometimes\"").is_err());
+    }
+}
@@ -0,0 +1,367 @@
+use serde_json::{Map, Value};
+use swc_common::{
+    comments::{Comment, CommentKind},
+    SourceMap,
+};
+
+use super::{GlobalAccess, LintConfig};
+use crate::linter::{
+    diagnostic::{Diagnostic, DiagnosticCollector, Severity},
+    directives::{split_justification, split_list},
+    rules::get_rule_meta,
+};
+
+/// Configuration written in a comment, which applies to the file it is in
+#[derive(Debug, Clone, PartialEq)]
+pub enum InlineConfig {
+    /// `/* eslint quotes: ["error", "single"], semi: "off" */`
+    Rules(Vec<(String, Value)>),
+    /// `/* global foo:writable, bar */`, or `/* globals ... */`
+    Globals(Vec<(String, GlobalAccess)>),
+    /// `/* exported foo, bar */`
+    Exported(Vec<String>),
+    /// `/* eslint-env node, browser */`
+    Env(Vec<String>),
+}
+
+/// Parse a comment as inline configuration, if it is one
+///
+/// Like ESLint, only block comments can configure the file.
+pub fn parse_inline_config(comment: &Comment) -> Option<Result<InlineConfig, String>> {
+    if comment.kind != CommentKind::Block {
+        return None;
+    }
+
+    let (body, _) = split_justification(&comment.text);
+    let (keyword, value) = match body.split_once(char::is_whitespace) {
+        Some((keyword, value)) => (keyword, value.trim()),
+        None => (body, ""),
+    };
+
+    let config = match keyword {
+        "eslint" => parse_rules(value).map(InlineConfig::Rules),
+        "global" | "globals" => parse_string_config(value)
+            .into_iter()
+            .map(|(name, value)| {
+                let access = match value {
+                    Some(value) => GlobalAccess::from_value(&Value::String(value))?,
+                    None => GlobalAccess::Readonly,
+                };
+                Ok((name, access))
+            })
+            .collect::<Result<_, String>>()
+            .map(InlineConfig::Globals),
+        "exported" => Ok(InlineConfig::Exported(
+            parse_string_config(value)
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect(),
+        )),
+        "eslint-env" => Ok(InlineConfig::Env(split_list(value))),
+        _ => return None,
+    };
+
+    Some(config)
+}
+
+/// Parse the rules configured by an `/* eslint */` comment
+///
+/// The comment's contents are the inside of a JSON object, but names and plain words don't need
+/// to be quoted, e.g., `quotes: [error, single]`.
+fn parse_rules(text: &str) -> Result<Vec<(String, Value)>, String> {
+    let json = format!("{{{}}}", quote_words(text));
+    let rules: Map<String, Value> = serde_json::from_str(&json)
+        .map_err(|err| format!("Failed to parse JSON from '{}': {}", json, err))?;
+
+    Ok(rules.into_iter().collect())
+}
+
+/// Put quotes around bare words outside of strings, leaving JSON literals and numbers alone
+fn quote_words(text: &str) -> String {
+    let is_word_char = |c: char| c.is_alphanumeric() || "_$@/.-".contains(c);
+
+    let mut quoted = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '"' {
+            // Copy strings as they are, including any escaped quotes
+            quoted.push(c);
+            let mut escaped = false;
+            for c in chars.by_ref() {
+                quoted.push(c);
+                match c {
+                    '\\' if !escaped => escaped = true,
+                    '"' if !escaped => break,
+                    _ => escaped = false,
+                }
+            }
+        } else if is_word_char(c) {
+            let mut word = String::from(c);
+            while let Some(&c) = chars.peek() {
+                if !is_word_char(c) {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+
+            let is_literal = ["true", "false", "null"].contains(&word.as_str())
+                || serde_json::from_str::<f64>(&word).is_ok();
+            if is_literal {
+                quoted.push_str(&word);
+            } else {
+                quoted.push_str(&format!("\"{}\"", word));
+            }
+        } else {
+            quoted.push(c);
+        }
+    }
+
+    quoted
+}
+
+/// Parse a list of names with optional values, e.g., `foo:writable, bar`
+fn parse_string_config(text: &str) -> Vec<(String, Option<String>)> {
+    // Names and values are separated by colons, and pairs by commas or whitespace
+    let mut normalized = String::with_capacity(text.len());
+    for part in text.split(':') {
+        if !normalized.is_empty() {
+            normalized.push(':');
+        }
+        normalized.push_str(part.trim());
+    }
+
+    normalized
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|item| !item.is_empty())
+        .map(|item| match item.split_once(':') {
+            Some((name, value)) => (name.to_string(), Some(value.to_string())),
+            None => (item.to_string(), None),
+        })
+        .collect()
+}
+
+impl LintConfig {
+    /// Merge configuration from a comment into this configuration
+    pub fn apply_inline_config(&mut self, inline_config: &InlineConfig) -> Result<(), String> {
+        match inline_config {
+            InlineConfig::Rules(rules) => {
+                for (rule_id, value) in rules {
+                    self.set_rule(rule_id, value).map_err(|err| {
+                        format!(
+                            "Inline configuration for rule \"{}\" is invalid: {}",
+                            rule_id, err
+                        )
+                    })?;
+                }
+            }
+            InlineConfig::Globals(globals) => self.globals.extend(globals.iter().cloned()),
+            InlineConfig::Exported(names) => self.exported.extend(names.iter().cloned()),
+            InlineConfig::Env(envs) => {
+                for env in envs {
+                    self.set_env(env, true)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Configure rules from a `--rule` option, which uses the same syntax as `/* eslint */`
+    /// comments, e.g., `quotes: [error, single]`
+    pub fn apply_cli_rules(&mut self, text: &str) -> Result<(), String> {
+        for (rule_id, value) in parse_rules(text)? {
+            if get_rule_meta(&rule_id).is_none() {
+                return Err(format!("Definition for rule '{}' was not found.", rule_id));
+            }
+            self.set_rule(&rule_id, &value).map_err(|err| {
+                format!("Configuration for rule \"{}\" is invalid: {}", rule_id, err)
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The configuration for a file, after applying configuration from its comments
+///
+/// Returns None if no comments configure the file. Invalid configuration comments are reported
+/// as errors at the comment.
+pub fn apply_inline_configs(
+    lint_config: &LintConfig,
+    source_map: &SourceMap,
+    comments: &[Comment],
+    diagnostics: &DiagnosticCollector,
+) -> Option<LintConfig> {
+    let mut file_config: Option<LintConfig> = None;
+
+    for comment in comments {
+        let result = match parse_inline_config(comment) {
+            Some(Ok(inline_config)) => file_config
+                .get_or_insert_with(|| lint_config.clone())
+                .apply_inline_config(&inline_config),
+            Some(Err(err)) => Err(err),
+            None => continue,
+        };
+
+        if let Err(err) = result {
+            diagnostics.push(Diagnostic::new(
+                source_map,
+                comment.span,
+                Severity::Error,
+                err,
+            ));
+        }
+    }
+
+    file_config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+    use swc_common::DUMMY_SP;
+
+    use crate::linter::config::{QuotesConfig, RuleOptions};
+
+    fn block(text: &str) -> Comment {
+        Comment {
+            kind: CommentKind::Block,
+            span: DUMMY_SP,
+            text: text.into(),
+        }
+    }
+
+    fn parse(text: &str) -> Option<Result<InlineConfig, String>> {
+        parse_inline_config(&block(text))
+    }
+
+    #[test]
+    fn parse_rules_json() {
+        assert_eq!(
+            parse(r#" eslint quotes: ["error", "single"], "no-eval": 0 "#),
+            Some(Ok(InlineConfig::Rules(vec![
+                (String::from("no-eval"), json!(0)),
+                (String::from("quotes"), json!(["error", "single"])),
+            ])))
+        );
+    }
+
+    #[test]
+    fn parse_rules_unquoted() {
+        assert_eq!(
+            parse(" eslint semi: [warn, always], eqeqeq: off -- legacy code "),
+            Some(Ok(InlineConfig::Rules(vec![
+                (String::from("eqeqeq"), json!("off")),
+                (String::from("semi"), json!(["warn", "always"])),
+            ])))
+        );
+    }
+
+    #[test]
+    fn parse_rules_invalid() {
+        assert!(matches!(parse(" eslint quotes: [error "), Some(Err(_))));
+    }
+
+    #[test]
+    fn cli_rules() {
+        let mut config = LintConfig::default();
+
+        config.apply_cli_rules("quotes: [warn, double]").unwrap();
+        config.apply_cli_rules("\"no-eval\": off").unwrap();
+        assert_eq!(config.quotes.severity(), Severity::Warn);
+        assert_eq!(
+            config.quotes.get_rule_config(),
+            &QuotesConfig::from_options(&[json!("double")]).unwrap()
+        );
+        assert!(config.disabled_rules.contains(&String::from("no-eval")));
+
+        assert!(config.apply_cli_rules("semi: [wrong]").is_err());
+        assert!(config.apply_cli_rules("semi: [error").is_err());
+        assert_eq!(
+            config.apply_cli_rules("no-var: error"),
+            Err(String::from("Definition for rule 'no-var' was not found."))
+        );
+    }
+
+    #[test]
+    fn parse_globals() {
+        assert_eq!(
+            parse(" global foo:writable, bar ,baz : off "),
+            Some(Ok(InlineConfig::Globals(vec![
+                (String::from("foo"), GlobalAccess::Writable),
+                (String::from("bar"), GlobalAccess::Readonly),
+                (String::from("baz"), GlobalAccess::Off),
+            ])))
+        );
+        assert!(matches!(parse(" globals foo: maybe "), Some(Err(_))));
+    }
+
+    #[test]
+    fn parse_exported_and_env() {
+        assert_eq!(
+            parse(" exported foo, bar "),
+            Some(Ok(InlineConfig::Exported(vec![
+                String::from("foo"),
+                String::from("bar")
+            ])))
+        );
+        assert_eq!(
+            parse(" eslint-env node, browser "),
+            Some(Ok(InlineConfig::Env(vec![
+                String::from("node"),
+                String::from("browser")
+            ])))
+        );
+    }
+
+    #[test]
+    fn apply_env() {
+        let mut config = LintConfig::default();
+
+        let env = InlineConfig::Env(vec![String::from("mocha")]);
+        config.apply_inline_config(&env).unwrap();
+        assert_eq!(config.env.get("mocha"), Some(&true));
+
+        let env = InlineConfig::Env(vec![String::from("mocah")]);
+        assert!(config.apply_inline_config(&env).is_err());
+    }
+
+    #[test]
+    fn ignores_other_comments() {
+        assert_eq!(parse(" eslint-disable quotes "), None);
+        assert_eq!(parse(" a global comment "), None);
+
+        let line = Comment {
+            kind: CommentKind::Line,
+            ..block(" eslint quotes: off")
+        };
+        assert_eq!(parse_inline_config(&line), None);
+    }
+
+    #[test]
+    fn apply_to_config() {
+        let mut config = LintConfig::default();
+        config
+            .apply_inline_config(&InlineConfig::Rules(vec![(
+                String::from("eqeqeq"),
+                json!("warn"),
+            )]))
+            .unwrap();
+        config
+            .apply_inline_config(&InlineConfig::Env(vec![String::from("node")]))
+            .unwrap();
+
+        assert_eq!(config.eqeqeq.severity(), Severity::Warn);
+        assert_eq!(config.env.get("node"), Some(&true));
+        assert!(config
+            .apply_inline_config(&InlineConfig::Rules(vec![(
+                String::from("semi"),
+                json!(["error", "sometimes"]),
+            )]))
+            .is_err());
+    }
+}
@@ -1,238 +1,414 @@
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Map, Value};
 
-use super::rules::lints::{
-    eqeqeq::EqeqeqConfig, no_eval::NoEvalConfig, quotes::QuotesConfig, semi::SemiConfig,
+pub use super::rules::lints::{
+    consistent_return::ConsistentReturnConfig, eqeqeq::EqeqeqConfig, no_eval::NoEvalConfig,
+    no_fallthrough::NoFallthroughConfig, no_implied_eval::NoImpliedEvalConfig,
+    no_new_func::NoNewFuncConfig, no_undef::NoUndefConfig, no_unreachable::NoUnreachableConfig,
+    no_unused_vars::NoUnusedVarsConfig, quotes::QuotesConfig, semi::SemiConfig,
 };
+use super::{
+    diagnostic::Severity,
+    rules::{get_rule_meta, RULES},
+};
+use crate::debug;
 use std::{collections::HashMap, fmt::Debug, fs::read_to_string, path::PathBuf};
 
-#[derive(Default, Clone, PartialEq, Debug)]
+mod environments;
+mod globals;
+pub(crate) mod inline;
+mod parser_options;
+
+pub use environments::{get_environment, Environment, BUILTIN, ENVIRONMENTS};
+pub use globals::GlobalAccess;
+pub use inline::InlineConfig;
+pub use parser_options::{ParserOptions, SourceType};
+
+#[derive(Default, Clone, PartialEq, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct LintConfig {
     /// Values from the "extends" property of configuration
     pub rules: Vec<String>,
     pub disabled_rules: Vec<String>,
+    /// Rules turned on by configuration, which are the only rules run without "eslint:all"
+    pub enabled_rules: Vec<String>,
+    /// Options for the parser, from the "parserOptions" property of configuration
+    pub parser_options: ParserOptions,
+    /// Environments enabled for the file, from the "env" property of configuration, or, e.g.,
+    /// `/* eslint-env node */`
+    pub env: HashMap<String, bool>,
+    /// Global variables defined for the file, from the "globals" property of configuration, or,
+    /// e.g., `/* global foo:writable */`
+    pub globals: HashMap<String, GlobalAccess>,
+    /// Variables marked as used elsewhere, by `/* exported foo */`
+    pub exported: Vec<String>,
+    /// Ignore directives and configuration in comments, like `--no-inline-config`
+    pub no_inline_config: bool,
+    /// Report disable directives which don't suppress any problems
+    pub report_unused_disable_directives: bool,
     // Config for linting rules
     pub quotes: RuleConfig<QuotesConfig>,
     pub semi: RuleConfig<SemiConfig>,
     pub eqeqeq: RuleConfig<EqeqeqConfig>,
     pub noeval: RuleConfig<NoEvalConfig>,
+    pub noimpliedeval: RuleConfig<NoImpliedEvalConfig>,
+    pub nonewfunc: RuleConfig<NoNewFuncConfig>,
+    pub noundef: RuleConfig<NoUndefConfig>,
+    pub nounusedvars: RuleConfig<NoUnusedVarsConfig>,
+    pub nounreachable: RuleConfig<NoUnreachableConfig>,
+    pub nofallthrough: RuleConfig<NoFallthroughConfig>,
+    pub consistentreturn: RuleConfig<ConsistentReturnConfig>,
 }
 
 // TODO impl default manually
 
 #[derive(Serialize, Deserialize, Debug)]
-#[serde(untagged)]
-enum Rules {
-    /// ESLint rule
-    /// * 0 - turns the rule off
-    /// * 1 - turn the rule on as a warning (doesn't affect exit code)
-    /// * 2 - turn the rule on as an error (exit code is 1 when triggered)
-    NumberEnabled(u8),
-    /// ESLint rule
-    /// * "off" - turns the rule off
-    /// * "warn" - turn the rule on as a warning (doesn't affect exit code)
-    /// * "error" - turn the rule on as an error (exit code is 1 when triggered)
-    StringEnabled(String),
-    /// ESLint rule
-    /// * "off" - turns the rule off
-    /// * "warn" - turn the rule on as a warning (doesn't affect exit code)
-    /// * "error" - turn the rule on as an error (exit code is 1 when triggered)
-    ///
-    /// The second property in the pair configures the rule, e.g., ["error", "single"] for quotes rule
-    StringEnabledString(String, String),
-    /// ESLint rule
-    /// * 0 - turns the rule off
-    /// * 1 - turn the rule on as a warning (doesn't affect exit code)
-    /// * 2 - turn the rule on as an error (exit code is 1 when triggered)
-    ///
-    /// The second property in the pair configures the rule, e.g., [2, "single"] for quotes rule
-    NumberEnabledString(u8, String),
-    /// ESLint rule
-    /// * "off" - turns the rule off
-    /// * "warn" - turn the rule on as a warning (doesn't affect exit code)
-    /// * "error" - turn the rule on as an error (exit code is 1 when triggered)
-    ///
-    /// The second property in the pair configures the rule, e.g., ["error", {prefer: "single"}] for quotes rule
-    StringEnabledObject(String, HashMap<String, Value>),
-    /// ESLint rule
-    /// * 0 - turns the rule off
-    /// * 1 - turn the rule on as a warning (doesn't affect exit code)
-    /// * 2 - turn the rule on as an error (exit code is 1 when triggered)
-    ///
-    /// The second property in the pair configures the rule, e.g., [2, {prefer: "single"}] for quotes rule
-    NumberEnabledObject(u8, HashMap<String, Value>),
-}
-
-#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
 struct RawConfigFile {
     extends: Vec<String>,
-    rules: HashMap<String, Rules>,
+    /// Rule configuration, e.g., `"warn"` or `["error", "always", {"null": "ignore"}]`, which is
+    /// checked by the rule it configures
+    rules: HashMap<String, Value>,
+    #[serde(default)]
+    parser_options: ParserOptions,
+    #[serde(default)]
+    env: HashMap<String, bool>,
+    #[serde(default)]
+    globals: HashMap<String, GlobalAccess>,
+    #[serde(default)]
+    no_inline_config: bool,
+    #[serde(default)]
+    report_unused_disable_directives: bool,
 }
 
-/// Check if config values indicates that the provided key should be enabled
-/// Returns Some(key) if the option is disabled
-/// Return None if the option is enabled
-fn is_num_disabled(key: String, num: &u8) -> Option<String> {
-    if *num == 0 {
-        Some(key)
-    } else {
-        None
-    }
-}
+/// Whether a rule's configuration turns it off, e.g., `0` or `["off", "single"]`
+fn is_rule_off(value: &Value) -> bool {
+    let severity = match value {
+        Value::Array(values) => values.first(),
+        value => Some(value),
+    };
 
-/// Check if config values indicates that the provided key should be enabled
-/// Returns Some(key) if the option is disabled
-/// Return None if the option is enabled
-fn is_string_disabled(key: String, str: &str) -> Option<String> {
-    if str == "off" {
-        Some(key)
-    } else {
-        None
-    }
+    severity.is_some_and(|severity| {
+        serde_json::from_value::<Severity>(severity.clone()).ok() == Some(Severity::Off)
+    })
 }
 
-impl From<PathBuf> for LintConfig {
-    fn from(buf: PathBuf) -> Self {
+impl TryFrom<PathBuf> for LintConfig {
+    type Error = String;
+
+    /// Load a configuration file, or the default configuration for an empty path
+    ///
+    /// Files which can't be read or parsed, or which configure rules or environments wrongly,
+    /// are reported as errors.
+    fn try_from(buf: PathBuf) -> Result<Self, Self::Error> {
         // If buf is default, the user did not specify a config file to load
         if buf == PathBuf::default() {
-            return LintConfig::default();
+            debug!("config", "No config file found, using the default config");
+            return Ok(LintConfig::default());
         }
 
+        debug!("config", "Loading config file {}", buf.display());
+
         let all_rules = String::from("eslint:all");
+        let invalid =
+            |err: String| format!("Failed to load config file {}: {}", buf.display(), err);
 
         // Get file extension
         let valid_extensions = ["json", "js", "cjs", "yml", "yaml"];
-        let ext = buf.extension().unwrap().to_str().unwrap();
+        let ext = buf
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default();
 
         // Check for invalid file extensions
         if !valid_extensions.contains(&ext) {
-            panic!(
+            return Err(invalid(format!(
                 "Extension {:?} is not a valid file extension. Please use one of {:?} instead.",
                 ext, valid_extensions
+            )));
+        }
+
+        // TODO: parse .eslintrc.js and .eslintrc.cjs files
+        if ext == "js" || ext == "cjs" {
+            return Err(invalid(String::from(
+                "JavaScript config files are not supported yet.",
+            )));
+        }
+
+        // Parse file from JSON or YAML to struct
+        let file = read_to_string(&buf).map_err(|err| invalid(err.to_string()))?;
+        let json: RawConfigFile = if ext == "json" {
+            serde_json::from_str(file.as_str()).map_err(|err| invalid(err.to_string()))?
+        } else {
+            serde_yaml::from_str(file.as_str()).map_err(|err| invalid(err.to_string()))?
+        };
+
+        // Get list of disabled rules, including those msrlint doesn't implement
+        let disabled_rules: Vec<String> = json
+            .rules
+            .iter()
+            .filter(|(_, value)| is_rule_off(value))
+            .map(|(key, _)| key.to_string())
+            .collect();
+
+        // If the eslint:all property is extended from, use all rules apart from those in disabled_rules
+        let rules = if json.extends.contains(&all_rules) {
+            vec![all_rules]
+        } else {
+            // Otherwise, we return the list of disabled rules and the default config
+            Vec::new()
+        };
+        let mut config = LintConfig {
+            rules,
+            disabled_rules,
+            parser_options: json.parser_options,
+            no_inline_config: json.no_inline_config,
+            report_unused_disable_directives: json.report_unused_disable_directives,
+            ..Default::default()
+        };
+
+        for (key, value) in &json.rules {
+            config.set_rule(key, value).map_err(|err| {
+                invalid(format!(
+                    "Configuration for rule \"{}\" is invalid: {}",
+                    key, err
+                ))
+            })?;
+        }
+
+        for (name, enabled) in json.env {
+            config.set_env(&name, enabled).map_err(invalid)?;
+        }
+        config.globals = json.globals;
+
+        Ok(config)
+    }
+}
+
+impl LintConfig {
+    /// Configure a rule by its id from a config value, e.g., `"warn"` or `["error", "single"]`,
+    /// ignoring rules msrlint doesn't implement
+    ///
+    /// A value without options, like `"warn"`, only changes the rule's severity.
+    pub fn set_rule(&mut self, rule_id: &str, value: &Value) -> Result<(), String> {
+        let (severity, options) = match value {
+            Value::Array(values) if !values.is_empty() => (&values[0], &values[1..]),
+            value => (value, &[][..]),
+        };
+        let severity: Severity = serde_json::from_value(severity.clone()).map_err(|_| {
+            format!(
+                "Severity should be one of the following: 0 = off, 1 = warn, 2 = error (you passed '{}').",
+                severity
             )
+        })?;
+
+        let meta = match get_rule_meta(rule_id) {
+            Some(meta) => meta,
+            None => return Ok(()),
+        };
+        (meta.config_mut)(self).configure(severity, options)?;
+
+        // Rules which are turned off aren't run at all
+        self.disabled_rules.retain(|rule| rule != rule_id);
+        self.enabled_rules.retain(|rule| rule != rule_id);
+        if severity == Severity::Off {
+            self.disabled_rules.push(rule_id.to_string());
+        } else {
+            self.enabled_rules.push(rule_id.to_string());
         }
 
-        // Parse .eslintrc.js files as priority
-        if ext == "js" {
-            todo!()
-        } else if ext == "json" {
-            // Parse file from JSON to struct
-            let file = read_to_string(buf).unwrap();
-            let json: RawConfigFile = serde_json::from_str(file.as_str()).unwrap();
-
-            // Get list of disabled rules
-            let disabled_rules: Vec<String> = json
-                .rules
-                .iter()
-                .filter_map(|(key, value)| match value {
-                    Rules::NumberEnabled(num) => is_num_disabled(key.to_string(), num),
-                    Rules::NumberEnabledString(num, _) => is_num_disabled(key.to_string(), num),
-                    Rules::NumberEnabledObject(num, _) => is_num_disabled(key.to_string(), num),
-                    Rules::StringEnabledObject(str, _) => is_string_disabled(key.to_string(), str),
-                    Rules::StringEnabled(str) => is_string_disabled(key.to_string(), str),
-                    Rules::StringEnabledString(str, _) => is_string_disabled(key.to_string(), str),
-                })
-                .collect();
-
-            // If the eslint:all property is extended from, use all rules apart from those in disabled_rules
-            if json.extends.contains(&all_rules) {
-                LintConfig {
-                    rules: [all_rules].to_vec(),
-                    disabled_rules,
-                    ..Default::default()
-                }
-            } else {
-                // Otherwise, we return the list of disabled rules and the default config
-                LintConfig {
-                    disabled_rules,
-                    ..Default::default()
-                }
-            }
+        Ok(())
+    }
+
+    /// Whether a rule is run, either by extending "eslint:all" without turning it off, or by
+    /// turning it on
+    pub fn is_rule_enabled(&self, rule_id: &str) -> bool {
+        if self.rules.iter().any(|rule| rule == "eslint:all") {
+            !self.disabled_rules.iter().any(|rule| rule == rule_id)
         } else {
-            // TODO: parse other file types
-            todo!()
+            self.enabled_rules.iter().any(|rule| rule == rule_id)
+        }
+    }
+
+    /// Whether any rule is turned on, before comments in files change the configuration
+    pub fn has_enabled_rules(&self) -> bool {
+        RULES.iter().any(|meta| self.is_rule_enabled(meta.id))
+    }
+
+    /// Enable or disable an environment by its id, e.g., "browser"
+    pub fn set_env(&mut self, name: &str, enabled: bool) -> Result<(), String> {
+        if get_environment(name).is_none() {
+            return Err(format!("Environment key \"{}\" is unknown", name));
         }
+
+        self.env.insert(name.to_string(), enabled);
+        Ok(())
+    }
+
+    /// Every global variable available to the file, from ES5 builtins, enabled environments and
+    /// the "globals" property of configuration
+    ///
+    /// Globals configured as "off" are left out, even if an environment defines them.
+    pub fn resolved_globals(&self) -> HashMap<String, GlobalAccess> {
+        let environments = self
+            .env
+            .iter()
+            .filter(|(_, enabled)| **enabled)
+            .filter_map(|(name, _)| get_environment(name));
+
+        let mut globals: HashMap<String, GlobalAccess> = BUILTIN
+            .globals()
+            .chain(environments.flat_map(Environment::globals))
+            .map(|(name, access)| (name.to_string(), access))
+            .collect();
+
+        for (name, access) in &self.globals {
+            match access {
+                GlobalAccess::Off => globals.remove(name),
+                access => globals.insert(name.clone(), *access),
+            };
+        }
+
+        globals
+    }
+}
+
+impl LintConfig {
+    /// The configuration in ESLint's format, e.g., as printed by `--print-config`
+    ///
+    /// Every rule msrlint implements is included, with its severity and all of its options.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "env": self.env,
+            "globals": self.globals,
+            "parserOptions": self.parser_options,
+            "noInlineConfig": self.no_inline_config,
+            "reportUnusedDisableDirectives": self.report_unused_disable_directives,
+            "rules": RULES
+                .iter()
+                .map(|meta| (meta.id.to_string(), self.rule_json(meta.id, (meta.config)(self))))
+                .collect::<Map<_, _>>(),
+        })
+    }
+
+    fn rule_json(&self, rule_id: &str, rule: &dyn RuleSettings) -> Value {
+        let severity = if !self.is_rule_enabled(rule_id) {
+            Severity::Off
+        } else {
+            rule.severity()
+        };
+
+        json!([severity, rule.options_json()])
     }
 }
 
+/// Options for a rule, parsed from the values after the severity in its configuration,
+/// e.g., `["single"]` in `["error", "single"]` for the quotes rule
+pub trait RuleOptions: Sized {
+    fn from_options(options: &[Value]) -> Result<Self, String>;
+}
+
 // Adapted from
 // https://github.com/swc-project/swc/blob/e9c1b229262c07d114e4b75bbc9f104b45fbedf3/crates/swc_ecma_lints/src/config.rs#L53-L67
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
-pub struct RuleConfig<T: Debug + Clone + Serialize + Default>(#[serde(default)] T);
+pub struct RuleConfig<T: Debug + Clone + Serialize + Default>(
+    #[serde(default)] Severity,
+    #[serde(default)] T,
+);
 
 impl<T: Debug + Clone + Serialize + Default> RuleConfig<T> {
     pub(crate) fn get_rule_config(&self) -> &T {
-        &self.0
+        &self.1
+    }
+
+    pub(crate) fn severity(&self) -> Severity {
+        self.0
     }
 }
-// end
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl<T: Debug + Clone + Serialize + Default + RuleOptions> RuleConfig<T> {
+    /// Set the rule's severity, and its options if any are given
+    fn configure(&mut self, severity: Severity, options: &[Value]) -> Result<(), String> {
+        if !options.is_empty() {
+            self.1 = T::from_options(options)?;
+        }
+        self.0 = severity;
 
-    #[test]
-    fn is_zero_enabled() {
-        assert_eq!(
-            is_num_disabled(String::from("key"), &0),
-            Some(String::from("key"))
-        );
+        Ok(())
     }
+}
+// end
 
-    #[test]
-    fn is_one_enabled() {
-        assert_eq!(is_num_disabled(String::from("key"), &1), None);
-    }
+/// A rule's [RuleConfig], whatever the type of its options, so rules can be configured by id
+pub(crate) trait RuleSettings {
+    fn configure(&mut self, severity: Severity, options: &[Value]) -> Result<(), String>;
+    fn severity(&self) -> Severity;
+    fn options_json(&self) -> Value;
+}
 
-    #[test]
-    fn is_two_enabled() {
-        assert_eq!(is_num_disabled(String::from("key"), &2), None);
+impl<T: Debug + Clone + Serialize + Default + RuleOptions> RuleSettings for RuleConfig<T> {
+    fn configure(&mut self, severity: Severity, options: &[Value]) -> Result<(), String> {
+        RuleConfig::configure(self, severity, options)
     }
 
-    #[test]
-    fn is_three_enabled() {
-        assert_eq!(is_num_disabled(String::from("key"), &3), None);
+    fn severity(&self) -> Severity {
+        self.0
     }
 
-    #[test]
-    fn is_off_enabled() {
-        assert_eq!(
-            is_string_disabled(String::from("key"), "off"),
-            Some(String::from("key"))
-        )
+    fn options_json(&self) -> Value {
+        json!(self.1)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
-    fn is_warn_enabled() {
-        assert_eq!(is_string_disabled(String::from("key"), "warn"), None)
+    fn has_enabled_rules_until_all_are_off() {
+        let mut config = LintConfig::default();
+        assert!(!config.has_enabled_rules());
+
+        config.rules.push(String::from("eslint:all"));
+        assert!(config.has_enabled_rules());
+
+        for meta in RULES {
+            config.set_rule(meta.id, &json!("off")).unwrap();
+        }
+        assert!(!config.has_enabled_rules());
     }
 
     #[test]
-    fn is_error_enabled() {
-        assert_eq!(is_string_disabled(String::from("key"), "error"), None)
+    fn only_rules_turned_on_are_enabled_without_eslint_all() {
+        let mut config = LintConfig::default();
+        config.set_rule("eqeqeq", &json!("warn")).unwrap();
+        assert!(config.has_enabled_rules());
+        assert!(config.is_rule_enabled("eqeqeq"));
+        assert!(!config.is_rule_enabled("semi"));
+
+        config.set_rule("eqeqeq", &json!("off")).unwrap();
+        assert!(!config.has_enabled_rules());
     }
 
     #[test]
     fn create_config_no_path() {
-        let config = LintConfig::from(PathBuf::default());
+        let config = LintConfig::try_from(PathBuf::default()).unwrap();
         assert_eq!(config, LintConfig::default())
     }
 
     #[test]
-    #[should_panic]
     fn create_config_invalid_extension() {
-        let _config = LintConfig::from(PathBuf::from("aconfig.thisisnotagoodextension"));
+        assert!(LintConfig::try_from(PathBuf::from("aconfig.thisisnotagoodextension")).is_err());
     }
 
     #[test]
-    #[should_panic]
     fn create_config_js() {
-        let _config = LintConfig::from(PathBuf::from("aconfig.js"));
+        assert!(LintConfig::try_from(PathBuf::from("aconfig.js")).is_err());
     }
 
     #[test]
     fn create_config_json() {
-        let config = LintConfig::from(PathBuf::from("./test/.eslintrc.json"));
+        let config = LintConfig::try_from(PathBuf::from("./test/.eslintrc.json")).unwrap();
         let expected = LintConfig {
             rules: Vec::from([String::from("eslint:all")]),
             disabled_rules: Vec::from([
@@ -249,7 +425,7 @@ mod tests {
 
     #[test]
     fn create_config_json_no_eslint_all() {
-        let config = LintConfig::from(PathBuf::from("./test/.confignoall.json"));
+        let config = LintConfig::try_from(PathBuf::from("./test/.confignoall.json")).unwrap();
         let expected = LintConfig {
             rules: Vec::from([]),
             disabled_rules: Vec::from([
@@ -265,20 +441,212 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
+    fn create_config_json_parser_options() {
+        let config =
+            LintConfig::try_from(PathBuf::from("./test/.configparseroptions.json")).unwrap();
+        assert_eq!(
+            config.parser_options.es_version(),
+            swc_ecma_ast::EsVersion::Es2020
+        );
+        assert_eq!(config.parser_options.source_type, Some(SourceType::Module));
+        assert_eq!(config.parser_options.ecma_features.jsx, Some(true));
+    }
+
+    #[test]
+    fn create_config_json_severities() {
+        let config = LintConfig::try_from(PathBuf::from("./test/.eslintrc.json")).unwrap();
+        assert_eq!(config.semi.severity(), Severity::Error);
+        // Rules not mentioned in the file default to errors
+        assert_eq!(config.quotes.severity(), Severity::Error);
+
+        let config =
+            LintConfig::try_from(PathBuf::from("./test/.configparseroptions.json")).unwrap();
+        assert_eq!(config.eqeqeq.severity(), Severity::Warn);
+        assert_eq!(config.noeval.severity(), Severity::Off);
+    }
+
+    #[test]
+    fn create_config_json_rule_options() {
+        let config = LintConfig::try_from(PathBuf::from("./test/.configruleoptions.json")).unwrap();
+        assert_eq!(
+            config.eqeqeq.get_rule_config(),
+            &EqeqeqConfig::from_options(&[json!("always"), json!({ "null": "ignore" })]).unwrap()
+        );
+        assert_eq!(
+            config.quotes.get_rule_config(),
+            &QuotesConfig::from_options(&[
+                json!("single"),
+                json!({ "avoidEscape": true, "allowTemplateLiterals": true })
+            ])
+            .unwrap()
+        );
+        assert_eq!(
+            config.semi.get_rule_config(),
+            &SemiConfig::from_options(&[
+                json!("always"),
+                json!({ "omitLastInOneLineBlock": true })
+            ])
+            .unwrap()
+        );
+
+        let config = LintConfig::try_from(PathBuf::from("./test/.configruleoptions.yaml")).unwrap();
+        assert_eq!(
+            config.semi.get_rule_config(),
+            &SemiConfig::from_options(&[
+                json!("never"),
+                json!({ "beforeStatementContinuationChars": "always" })
+            ])
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn create_config_json_invalid_rule_options() {
+        let err = LintConfig::try_from(PathBuf::from("./test/.configinvalid.json")).unwrap_err();
+        assert!(
+            err.contains("Configuration for rule \"eqeqeq\" is invalid"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn set_rule_severity_and_options() {
+        let mut config = LintConfig::default();
+        config
+            .set_rule("quotes", &serde_json::json!(["warn", "single"]))
+            .unwrap();
+        assert_eq!(config.quotes.severity(), Severity::Warn);
+        assert_eq!(
+            config.quotes.get_rule_config(),
+            &QuotesConfig::from_options(&[Value::from("single")]).unwrap()
+        );
+
+        // Without options, only the severity changes
+        config.set_rule("quotes", &Value::from(0)).unwrap();
+        assert_eq!(config.quotes.severity(), Severity::Off);
+        assert_ne!(config.quotes.get_rule_config(), &QuotesConfig::default());
+        assert_eq!(config.disabled_rules, ["quotes"]);
+
+        assert!(config.set_rule("semi", &Value::from("loud")).is_err());
+        assert!(config
+            .set_rule("semi", &serde_json::json!(["error", "sometimes"]))
+            .is_err());
+        assert!(config.set_rule("not-a-rule", &Value::from(2)).is_ok());
+    }
+
+    #[test]
+    fn create_config_yaml_file() {
+        let config = LintConfig::try_from(PathBuf::from("./test/.configyaml.yaml")).unwrap();
+        assert_eq!(config.rules, ["eslint:all"]);
+        assert_eq!(config.parser_options.source_type, Some(SourceType::Module));
+        assert_eq!(config.quotes.severity(), Severity::Warn);
+        assert_eq!(
+            config.semi.get_rule_config(),
+            &SemiConfig::from_options(&[Value::from("always")]).unwrap()
+        );
+        assert_eq!(config.disabled_rules, ["eqeqeq"]);
+        assert_eq!(config.env.get("node"), Some(&true));
+        assert_eq!(config.globals.get("process"), Some(&GlobalAccess::Off));
+    }
+
+    #[test]
+    fn resolve_globals() {
+        let mut config = LintConfig::try_from(PathBuf::from("./test/.configyaml.yaml")).unwrap();
+        config
+            .globals
+            .insert(String::from("module"), GlobalAccess::Readonly);
+        let globals = config.resolved_globals();
+
+        assert_eq!(globals.get("Array"), Some(&GlobalAccess::Readonly));
+        assert_eq!(globals.get("WeakRef"), Some(&GlobalAccess::Readonly));
+        assert_eq!(globals.get("require"), Some(&GlobalAccess::Readonly));
+        assert_eq!(globals.get("jQuery"), Some(&GlobalAccess::Readonly));
+        // Configured globals override environments, and "off" removes them
+        assert_eq!(globals.get("module"), Some(&GlobalAccess::Readonly));
+        assert_eq!(globals.get("process"), None);
+        assert_eq!(globals.get("window"), None);
+
+        assert!(config.set_env("browser", false).is_ok());
+        assert!(config.set_env("browsr", true).is_err());
+    }
+
+    #[test]
+    fn config_to_json() {
+        let config =
+            LintConfig::try_from(PathBuf::from("./test/.configparseroptions.json")).unwrap();
+        let json = config.to_json();
+
+        assert_eq!(json["parserOptions"]["ecmaVersion"], 2020);
+        assert_eq!(
+            json["rules"]["eqeqeq"],
+            json!([1, {"mode": "always", "null": "always"}])
+        );
+        assert_eq!(
+            json["rules"]["no-eval"],
+            json!([0, {"allowIndirect": false}])
+        );
+        assert_eq!(
+            json["rules"]["quotes"],
+            json!([2, {"prefer": "double", "avoidEscape": false, "allowTemplateLiterals": false}])
+        );
+        assert_eq!(json["noInlineConfig"], false);
+    }
+
+    #[test]
+    fn every_rule_is_configured_by_id() {
+        let mut config = LintConfig::default();
+        for meta in RULES {
+            config.set_rule(meta.id, &json!("warn")).unwrap();
+        }
+        let json = config.to_json();
+
+        assert_eq!(json["rules"].as_object().unwrap().len(), RULES.len());
+        for meta in RULES {
+            assert_eq!(json["rules"][meta.id][0], 1, "{}", meta.id);
+        }
+    }
+
+    #[test]
+    fn object_rule_options() {
+        let mut config = LintConfig::default();
+
+        config
+            .set_rule("no-unused-vars", &json!(["warn", "local"]))
+            .unwrap();
+        config
+            .set_rule("no-undef", &json!([2, { "typeof": true }]))
+            .unwrap();
+        let json = config.to_json();
+        assert_eq!(json["rules"]["no-undef"], json!([2, {"typeof": true}]));
+        assert_eq!(
+            json["rules"]["no-unused-vars"],
+            json!([1, {"vars": "local", "args": "after-used", "ignoreRestSiblings": false, "caughtErrors": "all"}])
+        );
+
+        assert!(config
+            .set_rule("no-undef", &json!([2, { "typeof": "yes" }]))
+            .is_err());
+        assert!(config
+            .set_rule("no-unused-vars", &json!([2, { "argsIgnorePattern": "(" }]))
+            .is_err());
+        assert!(config
+            .set_rule("no-unused-vars", &json!([2, { "args": "some" }]))
+            .is_err());
+    }
+
+    #[test]
     fn create_config_cjs() {
-        let _config = LintConfig::from(PathBuf::from("aconfig.cjs"));
+        assert!(LintConfig::try_from(PathBuf::from("aconfig.cjs")).is_err());
     }
 
     #[test]
-    #[should_panic]
     fn create_config_yml() {
-        let _config = LintConfig::from(PathBuf::from("aconfig.yml"));
+        assert!(LintConfig::try_from(PathBuf::from("aconfig.yml")).is_err());
     }
 
     #[test]
-    #[should_panic]
     fn create_config_yaml() {
-        let _config = LintConfig::from(PathBuf::from("aconfig.yaml"));
+        assert!(LintConfig::try_from(PathBuf::from("aconfig.yaml")).is_err());
     }
 }
@@ -0,0 +1,311 @@
+use std::path::Path;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+use swc_ecma_ast::EsVersion;
+use swc_ecma_parser::{error::SyntaxError, EsConfig, Syntax};
+
+/// ESLint's `parserOptions`, from configuration files or `--parser-options`
+///
+/// Every option is optional so that options from several sources can be merged,
+/// with the accessors below providing ESLint's defaults for anything left unset.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ParserOptions {
+    /// e.g., 5, 2015, 6, "latest"
+    #[serde(
+        default,
+        deserialize_with = "deserialize_ecma_version",
+        serialize_with = "serialize_ecma_version",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub ecma_version: Option<EsVersion>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_type: Option<SourceType>,
+    #[serde(default)]
+    pub ecma_features: EcmaFeatures,
+    /// Non-standard alias for `ecmaFeatures.globalReturn`, as accepted by @babel/eslint-parser
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow_return_outside_function: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SourceType {
+    Script,
+    Module,
+    /// A script with top-level `return` allowed, as in Node.js modules
+    Commonjs,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EcmaFeatures {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jsx: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub global_return: Option<bool>,
+}
+
+impl ParserOptions {
+    /// Parse `--parser-options` values, each of the form `key:value`
+    ///
+    /// Nested options can be reached with dots, e.g., `ecmaFeatures.jsx:true`.
+    pub fn from_cli(options: &[String]) -> Result<Self, String> {
+        let mut root = Map::new();
+
+        for option in options {
+            let (key, value) = option.split_once(':').ok_or_else(|| {
+                format!(
+                    "Invalid parser option {:?}. Expected the form \"key:value\".",
+                    option
+                )
+            })?;
+
+            // Values are JSON where possible (numbers, booleans), falling back to plain strings
+            let value = serde_json::from_str(value.trim())
+                .unwrap_or_else(|_| Value::String(value.trim().to_string()));
+
+            let mut keys: Vec<&str> = key.trim().split('.').collect();
+            let last = keys.pop().unwrap();
+            let mut map = &mut root;
+            for key in keys {
+                map = map
+                    .entry(key)
+                    .or_insert_with(|| Value::Object(Map::new()))
+                    .as_object_mut()
+                    .ok_or_else(|| format!("Parser option {:?} is not an object.", key))?;
+            }
+            map.insert(last.to_string(), value);
+        }
+
+        serde_json::from_value(Value::Object(root))
+            .map_err(|err| format!("Invalid parser options: {}", err))
+    }
+
+    /// Overwrite options with any that are set in `other`
+    pub fn merge(&mut self, other: &ParserOptions) {
+        if other.ecma_version.is_some() {
+            self.ecma_version = other.ecma_version;
+        }
+        if other.source_type.is_some() {
+            self.source_type = other.source_type;
+        }
+        if other.ecma_features.jsx.is_some() {
+            self.ecma_features.jsx = other.ecma_features.jsx;
+        }
+        if other.ecma_features.global_return.is_some() {
+            self.ecma_features.global_return = other.ecma_features.global_return;
+        }
+        if other.allow_return_outside_function.is_some() {
+            self.allow_return_outside_function = other.allow_return_outside_function;
+        }
+    }
+
+    /// The ECMAScript version to parse and lint against, defaulting to ES5 like ESLint
+    pub fn es_version(&self) -> EsVersion {
+        self.ecma_version.unwrap_or_default()
+    }
+
+    /// Syntax to give to the parser for the file at `path`
+    ///
+    /// JSX is enabled by `ecmaFeatures.jsx`, or for `.jsx` files when not explicitly disabled.
+    pub fn syntax(&self, path: &Path) -> Syntax {
+        let is_jsx_file = path.extension().is_some_and(|ext| ext == "jsx");
+
+        Syntax::Es(EsConfig {
+            jsx: self.ecma_features.jsx.unwrap_or(is_jsx_file),
+            ..Default::default()
+        })
+    }
+
+    pub fn allows_return_outside_function(&self) -> bool {
+        self.source_type == Some(SourceType::Commonjs)
+            || self.ecma_features.global_return == Some(true)
+            || self.allow_return_outside_function == Some(true)
+    }
+
+    /// Whether a recoverable parser error should be reported given these options
+    pub fn reports_error(&self, error: &SyntaxError) -> bool {
+        !(matches!(error, SyntaxError::ReturnNotAllowed) && self.allows_return_outside_function())
+    }
+}
+
+/// Map an ESLint `ecmaVersion` (3, 5, 6..13, 2015..2022 or "latest") onto an `EsVersion`
+pub fn ecma_version_from_value(value: &Value) -> Option<EsVersion> {
+    let year = match value {
+        Value::String(str) if str == "latest" => return Some(EsVersion::latest()),
+        Value::String(str) => str.parse::<u64>().ok()?,
+        Value::Number(num) => num.as_u64()?,
+        _ => return None,
+    };
+
+    // Edition numbers from 6 onwards map onto years from 2015
+    let year = if (6..2015).contains(&year) {
+        year + 2009
+    } else {
+        year
+    };
+
+    match year {
+        3 => Some(EsVersion::Es3),
+        5 => Some(EsVersion::Es5),
+        2015 => Some(EsVersion::Es2015),
+        2016 => Some(EsVersion::Es2016),
+        2017 => Some(EsVersion::Es2017),
+        2018 => Some(EsVersion::Es2018),
+        2019 => Some(EsVersion::Es2019),
+        2020 => Some(EsVersion::Es2020),
+        2021 => Some(EsVersion::Es2021),
+        2022 => Some(EsVersion::Es2022),
+        // Anything newer than swc knows about is treated as the latest version
+        year if year > 2022 => Some(EsVersion::latest()),
+        _ => None,
+    }
+}
+
+/// The year an `EsVersion` was published, as used in ESLint's `ecmaVersion`
+pub fn ecma_version_year(version: EsVersion) -> u64 {
+    match version {
+        EsVersion::Es3 => 3,
+        EsVersion::Es5 => 5,
+        EsVersion::Es2015 => 2015,
+        EsVersion::Es2016 => 2016,
+        EsVersion::Es2017 => 2017,
+        EsVersion::Es2018 => 2018,
+        EsVersion::Es2019 => 2019,
+        EsVersion::Es2020 => 2020,
+        EsVersion::Es2021 => 2021,
+        EsVersion::Es2022 => 2022,
+    }
+}
+
+fn deserialize_ecma_version<'de, D>(deserializer: D) -> Result<Option<EsVersion>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Value::deserialize(deserializer)?;
+    ecma_version_from_value(&value)
+        .map(Some)
+        .ok_or_else(|| de::Error::custom(format!("invalid ecmaVersion {}", value)))
+}
+
+fn serialize_ecma_version<S>(version: &Option<EsVersion>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match version {
+        Some(version) => serializer.serialize_u64(ecma_version_year(*version)),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ecma_version_edition_numbers() {
+        assert_eq!(
+            ecma_version_from_value(&Value::from(6)),
+            Some(EsVersion::Es2015)
+        );
+        assert_eq!(
+            ecma_version_from_value(&Value::from(13)),
+            Some(EsVersion::Es2022)
+        );
+    }
+
+    #[test]
+    fn ecma_version_years() {
+        assert_eq!(
+            ecma_version_from_value(&Value::from(2018)),
+            Some(EsVersion::Es2018)
+        );
+        assert_eq!(
+            ecma_version_from_value(&Value::from(5)),
+            Some(EsVersion::Es5)
+        );
+        assert_eq!(ecma_version_from_value(&Value::from(4)), None);
+    }
+
+    #[test]
+    fn ecma_version_latest() {
+        assert_eq!(
+            ecma_version_from_value(&Value::from("latest")),
+            Some(EsVersion::latest())
+        );
+    }
+
+    #[test]
+    fn default_es_version_is_es5() {
+        assert_eq!(ParserOptions::default().es_version(), EsVersion::Es5);
+    }
+
+    #[test]
+    fn parse_config_json() {
+        let options: ParserOptions = serde_json::from_str(
+            r#"{"ecmaVersion": 2020, "sourceType": "module", "ecmaFeatures": {"jsx": true}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(options.es_version(), EsVersion::Es2020);
+        assert_eq!(options.source_type, Some(SourceType::Module));
+        assert_eq!(options.ecma_features.jsx, Some(true));
+    }
+
+    #[test]
+    fn parse_cli_options() {
+        let options = ParserOptions::from_cli(&[
+            String::from("ecmaVersion:2021"),
+            String::from("sourceType:script"),
+            String::from("ecmaFeatures.globalReturn:true"),
+        ])
+        .unwrap();
+
+        assert_eq!(options.es_version(), EsVersion::Es2021);
+        assert_eq!(options.source_type, Some(SourceType::Script));
+        assert!(options.allows_return_outside_function());
+    }
+
+    #[test]
+    fn parse_cli_options_missing_value() {
+        assert_eq!(
+            ParserOptions::from_cli(&[String::from("ecmaVersion")]),
+            Err(String::from(
+                "Invalid parser option \"ecmaVersion\". Expected the form \"key:value\"."
+            ))
+        );
+        assert!(ParserOptions::from_cli(&[String::from("ecmaVersion:sometime")]).is_err());
+    }
+
+    #[test]
+    fn merge_overrides_set_options() {
+        let mut options = ParserOptions {
+            ecma_version: Some(EsVersion::Es2015),
+            source_type: Some(SourceType::Module),
+            ..Default::default()
+        };
+        options.merge(&ParserOptions {
+            ecma_version: Some(EsVersion::Es2022),
+            ..Default::default()
+        });
+
+        assert_eq!(options.ecma_version, Some(EsVersion::Es2022));
+        assert_eq!(options.source_type, Some(SourceType::Module));
+    }
+
+    #[test]
+    fn jsx_enabled_for_jsx_files() {
+        let options = ParserOptions::default();
+
+        assert!(matches!(
+            options.syntax(Path::new("a.jsx")),
+            Syntax::Es(EsConfig { jsx: true, .. })
+        ));
+        assert!(matches!(
+            options.syntax(Path::new("a.js")),
+            Syntax::Es(EsConfig { jsx: false, .. })
+        ));
+    }
+}
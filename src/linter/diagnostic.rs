@@ -0,0 +1,348 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use swc_common::{Loc, SourceMap, Span, DUMMY_SP};
+
+/// How seriously a rule's problems should be taken
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// 0 or "off" - the rule is turned off
+    Off,
+    /// 1 or "warn" - problems are warnings (doesn't affect exit code)
+    Warn,
+    /// 2 or "error" - problems are errors (exit code is 1 when triggered)
+    #[default]
+    Error,
+}
+
+impl Severity {
+    pub fn from_number(num: u8) -> Self {
+        match num {
+            0 => Severity::Off,
+            1 => Severity::Warn,
+            _ => Severity::Error,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "off" => Severity::Off,
+            "warn" => Severity::Warn,
+            _ => Severity::Error,
+        }
+    }
+}
+
+/// Severities are serialized as numbers, as in ESLint's JSON output
+impl Serialize for Severity {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+/// Severities can be deserialized from either of ESLint's forms, e.g., 1 or "warn"
+impl<'de> Deserialize<'de> for Severity {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Value::deserialize(deserializer)? {
+            Value::Number(num) if num.as_u64().is_some_and(|num| num <= 2) => {
+                Ok(Severity::from_number(num.as_u64().unwrap() as u8))
+            }
+            Value::String(str) if ["off", "warn", "error"].contains(&str.as_str()) => {
+                Ok(Severity::from_name(&str))
+            }
+            value => Err(de::Error::custom(format!("invalid severity {}", value))),
+        }
+    }
+}
+
+/// A replacement of part of a file's source, used to automatically fix a problem
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fix {
+    /// Start and end byte offsets of the text to replace
+    pub range: (u32, u32),
+    pub text: String,
+}
+
+impl Fix {
+    /// A fix which replaces the source at `span` with `text`
+    pub fn new(source_map: &SourceMap, span: Span, text: &str) -> Self {
+        let start = source_map.lookup_byte_offset(span.lo).pos;
+        let end = source_map.lookup_byte_offset(span.hi).pos;
+
+        Self {
+            range: (start.0, end.0),
+            text: text.to_string(),
+        }
+    }
+}
+
+/// An alternative fix, which is not applied automatically as it may change behaviour
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Suggestion {
+    pub desc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    pub fix: Fix,
+}
+
+/// The (1-based) column of a location in UTF-16 code units, like ESLint's columns, rather than
+/// bytes or display width
+pub(crate) fn column(loc: &Loc) -> usize {
+    let prefix = match loc.file.get_line(loc.line - 1) {
+        Some(line) => line.chars().take(loc.col.0).map(char::len_utf16).sum(),
+        None => loc.col.0,
+    };
+
+    prefix + 1
+}
+
+/// A single problem found in a file, serialized like ESLint's `LintMessage`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    /// The rule that reported the problem, or None for parsing errors
+    pub rule_id: Option<String>,
+    /// Set for problems that prevented the file from being parsed
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub fatal: bool,
+    pub severity: Severity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    pub message: String,
+    #[serde(skip)]
+    pub span: Span,
+    /// The (1-based) line number, or 0 if the problem has no location
+    pub line: usize,
+    /// The (1-based) column number, or 0 if the problem has no location
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Fix>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl Diagnostic {
+    /// A problem at `span`, located within the file using `source_map`
+    pub fn new(source_map: &SourceMap, span: Span, severity: Severity, message: String) -> Self {
+        let start = source_map.lookup_char_pos(span.lo);
+        let end = source_map.lookup_char_pos(span.hi);
+
+        Self {
+            rule_id: None,
+            fatal: false,
+            severity,
+            message_id: None,
+            message,
+            span,
+            line: start.line,
+            column: column(&start),
+            end_line: end.line,
+            end_column: column(&end),
+            fix: None,
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// A fatal error, for a file that could not be read or parsed
+    pub fn fatal(message: String, location: Option<(&SourceMap, Span)>) -> Self {
+        let mut diagnostic = match location {
+            Some((source_map, span)) => Diagnostic::new(source_map, span, Severity::Error, message),
+            None => Self {
+                rule_id: None,
+                fatal: true,
+                severity: Severity::Error,
+                message_id: None,
+                message,
+                span: DUMMY_SP,
+                line: 0,
+                column: 0,
+                end_line: 0,
+                end_column: 0,
+                fix: None,
+                suggestions: Vec::new(),
+            },
+        };
+        diagnostic.fatal = true;
+        diagnostic
+    }
+
+    pub fn with_message_id(mut self, message_id: &str) -> Self {
+        self.message_id = Some(message_id.to_string());
+        self
+    }
+
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+}
+
+/// Collects the diagnostics reported while linting a single file
+#[derive(Clone)]
+pub struct DiagnosticCollector {
+    source_map: Arc<SourceMap>,
+    diagnostics: Arc<Mutex<Vec<Diagnostic>>>,
+}
+
+impl DiagnosticCollector {
+    pub fn new(source_map: Arc<SourceMap>) -> Self {
+        Self {
+            source_map,
+            diagnostics: Default::default(),
+        }
+    }
+
+    /// A reporter for a rule, which reports problems with the rule's id and severity
+    pub fn reporter(&self, rule_id: &str, severity: Severity) -> Reporter {
+        Reporter {
+            collector: self.clone(),
+            rule_id: rule_id.to_string(),
+            severity,
+        }
+    }
+
+    pub fn push(&self, diagnostic: Diagnostic) {
+        self.diagnostics.lock().unwrap().push(diagnostic);
+    }
+
+    /// Take all diagnostics collected so far, in the order they appear in the file
+    pub fn take(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = std::mem::take(&mut *self.diagnostics.lock().unwrap());
+        diagnostics.sort_by_key(|diagnostic| (diagnostic.line, diagnostic.column));
+        diagnostics
+    }
+}
+
+impl fmt::Debug for DiagnosticCollector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DiagnosticCollector").finish()
+    }
+}
+
+/// Used by rules to report problems
+#[derive(Clone, Debug)]
+pub struct Reporter {
+    collector: DiagnosticCollector,
+    rule_id: String,
+    severity: Severity,
+}
+
+impl Reporter {
+    /// Create a diagnostic for this rule, which can be given a message id or fixes before
+    /// being passed to `emit`
+    pub fn diagnostic(&self, span: Span, message: &str) -> Diagnostic {
+        let mut diagnostic = Diagnostic::new(
+            &self.collector.source_map,
+            span,
+            self.severity,
+            message.to_string(),
+        );
+        diagnostic.rule_id = Some(self.rule_id.clone());
+        diagnostic
+    }
+
+    pub fn emit(&self, diagnostic: Diagnostic) {
+        self.collector.push(diagnostic);
+    }
+
+    /// A fix which replaces the source at `span` with `text`
+    pub fn fix(&self, span: Span, text: &str) -> Fix {
+        Fix::new(&self.collector.source_map, span, text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use swc_common::{BytePos, FileName};
+
+    fn collector(src: &str) -> (DiagnosticCollector, BytePos) {
+        let cm: Arc<SourceMap> = Default::default();
+        let file = cm.new_source_file(FileName::Anon, src.to_string());
+        (DiagnosticCollector::new(cm), file.start_pos)
+    }
+
+    #[test]
+    fn severity_from_config_values() {
+        assert_eq!(
+            serde_json::from_str::<Severity>("\"warn\"").unwrap(),
+            Severity::Warn
+        );
+        assert_eq!(
+            serde_json::from_str::<Severity>("2").unwrap(),
+            Severity::Error
+        );
+        assert!(serde_json::from_str::<Severity>("\"loud\"").is_err());
+    }
+
+    #[test]
+    fn report_locates_diagnostic() {
+        let (collector, start) = collector("a;\nb == c;");
+        let reporter = collector.reporter("eqeqeq", Severity::Warn);
+        reporter.emit(reporter.diagnostic(
+            Span::new(start + BytePos(3), start + BytePos(9), Default::default()),
+            "Expected \"===\"",
+        ));
+
+        let diagnostics = collector.take();
+        assert_eq!(diagnostics.len(), 1);
+
+        let diagnostic = &diagnostics[0];
+        assert_eq!(diagnostic.rule_id.as_deref(), Some("eqeqeq"));
+        assert_eq!(diagnostic.severity, Severity::Warn);
+        assert_eq!((diagnostic.line, diagnostic.column), (2, 1));
+        assert_eq!((diagnostic.end_line, diagnostic.end_column), (2, 7));
+    }
+
+    #[test]
+    fn take_sorts_by_location() {
+        let (collector, start) = collector("a;\nb;");
+        let reporter = collector.reporter("semi", Severity::Error);
+        reporter.emit(reporter.diagnostic(
+            Span::new(start + BytePos(3), start + BytePos(4), Default::default()),
+            "second",
+        ));
+        reporter.emit(reporter.diagnostic(
+            Span::new(start, start + BytePos(1), Default::default()),
+            "first",
+        ));
+
+        let messages: Vec<String> = collector
+            .take()
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect();
+        assert_eq!(messages, ["first", "second"]);
+    }
+
+    #[test]
+    fn fix_range_is_relative_to_file() {
+        let (collector, start) = collector("a == b;");
+        let reporter = collector.reporter("eqeqeq", Severity::Error);
+        let fix = reporter.fix(
+            Span::new(start + BytePos(2), start + BytePos(4), Default::default()),
+            "===",
+        );
+
+        assert_eq!(fix.range, (2, 4));
+    }
+
+    #[test]
+    fn fatal_diagnostic_serializes_fatal_field() {
+        let diagnostic = Diagnostic::fatal(String::from("Cannot read file"), None);
+        let json = serde_json::to_value(&diagnostic).unwrap();
+
+        assert_eq!(json["fatal"], Value::Bool(true));
+        assert_eq!(json["ruleId"], Value::Null);
+        assert_eq!(json["severity"], 2);
+        assert_eq!(json["line"], 0);
+    }
+}
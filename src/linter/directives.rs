@@ -0,0 +1,547 @@
+use std::collections::{HashMap, HashSet};
+
+use swc_common::{
+    comments::{Comment, CommentKind, SingleThreadedComments},
+    BytePos, SourceMap, Span,
+};
+
+use super::diagnostic::{column, Diagnostic, Fix, Severity};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirectiveKind {
+    /// `/* eslint-disable */`, until the next `/* eslint-enable */`
+    Disable,
+    /// `/* eslint-enable */`
+    Enable,
+    /// `// eslint-disable-line`, for the line the comment is on
+    DisableLine,
+    /// `// eslint-disable-next-line`, for the line after the comment
+    DisableNextLine,
+}
+
+/// A comment which disables or enables rules for part of a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Directive {
+    pub kind: DirectiveKind,
+    /// The rules affected, or empty for all rules
+    pub rules: Vec<String>,
+    /// Where each of `rules` is written in the comment
+    pub rule_spans: Vec<Span>,
+    /// Why the rules are disabled, from after `--` in the comment
+    pub justification: Option<String>,
+    /// The span of the whole comment
+    pub span: Span,
+    /// The (1-based) line and column the comment starts at
+    pub line: usize,
+    pub column: usize,
+    /// The (1-based) line the comment ends on
+    pub end_line: usize,
+}
+
+/// All comments in a file, in source order
+pub fn get_all_comments(comments: SingleThreadedComments) -> Vec<Comment> {
+    let (leading, trailing) = comments.take_all();
+    let mut all: Vec<Comment> = leading
+        .take()
+        .into_values()
+        .chain(trailing.take().into_values())
+        .flatten()
+        .collect();
+
+    // A comment can be both leading and trailing, so remove duplicates
+    all.sort_by_key(|comment| comment.span.lo);
+    all.dedup_by_key(|comment| comment.span.lo);
+    all
+}
+
+/// Split a comment's text into its body and any `-- justification`
+pub fn split_justification(text: &str) -> (&str, Option<&str>) {
+    let text = text.trim();
+
+    // The description must be separated by whitespace and at least two dashes
+    let mut search_from = 0;
+    while let Some(index) = text[search_from..].find("--") {
+        let start = search_from + index;
+        let before = text[..start].chars().last();
+        let dashes = text[start..].chars().take_while(|c| *c == '-').count();
+        let after = text[start + dashes..].chars().next();
+
+        if before.is_none_or(char::is_whitespace) && after.is_none_or(char::is_whitespace) {
+            let justification = text[start + dashes..].trim();
+            let justification = (!justification.is_empty()).then_some(justification);
+            return (text[..start].trim(), justification);
+        }
+
+        search_from = start + dashes;
+    }
+
+    (text, None)
+}
+
+/// Split a comma-separated list of names, e.g., rules in a directive
+pub fn split_list(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Parse a comment as a disable/enable directive, if it is one
+pub fn parse_directive(source_map: &SourceMap, comment: &Comment) -> Option<Directive> {
+    let (body, justification) = split_justification(&comment.text);
+
+    let (keyword, rules) = match body.split_once(char::is_whitespace) {
+        Some((keyword, rules)) => (keyword, split_list(rules)),
+        None => (body, Vec::new()),
+    };
+
+    let kind = match keyword {
+        "eslint-disable" => DirectiveKind::Disable,
+        "eslint-enable" => DirectiveKind::Enable,
+        "eslint-disable-line" => DirectiveKind::DisableLine,
+        "eslint-disable-next-line" => DirectiveKind::DisableNextLine,
+        _ => return None,
+    };
+
+    let start = source_map.lookup_char_pos(comment.span.lo);
+    let end = source_map.lookup_char_pos(comment.span.hi);
+
+    // The comment's text follows its opening `//` or `/*`, and each rule follows the last
+    let text_start = comment.span.lo + BytePos(2);
+    let mut cursor = comment.text.find(keyword).unwrap_or(0) + keyword.len();
+    let rule_spans = rules
+        .iter()
+        .map(|rule| {
+            let lo = cursor + comment.text[cursor..].find(rule.as_str()).unwrap_or(0);
+            cursor = lo + rule.len();
+            Span::new(
+                text_start + BytePos(lo as u32),
+                text_start + BytePos(cursor as u32),
+                Default::default(),
+            )
+        })
+        .collect();
+
+    match (comment.kind, kind) {
+        // Like ESLint, only line directives can be written as line comments
+        (CommentKind::Line, DirectiveKind::Disable | DirectiveKind::Enable) => return None,
+        // A disable-line directive must fit on the line it applies to
+        (CommentKind::Block, DirectiveKind::DisableLine) if start.line != end.line => return None,
+        _ => {}
+    }
+
+    Some(Directive {
+        kind,
+        rules,
+        rule_spans,
+        justification: justification.map(String::from),
+        span: comment.span,
+        line: start.line,
+        column: column(&start),
+        end_line: end.line,
+    })
+}
+
+/// Parse all disable/enable directives from a file's comments
+pub fn parse_directives(source_map: &SourceMap, comments: &[Comment]) -> Vec<Directive> {
+    comments
+        .iter()
+        .filter_map(|comment| parse_directive(source_map, comment))
+        .collect()
+}
+
+/// Find the directive which suppresses a diagnostic, if any
+///
+/// Fatal errors, such as parsing errors, can't be suppressed.
+pub fn find_suppressing_directive(
+    diagnostic: &Diagnostic,
+    directives: &[Directive],
+) -> Option<usize> {
+    let rule_id = match (&diagnostic.rule_id, diagnostic.fatal) {
+        (Some(rule_id), false) => rule_id.as_str(),
+        _ => return None,
+    };
+    let applies = |directive: &Directive| {
+        directive.rules.is_empty() || directive.rules.iter().any(|rule| rule == rule_id)
+    };
+
+    // Line directives only apply to a single line
+    let line_directive = directives.iter().position(|directive| {
+        let line = match directive.kind {
+            DirectiveKind::DisableLine => directive.line,
+            DirectiveKind::DisableNextLine => directive.end_line + 1,
+            _ => return false,
+        };
+        line == diagnostic.line && applies(directive)
+    });
+    if line_directive.is_some() {
+        return line_directive;
+    }
+
+    // Block directives apply from where they are written, until they are overridden
+    let mut disabled_all: Option<usize> = None;
+    let mut disabled: HashMap<&str, usize> = HashMap::new();
+    let mut enabled: HashSet<&str> = HashSet::new();
+
+    for (index, directive) in directives.iter().enumerate() {
+        if (directive.line, directive.column) > (diagnostic.line, diagnostic.column) {
+            break;
+        }
+
+        match (directive.kind, directive.rules.is_empty()) {
+            (DirectiveKind::Disable, true) => {
+                disabled_all = Some(index);
+                disabled.clear();
+                enabled.clear();
+            }
+            (DirectiveKind::Disable, false) => {
+                for rule in &directive.rules {
+                    disabled.insert(rule, index);
+                    enabled.remove(rule.as_str());
+                }
+            }
+            (DirectiveKind::Enable, true) => {
+                disabled_all = None;
+                disabled.clear();
+                enabled.clear();
+            }
+            (DirectiveKind::Enable, false) => {
+                for rule in &directive.rules {
+                    disabled.remove(rule.as_str());
+                    enabled.insert(rule);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    match disabled.get(rule_id) {
+        Some(index) => Some(*index),
+        None if !enabled.contains(rule_id) => disabled_all,
+        None => None,
+    }
+}
+
+/// Remove diagnostics which are suppressed by directives
+///
+/// Also returns the rules each directive suppressed problems from, in the same order as
+/// `directives`.
+pub fn apply_directives(
+    diagnostics: Vec<Diagnostic>,
+    directives: &[Directive],
+) -> (Vec<Diagnostic>, Vec<HashSet<String>>) {
+    let mut used = vec![HashSet::new(); directives.len()];
+
+    let diagnostics = diagnostics
+        .into_iter()
+        .filter(
+            |diagnostic| match find_suppressing_directive(diagnostic, directives) {
+                Some(index) => {
+                    // Diagnostics which can be suppressed always have a rule id
+                    used[index].insert(diagnostic.rule_id.clone().unwrap_or_default());
+                    false
+                }
+                None => true,
+            },
+        )
+        .collect();
+
+    (diagnostics, used)
+}
+
+/// Report disable directives which didn't suppress any problems, with fixes to remove them
+///
+/// `used` holds the rules each directive suppressed problems from, as returned by
+/// `apply_directives`.
+pub fn report_unused_directives(
+    source_map: &SourceMap,
+    directives: &[Directive],
+    used: &[HashSet<String>],
+    severity: Severity,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for (directive, used) in directives.iter().zip(used) {
+        if directive.kind == DirectiveKind::Enable {
+            continue;
+        }
+
+        let unused: Vec<usize> = (0..directive.rules.len())
+            .filter(|index| !used.contains(&directive.rules[*index]))
+            .collect();
+
+        if directive.rules.is_empty() && used.is_empty()
+            || !directive.rules.is_empty() && unused.len() == directive.rules.len()
+        {
+            // Nothing was suppressed, so the whole comment can go
+            let message = match directive.rules.as_slice() {
+                [rule] => format!(
+                    "Unused eslint-disable directive (no problems were reported from '{}').",
+                    rule
+                ),
+                _ => String::from("Unused eslint-disable directive (no problems were reported)."),
+            };
+            let fix = Fix::new(
+                source_map,
+                comment_removal_span(source_map, directive.span),
+                "",
+            );
+
+            diagnostics
+                .push(Diagnostic::new(source_map, directive.span, severity, message).with_fix(fix));
+            continue;
+        }
+
+        // Otherwise, remove unused rules from the list, along with a separating comma
+        for index in unused {
+            let span = directive.rule_spans[index];
+            let removal = if index > 0 {
+                span.with_lo(directive.rule_spans[index - 1].hi)
+            } else {
+                span.with_hi(directive.rule_spans[index + 1].lo)
+            };
+            let message = format!(
+                "Unused eslint-disable directive (no problems were reported from '{}').",
+                directive.rules[index]
+            );
+            let fix = Fix::new(source_map, removal, "");
+
+            diagnostics.push(Diagnostic::new(source_map, span, severity, message).with_fix(fix));
+        }
+    }
+
+    diagnostics
+}
+
+/// The span to remove to delete a comment: the comment and whitespace before it, or the whole
+/// line if the comment is on a line of its own
+fn comment_removal_span(source_map: &SourceMap, span: Span) -> Span {
+    let start = source_map.lookup_byte_offset(span.lo);
+    let end = source_map.lookup_byte_offset(span.hi);
+    let src = start.sf.src.as_str();
+    let file_start = span.lo - start.pos;
+
+    let line_start = src[..start.pos.0 as usize].trim_end_matches([' ', '\t']);
+    let line_end = src[end.pos.0 as usize..].trim_start_matches([' ', '\t']);
+
+    let at_line_start = line_start.is_empty() || line_start.ends_with('\n');
+    let at_line_end = line_end.is_empty() || line_end.starts_with(['\r', '\n']);
+
+    let lo = file_start + BytePos(line_start.len() as u32);
+    if at_line_start && at_line_end {
+        // Remove the line, including its line break
+        let rest = line_end
+            .strip_prefix("\r\n")
+            .or_else(|| line_end.strip_prefix('\n'))
+            .unwrap_or(line_end);
+        let hi = file_start + BytePos((src.len() - rest.len()) as u32);
+        Span::new(lo, hi, Default::default())
+    } else {
+        span.with_lo(lo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use swc_common::{input::SourceFileInput, BytePos, FileName};
+    use swc_ecma_parser::lexer::Lexer;
+
+    use crate::linter::diagnostic::Severity;
+
+    /// Parse directives from a source, with a diagnostic at each `!` character
+    fn check(src: &str) -> Vec<(usize, usize)> {
+        let (_, diagnostics, _) = lint(src);
+        diagnostics
+            .into_iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.column))
+            .collect()
+    }
+
+    /// Messages for unused directives in a source, and the source with them all fixed
+    fn unused(src: &str) -> (Vec<String>, String) {
+        let (_, _, unused) = lint(src);
+        let messages = unused.iter().map(|d| d.message.clone()).collect();
+
+        let mut output = src.to_string();
+        for diagnostic in unused.iter().rev() {
+            let (start, end) = diagnostic.fix.as_ref().unwrap().range;
+            output.replace_range(start as usize..end as usize, "");
+        }
+
+        (messages, output)
+    }
+
+    fn lint(src: &str) -> (SourceMap, Vec<Diagnostic>, Vec<Diagnostic>) {
+        let cm = SourceMap::default();
+        let file = cm.new_source_file(FileName::Anon, src.to_string());
+
+        // Running the lexer is enough to collect comments
+        let comments = SingleThreadedComments::default();
+        let lexer = Lexer::new(
+            Default::default(),
+            Default::default(),
+            SourceFileInput::from(&*file),
+            Some(&comments),
+        );
+        lexer.for_each(drop);
+
+        let comments = get_all_comments(comments);
+        let directives = parse_directives(&cm, &comments);
+
+        let diagnostics = src
+            .match_indices('!')
+            .map(|(index, _)| {
+                let pos = file.start_pos + BytePos(index as u32);
+                let mut diagnostic = Diagnostic::new(
+                    &cm,
+                    Span::new(pos, pos, Default::default()),
+                    Severity::Error,
+                    String::from("problem"),
+                );
+                diagnostic.rule_id = Some(String::from("semi"));
+                diagnostic
+            })
+            .collect();
+
+        let (diagnostics, used) = apply_directives(diagnostics, &directives);
+        let unused = report_unused_directives(&cm, &directives, &used, Severity::Error);
+        (cm, diagnostics, unused)
+    }
+
+    #[test]
+    fn justification() {
+        assert_eq!(
+            split_justification(" eslint-disable semi -- legacy code "),
+            ("eslint-disable semi", Some("legacy code"))
+        );
+        assert_eq!(
+            split_justification("eslint-disable no-eval"),
+            ("eslint-disable no-eval", None)
+        );
+        assert_eq!(
+            split_justification("eslint-disable a--b"),
+            ("eslint-disable a--b", None)
+        );
+    }
+
+    #[test]
+    fn rule_list() {
+        assert_eq!(split_list(" semi,quotes , "), ["semi", "quotes"]);
+    }
+
+    #[test]
+    fn disable_and_enable() {
+        assert_eq!(
+            check("!\n/* eslint-disable */\n!\n/* eslint-enable */\n!"),
+            [(1, 1), (5, 1)]
+        );
+    }
+
+    #[test]
+    fn disable_specific_rules() {
+        assert_eq!(check("/* eslint-disable quotes */\n!"), [(2, 1)]);
+        assert_eq!(check("/* eslint-disable quotes, semi */\n!"), []);
+    }
+
+    #[test]
+    fn enable_rule_after_disabling_all() {
+        assert_eq!(
+            check("/* eslint-disable */\n!\n/* eslint-enable semi */\n!"),
+            [(4, 1)]
+        );
+    }
+
+    #[test]
+    fn disable_line() {
+        assert_eq!(check("! // eslint-disable-line\n!"), [(2, 1)]);
+        assert_eq!(
+            check("! /* eslint-disable-line semi -- reason */\n!"),
+            [(2, 1)]
+        );
+    }
+
+    #[test]
+    fn disable_next_line() {
+        assert_eq!(check("// eslint-disable-next-line semi\n!\n!"), [(3, 1)]);
+        assert_eq!(
+            check("/* eslint-disable-next-line\n  semi */\n!\n!"),
+            [(4, 1)]
+        );
+    }
+
+    #[test]
+    fn line_comments_cannot_disable_blocks() {
+        assert_eq!(check("// eslint-disable\n!"), [(2, 1)]);
+    }
+
+    #[test]
+    fn used_directives_are_not_reported() {
+        assert_eq!(
+            unused("! // eslint-disable-line semi\n").0,
+            Vec::<String>::new()
+        );
+        assert_eq!(unused("/* eslint-disable */\n!\n").0, Vec::<String>::new());
+    }
+
+    #[test]
+    fn unused_directives_are_removed() {
+        assert_eq!(
+            unused("a; // eslint-disable-line\n/* eslint-disable quotes */\nb;\n"),
+            (
+                vec![
+                    String::from("Unused eslint-disable directive (no problems were reported)."),
+                    String::from(
+                        "Unused eslint-disable directive (no problems were reported from 'quotes')."
+                    ),
+                ],
+                String::from("a;\nb;\n")
+            )
+        );
+    }
+
+    #[test]
+    fn unused_rules_are_removed_from_directives() {
+        assert_eq!(
+            unused("! // eslint-disable-line quotes, semi, no-undef\n").1,
+            "! // eslint-disable-line semi\n"
+        );
+        assert_eq!(
+            unused("! // eslint-disable-line semi, no-undef\n"),
+            (
+                vec![String::from(
+                    "Unused eslint-disable directive (no problems were reported from 'no-undef')."
+                )],
+                String::from("! // eslint-disable-line semi\n")
+            )
+        );
+    }
+
+    #[test]
+    fn columns_count_utf16_code_units() {
+        assert_eq!(check("'日本語'; !\n'😀'; !"), [(1, 8), (2, 7)]);
+
+        let (_, _, unused) = lint("'日本語'; // eslint-disable-line\n");
+        assert_eq!((unused[0].line, unused[0].column), (1, 8));
+    }
+
+    #[test]
+    fn fatal_errors_are_not_suppressed() {
+        let mut diagnostic = Diagnostic::fatal(String::from("Parsing error"), None);
+        diagnostic.line = 2;
+        let directive = Directive {
+            kind: DirectiveKind::Disable,
+            rules: Vec::new(),
+            rule_spans: Vec::new(),
+            justification: None,
+            span: Default::default(),
+            line: 1,
+            column: 1,
+            end_line: 1,
+        };
+
+        assert_eq!(find_suppressing_directive(&diagnostic, &[directive]), None);
+    }
+}
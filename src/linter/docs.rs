@@ -0,0 +1,217 @@
+//! Documentation of rules, generated from their metadata in [RULES](super::RULES)
+//!
+//! Every rule has a Markdown page in `docs/rules`, and the README lists every rule between
+//! `<!-- rules:start -->` and `<!-- rules:end -->`. A test checks both are up to date, and
+//! writes them instead when run with `MSRLINT_UPDATE_DOCS=1`.
+
+use std::{fs, io, path::Path};
+
+use super::{rule::RuleMeta, rules::RULES};
+
+/// Where rule pages are written, relative to the root of the repository
+pub const DOCS_DIR: &str = "docs/rules";
+
+const LIST_START: &str = "<!-- rules:start -->";
+const LIST_END: &str = "<!-- rules:end -->";
+
+/// The Markdown page documenting a rule
+pub fn rule_page(meta: &RuleMeta) -> String {
+    let schema = serde_json::to_string_pretty(&(meta.schema)()).unwrap();
+    let options = if schema == "[]" {
+        String::from("This rule has no options.\n")
+    } else {
+        format!(
+            "Options after the rule's severity, as a JSON schema:\n\n```json\n{}\n```\n",
+            schema
+        )
+    };
+
+    format!(
+        "<!-- Generated from the rule's metadata with `MSRLINT_UPDATE_DOCS=1 cargo test docs`. -->\n\
+        \n\
+        # {id}\n\
+        \n\
+        {description}.\n\
+        \n\
+        - Type: {rule_type} ({title})\n\
+        - Recommended: {recommended}\n\
+        - Fixable: {fixable}\n\
+        - Suggestions: {suggestions}\n\
+        \n\
+        ## Options\n\
+        \n\
+        {options}\n\
+        ## Examples\n\
+        \n\
+        See [ESLint's documentation]({url}).\n",
+        id = meta.id,
+        description = meta.description,
+        rule_type = meta.rule_type.name(),
+        title = meta.rule_type.title(),
+        recommended = if meta.recommended {
+            "yes, by `eslint:recommended`"
+        } else {
+            "no"
+        },
+        fixable = match meta.fixable {
+            Some(fixable) => format!("{}, with `--fix`", fixable.name()),
+            None => String::from("no"),
+        },
+        suggestions = if meta.has_suggestions { "yes" } else { "no" },
+        options = options,
+        url = meta.docs_url,
+    )
+}
+
+/// The Markdown list of every rule, linking to their pages, as in the README
+pub fn rule_list() -> String {
+    RULES
+        .iter()
+        .map(|meta| {
+            let mut line = format!(
+                "- [`{id}`]({dir}/{id}.md): {description}",
+                id = meta.id,
+                dir = DOCS_DIR,
+                description = meta.description
+            );
+            if meta.recommended {
+                line.push_str(" ✅");
+            }
+            if meta.fixable.is_some() {
+                line.push_str(" 🔧");
+            }
+            if meta.has_suggestions {
+                line.push_str(" 💡");
+            }
+            line + "\n"
+        })
+        .collect()
+}
+
+/// Replace the list of rules in a README, or None if it has no list to replace
+pub fn update_readme(readme: &str) -> Option<String> {
+    let start = readme.find(LIST_START)? + LIST_START.len();
+    let end = start + readme[start..].find(LIST_END)?;
+
+    Some(format!(
+        "{}\n{}{}",
+        &readme[..start],
+        rule_list(),
+        &readme[end..]
+    ))
+}
+
+/// Write the page of every rule to `docs/rules`, and the list of rules to the README, in the
+/// repository at `root`
+///
+/// Pages of rules which no longer exist are removed.
+pub fn write_docs(root: &Path) -> io::Result<()> {
+    let dir = root.join(DOCS_DIR);
+    fs::create_dir_all(&dir)?;
+
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        let is_rule_page = path
+            .file_stem()
+            .is_some_and(|stem| RULES.iter().any(|meta| stem.to_str() == Some(meta.id)));
+        if !is_rule_page && path.extension().is_some_and(|ext| ext == "md") {
+            fs::remove_file(path)?;
+        }
+    }
+
+    for meta in RULES {
+        fs::write(dir.join(format!("{}.md", meta.id)), rule_page(meta))?;
+    }
+
+    let readme_path = root.join("README.md");
+    let readme = fs::read_to_string(&readme_path)?;
+    let readme = update_readme(&readme).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("README.md has no {} and {} comments", LIST_START, LIST_END),
+        )
+    })?;
+    fs::write(readme_path, readme)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+
+    use serde_json::Value;
+
+    #[test]
+    fn rules_are_ordered_by_id() {
+        let ids: Vec<&str> = RULES.iter().map(|meta| meta.id).collect();
+        let mut sorted = ids.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+
+        assert_eq!(ids, sorted);
+    }
+
+    #[test]
+    fn schemas_are_arrays_or_objects() {
+        for meta in RULES {
+            assert!(
+                matches!((meta.schema)(), Value::Array(_) | Value::Object(_)),
+                "{}",
+                meta.id
+            );
+        }
+    }
+
+    #[test]
+    fn rule_page_describes_rule() {
+        let meta = RULES.iter().find(|meta| meta.id == "eqeqeq").unwrap();
+        let page = rule_page(meta);
+
+        assert!(page.contains("# eqeqeq\n\nRequire the use of `===` and `!==`.\n"));
+        assert!(page.contains("- Type: suggestion (Suggestions)\n"));
+        assert!(page.contains("- Fixable: code, with `--fix`\n"));
+        assert!(page.contains("\"enum\": [\n"));
+        assert!(page.contains("(https://eslint.org/docs/latest/rules/eqeqeq)"));
+    }
+
+    #[test]
+    fn update_readme_replaces_list() {
+        let readme = format!("Rules:\n{}\n- old\n{}\nMore", LIST_START, LIST_END);
+        let updated = update_readme(&readme).unwrap();
+
+        assert!(updated.starts_with(&format!("Rules:\n{}\n- [`consistent-return`]", LIST_START)));
+        assert!(updated.ends_with(&format!("- [`semi`](docs/rules/semi.md): Require or disallow semicolons instead of ASI 🔧\n{}\nMore", LIST_END)));
+        assert!(!updated.contains("- old"));
+        assert_eq!(update_readme("No list"), None);
+    }
+
+    /// Fails if the docs weren't regenerated after rules changed
+    #[test]
+    fn docs_are_up_to_date() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        if env::var_os("MSRLINT_UPDATE_DOCS").is_some() {
+            write_docs(root).unwrap();
+        }
+
+        let outdated = "Docs are out of date, run `MSRLINT_UPDATE_DOCS=1 cargo test docs`";
+        for meta in RULES {
+            let path = root.join(DOCS_DIR).join(format!("{}.md", meta.id));
+            assert_eq!(
+                fs::read_to_string(&path).ok(),
+                Some(rule_page(meta)),
+                "{}",
+                outdated
+            );
+        }
+        assert_eq!(
+            fs::read_dir(root.join(DOCS_DIR)).unwrap().count(),
+            RULES.len(),
+            "{}",
+            outdated
+        );
+
+        let readme = fs::read_to_string(root.join("README.md")).unwrap();
+        assert_eq!(update_readme(&readme), Some(readme), "{}", outdated);
+    }
+}
@@ -0,0 +1,139 @@
+use std::{fs, io};
+
+use super::{diagnostic::Diagnostic, rules::get_rule_type, LintResult};
+
+/// The most times a file is fixed and linted again, in case fixes cause new problems
+pub const MAX_FIX_PASSES: usize = 10;
+
+/// The kinds of fixes which can be applied, as selected by `--fix-type`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FixType {
+    /// Fixes for unused disable directives
+    Directive,
+    /// Fixes from rules which find code that causes errors or confusing behaviour
+    Problem,
+    /// Fixes from rules which suggest a better way of doing something
+    Suggestion,
+    /// Fixes from rules which only change whitespace, semicolons, etc.
+    Layout,
+}
+
+impl FixType {
+    /// The type of a diagnostic's fix: directive for problems without a rule, or otherwise the
+    /// type of the rule
+    pub fn of(diagnostic: &Diagnostic) -> Option<FixType> {
+        match &diagnostic.rule_id {
+            Some(rule_id) => get_rule_type(rule_id),
+            None => Some(FixType::Directive),
+        }
+    }
+}
+
+/// The result of applying fixes to a file's source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixOutput {
+    /// Whether any fixes were applied
+    pub fixed: bool,
+    pub output: String,
+}
+
+/// Apply the fixes of diagnostics matching `should_fix` to `source`
+///
+/// Like ESLint, fixes are applied in order, and fixes which overlap an earlier fix are skipped,
+/// to be applied in another pass.
+pub fn apply_fixes<F>(source: &str, diagnostics: &[Diagnostic], should_fix: F) -> FixOutput
+where
+    F: Fn(&Diagnostic) -> bool,
+{
+    let mut fixes: Vec<_> = diagnostics
+        .iter()
+        .filter(|diagnostic| should_fix(diagnostic))
+        .filter_map(|diagnostic| diagnostic.fix.as_ref())
+        .collect();
+    fixes.sort_by_key(|fix| fix.range);
+
+    let mut output = String::with_capacity(source.len());
+    let mut last_end: Option<usize> = None;
+
+    for fix in fixes {
+        let (start, end) = (fix.range.0 as usize, fix.range.1 as usize);
+        if last_end.is_some_and(|last_end| last_end >= start) || end > source.len() {
+            continue;
+        }
+
+        output.push_str(&source[last_end.unwrap_or(0)..start]);
+        output.push_str(&fix.text);
+        last_end = Some(end);
+    }
+
+    output.push_str(&source[last_end.unwrap_or(0)..]);
+
+    FixOutput {
+        fixed: last_end.is_some(),
+        output,
+    }
+}
+
+/// Write fixed files back to disk, e.g., for `--fix`
+pub fn output_fixes(results: &[LintResult]) -> io::Result<()> {
+    for result in results {
+        if let Some(output) = &result.output {
+            fs::write(&result.file_path, output)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::linter::diagnostic::{Fix, Severity};
+
+    fn diagnostic(rule_id: Option<&str>, range: (u32, u32), text: &str) -> Diagnostic {
+        let mut diagnostic = Diagnostic::fatal(String::from("problem"), None);
+        diagnostic.fatal = false;
+        diagnostic.severity = Severity::Error;
+        diagnostic.rule_id = rule_id.map(String::from);
+        diagnostic.with_fix(Fix {
+            range,
+            text: text.to_string(),
+        })
+    }
+
+    #[test]
+    fn applies_fixes_in_order() {
+        let diagnostics = [
+            diagnostic(Some("semi"), (6, 6), ";"),
+            diagnostic(Some("eqeqeq"), (2, 4), "==="),
+        ];
+        let output = apply_fixes("a == b", &diagnostics, |_| true);
+
+        assert!(output.fixed);
+        assert_eq!(output.output, "a === b;");
+    }
+
+    #[test]
+    fn skips_overlapping_fixes() {
+        let diagnostics = [
+            diagnostic(Some("eqeqeq"), (2, 4), "==="),
+            diagnostic(Some("eqeqeq"), (3, 6), "x"),
+        ];
+        let output = apply_fixes("a == b", &diagnostics, |_| true);
+
+        assert_eq!(output.output, "a === b");
+    }
+
+    #[test]
+    fn filters_fixes() {
+        let diagnostics = [diagnostic(None, (0, 3), "")];
+        let output = apply_fixes("/**/a", &diagnostics, |diagnostic| {
+            FixType::of(diagnostic) == Some(FixType::Layout)
+        });
+
+        assert!(!output.fixed);
+        assert_eq!(output.output, "/**/a");
+        assert_eq!(FixType::of(&diagnostics[0]), Some(FixType::Directive));
+    }
+}
@@ -1,71 +1,529 @@
-use std::path::Path;
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
 
+pub mod code_path;
 pub mod config;
+pub mod diagnostic;
+mod directives;
+pub mod docs;
+pub mod fix;
+mod result;
 mod rule;
+pub mod rule_tester;
 mod rules;
+pub mod scope;
+pub mod tokens;
+
+pub use result::LintResult;
+pub use rules::{get_rule_meta, RULES};
 
+use rayon::prelude::*;
 use swc_common::{
-    self,
-    errors::{ColorConfig, Handler},
-    input::SourceFileInput,
-    sync::Lrc,
-    SourceMap,
+    self, comments::SingleThreadedComments, input::SourceFileInput, sync::Lrc, FileName,
+    SourceFile, SourceMap, Spanned,
 };
 
-use swc_ecma_ast::{EsVersion, Program};
-use swc_ecma_parser::{lexer::Lexer, Parser, Syntax};
+use swc_ecma_ast::Program;
+use swc_ecma_parser::{lexer::Lexer, Capturing, Parser};
 
+use code_path::CodePathAnalysis;
 use rules::get_all_rules;
-use swc_ecma_utils::HANDLER;
+use scope::ScopeManager;
+use tokens::Tokens;
+
+use crate::debug;
+
+use self::{
+    config::{LintConfig, SourceType},
+    diagnostic::{Diagnostic, DiagnosticCollector, Severity},
+    directives::Directive,
+    fix::{apply_fixes, FixType, MAX_FIX_PASSES},
+    rules::LintContext,
+};
+
+/// Lints files, or source code held in memory, with a resolved configuration
+#[derive(Debug, Clone, Default)]
+pub struct Linter {
+    lint_config: LintConfig,
+    fix: bool,
+    fix_types: Vec<FixType>,
+}
+
+impl Linter {
+    pub fn new(lint_config: LintConfig) -> Self {
+        Self {
+            lint_config,
+            ..Default::default()
+        }
+    }
+
+    /// Apply fixes to problems, setting `output` on results with the fixed source
+    ///
+    /// Fixes are only written to disk by `fix::output_fixes`.
+    pub fn with_fix(mut self, fix: bool) -> Self {
+        self.fix = fix;
+        self
+    }
+
+    /// Only apply fixes of these types, or all fixes if empty
+    pub fn with_fix_types(mut self, fix_types: Vec<FixType>) -> Self {
+        self.fix_types = fix_types;
+        self
+    }
+
+    pub fn config(&self) -> &LintConfig {
+        &self.lint_config
+    }
+
+    /// Whether problems are fixed, see `with_fix`
+    pub fn fixes(&self) -> bool {
+        self.fix
+    }
+
+    /// Lint the file at `path`
+    pub fn lint_path(&self, path: &Path) -> LintResult {
+        self.fix_result(lint_file(path, &self.lint_config))
+    }
+
+    /// Lint `source` as though it were the contents of the file at `path`
+    pub fn lint_text(&self, source: &str, path: &Path) -> LintResult {
+        self.fix_result(lint_text(source, path, &self.lint_config))
+    }
+
+    /// Lint files in parallel on the current rayon thread pool
+    ///
+    /// Results are returned in the same order as `paths`, regardless of completion order.
+    pub fn lint_paths(&self, paths: &[PathBuf]) -> Vec<LintResult> {
+        paths.par_iter().map(|path| self.lint_path(path)).collect()
+    }
+
+    fn should_fix(&self, diagnostic: &Diagnostic) -> bool {
+        self.fix_types.is_empty()
+            || FixType::of(diagnostic).is_some_and(|fix_type| self.fix_types.contains(&fix_type))
+    }
+
+    /// Fix the problems in a result, linting the fixed source again until nothing changes
+    fn fix_result(&self, mut result: LintResult) -> LintResult {
+        if !self.fix {
+            return result;
+        }
+
+        let mut output = None;
+
+        for _ in 0..MAX_FIX_PASSES {
+            let source = match result
+                .source_map
+                .as_ref()
+                .and_then(|cm| cm.files().first().cloned())
+            {
+                Some(source_file) => source_file.src.to_string(),
+                None => break,
+            };
 
-use self::{config::LintConfig, rules::LintContext};
+            let fixed = apply_fixes(&source, &result.messages, |diagnostic| {
+                self.should_fix(diagnostic)
+            });
+            if !fixed.fixed {
+                break;
+            }
+
+            result = lint_text(&fixed.output, &result.file_path, &self.lint_config);
+            output = Some(fixed.output);
+        }
+
+        result.output = output;
+        result
+    }
+}
+
+/// Lint file, returning the problems found
+///
+/// Files that can't be read or parsed produce fatal messages rather than stopping the run.
+pub fn lint_file(path: &Path, lint_config: &LintConfig) -> LintResult {
+    lint(path, lint_config, |cm| cm.load_file(path))
+}
+
+/// Lint source code, as though it were the contents of the file at `path`
+pub fn lint_text(source: &str, path: &Path, lint_config: &LintConfig) -> LintResult {
+    lint(path, lint_config, |cm| {
+        Ok(cm.new_source_file(FileName::Real(path.to_path_buf()), source.to_string()))
+    })
+}
+
+fn lint<F>(path: &Path, lint_config: &LintConfig, load: F) -> LintResult
+where
+    F: FnOnce(&SourceMap) -> io::Result<Lrc<SourceFile>>,
+{
+    let mut result = LintResult::new(path.to_path_buf());
 
-/// Lint file, returning the number of errors found
-pub fn lint_file(path: &Path, lint_config: &LintConfig) -> usize {
     let cm: Lrc<SourceMap> = Default::default();
-    let source_file = cm.load_file(path).unwrap();
-    let es_version: EsVersion = Default::default();
+    let diagnostics = DiagnosticCollector::new(cm.clone());
+
+    let directives = match load(&cm) {
+        Ok(source_file) => lint_source(path, &source_file, lint_config, cm.clone(), &diagnostics),
+        Err(err) => {
+            let msg = format!("Cannot read file {}: {}", path.display(), err);
+            diagnostics.push(Diagnostic::fatal(msg, None));
+            Vec::new()
+        }
+    };
+
+    // Drop problems that were disabled by comments in the file
+    let (mut messages, used) = directives::apply_directives(diagnostics.take(), &directives);
+    if lint_config.report_unused_disable_directives {
+        messages.extend(directives::report_unused_directives(
+            &cm,
+            &directives,
+            &used,
+            Severity::Error,
+        ));
+        messages.sort_by_key(|diagnostic| (diagnostic.line, diagnostic.column));
+    }
+
+    for diagnostic in messages {
+        result.add(diagnostic);
+    }
+    result.source_map = Some(cm);
+
+    result
+}
 
-    let handler = Handler::with_tty_emitter(ColorConfig::Auto, true, false, Some(cm.clone()));
+fn lint_source(
+    path: &Path,
+    source_file: &SourceFile,
+    lint_config: &LintConfig,
+    cm: Lrc<SourceMap>,
+    diagnostics: &DiagnosticCollector,
+) -> Vec<Directive> {
+    let parser_options = &lint_config.parser_options;
+    let es_version = parser_options.es_version();
 
+    let comments = SingleThreadedComments::default();
     let lexer = Lexer::new(
-        // We want to parse ecmascript
-        Syntax::Es(Default::default()),
+        // We want to parse ecmascript, with JSX if enabled
+        parser_options.syntax(path),
         // EsVersion defaults to es5
         es_version,
-        SourceFileInput::from(&*source_file),
-        None,
+        SourceFileInput::from(source_file),
+        Some(&comments),
+    );
+
+    let parse_start = Instant::now();
+    // Keep the tokens the parser reads, for rules which check tokens rather than nodes
+    let mut capturing = Capturing::new(lexer);
+    let mut parser = Parser::new_from(capturing.clone());
+    // Without a sourceType, decide between script and module based on the file's contents
+    let parsed = match parser_options.source_type {
+        Some(SourceType::Module) => parser.parse_module().map(Program::Module),
+        Some(SourceType::Script) | Some(SourceType::Commonjs) => {
+            parser.parse_script().map(Program::Script)
+        }
+        None => parser.parse_program(),
+    };
+
+    debug!(
+        "linter",
+        "Parsed {} in {:.2}ms",
+        path.display(),
+        parse_start.elapsed().as_secs_f64() * 1000.0
+    );
+
+    // Errors the parser was able to recover from are reported alongside any fatal error
+    let mut errors = parser.take_errors();
+    let tokens = Tokens::new(Capturing::take(&mut capturing));
+    errors.retain(|error| parser_options.reports_error(error.kind()));
+    let program = match parsed {
+        Ok(program) => Some(program),
+        Err(error) => {
+            errors.push(error);
+            None
+        }
+    };
+
+    for error in errors {
+        let msg = format!("Parsing error: {}", error.kind().msg());
+        diagnostics.push(Diagnostic::fatal(msg, Some((&cm, error.span()))));
+    }
+
+    // Without a program, there is nothing for rules to lint
+    let program = match program {
+        Some(program) => program,
+        None => return Vec::new(),
+    };
+
+    // Comments can configure the file and disable rules, unless inline config is turned off
+    let comments = directives::get_all_comments(comments);
+    let (file_config, directives) = if lint_config.no_inline_config {
+        (None, Vec::new())
+    } else {
+        (
+            config::inline::apply_inline_configs(lint_config, &cm, &comments, diagnostics),
+            directives::parse_directives(&cm, &comments),
+        )
+    };
+    if file_config.is_some() {
+        debug!(
+            "linter",
+            "Applied inline config comments in {}",
+            path.display()
+        );
+    }
+    let lint_config = file_config.as_ref().unwrap_or(lint_config);
+
+    let globals = lint_config.resolved_globals();
+    let scope_start = Instant::now();
+    let scope = ScopeManager::analyze(&program, &globals);
+    debug!(
+        "linter",
+        "Analyzed scopes of {} in {:.2}ms",
+        path.display(),
+        scope_start.elapsed().as_secs_f64() * 1000.0
     );
 
-    let mut parser = Parser::new_from(lexer);
-    let module = parser.parse_module().unwrap();
-    let program = Program::Module(module);
+    let code_path_start = Instant::now();
+    let code_paths = CodePathAnalysis::analyze(&program);
+    debug!(
+        "linter",
+        "Analyzed code paths of {} in {:.2}ms",
+        path.display(),
+        code_path_start.elapsed().as_secs_f64() * 1000.0
+    );
 
     let context = LintContext {
         program: &program,
         lint_config,
         es_version,
         source_map: cm,
+        diagnostics: diagnostics.clone(),
+        globals,
+        scope: Arc::new(scope),
+        code_paths: Arc::new(code_paths),
+        comments: Arc::new(comments),
+        tokens: Arc::new(tokens),
     };
 
-    let rules = get_all_rules(context);
+    // Comments can turn off every rule, leaving only parse errors and directives to report
+    let rules = get_all_rules(&context);
 
-    if rules.is_empty() {
-        panic!("No rules are enabled. Consider adding 'eslint:all' to the 'extends' property of your .eslintrc.* file.")
+    debug!(
+        "linter",
+        "Rules for {}: {}",
+        path.display(),
+        rules
+            .iter()
+            .map(|(rule_id, _)| *rule_id)
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    debug!(
+        "linter",
+        "{} global(s) and {} code path(s) in {}",
+        context.globals.len(),
+        context.code_paths.paths.len(),
+        path.display()
+    );
+
+    // apply all rules
+    for (_, mut rule) in rules {
+        match context.program {
+            Program::Module(m) => rule.lint_module(m),
+            Program::Script(s) => rule.lint_script(s),
+        }
     }
 
-    let mut num_errors = 0;
+    directives
+}
 
-    HANDLER.set(&handler, || {
-        // apply all rules
-        if let Program::Module(m) = program {
-            for mut rule in rules {
-                rule.lint_module(&m);
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    use crate::linter::{config::GlobalAccess, diagnostic::Severity};
+
+    fn linter() -> Linter {
+        // Globals used by the sources below, so they aren't reported by no-undef
+        let globals = ["a", "b", "c"].map(|name| (String::from(name), GlobalAccess::Readonly));
+
+        Linter::new(LintConfig {
+            rules: vec![String::from("eslint:all")],
+            globals: HashMap::from(globals),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn lint_text_reports_rule_diagnostics() {
+        let result = linter().lint_text("if (a == b) {}\n", Path::new("a.js"));
+
+        assert_eq!(result.file_path, PathBuf::from("a.js"));
+        assert_eq!(result.error_count, 1);
+
+        let diagnostic = &result.messages[0];
+        assert_eq!(diagnostic.rule_id.as_deref(), Some("eqeqeq"));
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!((diagnostic.line, diagnostic.column), (1, 5));
+    }
+
+    #[test]
+    fn lint_text_uses_scope_analysis() {
+        let source = "eval(a);\nfunction f(eval) { eval(b); }\nobj.eval(c);\n";
+        let result = linter().lint_text(source, Path::new("a.js"));
+
+        // Only the global eval is reported, not the parameter or the property
+        let no_eval: Vec<_> = result
+            .messages
+            .iter()
+            .filter(|diagnostic| diagnostic.rule_id.as_deref() == Some("no-eval"))
+            .map(|diagnostic| (diagnostic.line, diagnostic.column))
+            .collect();
+        assert_eq!(no_eval, [(1, 1)]);
+    }
+
+    #[test]
+    fn lint_text_reports_parse_errors() {
+        let result = linter().lint_text("var a = ;\n", Path::new("a.js"));
+
+        assert_eq!(result.fatal_error_count, 1);
+        assert!(result.messages[0].fatal);
+        assert_eq!((result.messages[0].line, result.messages[0].column), (1, 9));
+    }
 
-        num_errors += handler.err_count()
-    });
+    #[test]
+    fn lint_text_runs_only_rules_turned_on_without_eslint_all() {
+        let source = "if (a == b) {}\n";
+        let result = Linter::new(LintConfig::default()).lint_text(source, Path::new("a.js"));
+        assert!(result.messages.is_empty());
 
-    num_errors
+        let mut lint_config = LintConfig::default();
+        lint_config.set_rule("eqeqeq", &json!("warn")).unwrap();
+        let result = Linter::new(lint_config).lint_text(source, Path::new("a.js"));
+        let rule_ids: Vec<_> = result
+            .messages
+            .iter()
+            .map(|diagnostic| diagnostic.rule_id.as_deref())
+            .collect();
+        assert_eq!(rule_ids, [Some("eqeqeq")]);
+
+        // Comments in the file can turn rules on too
+        let result = Linter::new(LintConfig::default()).lint_text(
+            &format!("/* eslint semi: 2 */\n{}a\n", source),
+            Path::new("a.js"),
+        );
+        assert_eq!(result.error_count, 1);
+    }
+
+    #[test]
+    fn lint_text_without_enabled_rules() {
+        let rules: Vec<String> = RULES
+            .iter()
+            .map(|meta| format!("{}: off", meta.id))
+            .collect();
+        let source = format!(
+            "/* eslint {} */
+if (a == b) {{}}
+",
+            rules.join(", ")
+        );
+        let result = linter().lint_text(&source, Path::new("a.js"));
+
+        assert!(result.messages.is_empty());
+
+        // Parse errors are still reported
+        let result = linter().lint_text(&format!("{}var a = ;\n", source), Path::new("a.js"));
+        assert_eq!(result.fatal_error_count, 1);
+    }
+
+    #[test]
+    fn lint_text_applies_disable_directives() {
+        let source =
+            "if (a == b) {} // eslint-disable-line eqeqeq\n/* eslint-disable */\nif (a == b) {}\n";
+        let result = linter().lint_text(source, Path::new("a.js"));
+
+        assert_eq!(result.error_count, 0);
+        assert!(result.messages.is_empty());
+    }
+
+    #[test]
+    fn lint_text_applies_inline_config() {
+        let source = "/* eslint eqeqeq: warn, no-eval: off */\nif (a == b) { eval(c); }\n";
+        let result = linter().lint_text(source, Path::new("a.js"));
+
+        assert_eq!(result.error_count, 0);
+        assert_eq!(result.warning_count, 1);
+        assert_eq!(result.messages[0].rule_id.as_deref(), Some("eqeqeq"));
+
+        let result = linter().lint_text("/* eslint eqeqeq: loud */\n", Path::new("a.js"));
+        assert_eq!(result.error_count, 1);
+        assert_eq!(result.messages[0].rule_id, None);
+    }
+
+    #[test]
+    fn lint_text_without_inline_config() {
+        let mut linter = linter();
+        linter.lint_config.no_inline_config = true;
+
+        let source = "/* eslint eqeqeq: off */\nif (a == b) {} // eslint-disable-line\n";
+        let result = linter.lint_text(source, Path::new("a.js"));
+        assert_eq!(result.error_count, 1);
+    }
+
+    #[test]
+    fn lint_text_reports_unused_directives() {
+        let mut linter = linter();
+        linter.lint_config.report_unused_disable_directives = true;
+
+        let source = "if (a === b) {} // eslint-disable-line eqeqeq\n";
+        let result = linter.lint_text(source, Path::new("a.js"));
+        assert_eq!(result.error_count, 1);
+        assert_eq!(result.messages[0].rule_id, None);
+
+        let result = linter.with_fix(true).lint_text(source, Path::new("a.js"));
+        assert_eq!(result.error_count, 0);
+        assert_eq!(result.output.as_deref(), Some("if (a === b) {}\n"));
+    }
+
+    #[test]
+    fn lint_text_applies_fix_types() {
+        let mut linter = linter().with_fix(true);
+        linter.lint_config.report_unused_disable_directives = true;
+
+        let source = "a; // eslint-disable-line eqeqeq\n";
+        let result = linter
+            .clone()
+            .with_fix_types(vec![FixType::Directive])
+            .lint_text(source, Path::new("a.js"));
+        assert_eq!(result.output.as_deref(), Some("a;\n"));
+
+        let result = linter
+            .with_fix_types(vec![FixType::Problem])
+            .lint_text(source, Path::new("a.js"));
+        assert_eq!(result.output, None);
+    }
+
+    #[test]
+    fn lint_path_reports_unreadable_files() {
+        let result = linter().lint_path(Path::new("./test/src/doesNotExist.js"));
+
+        assert_eq!(result.fatal_error_count, 1);
+        assert_eq!(result.messages[0].line, 0);
+    }
+
+    #[test]
+    fn lint_paths_keeps_order() {
+        let paths = vec![
+            PathBuf::from("./test/src/myTestDir/another.js"),
+            PathBuf::from("./test/src/index.js"),
+        ];
+        let results = linter().lint_paths(&paths);
+
+        let result_paths: Vec<PathBuf> = results.into_iter().map(|r| r.file_path).collect();
+        assert_eq!(result_paths, paths);
+    }
 }
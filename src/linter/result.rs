@@ -0,0 +1,86 @@
+use std::{fmt, path::PathBuf, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use swc_common::SourceMap;
+
+use super::diagnostic::{Diagnostic, Severity};
+
+/// The outcome of linting a single file, mirroring ESLint's `LintResult`
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintResult {
+    pub file_path: PathBuf,
+    pub messages: Vec<Diagnostic>,
+    pub error_count: usize,
+    pub fatal_error_count: usize,
+    pub warning_count: usize,
+    /// The file's source after fixes were applied, if any were
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    /// The source map the file was loaded into, used by formatters to show code frames
+    #[serde(skip)]
+    pub source_map: Option<Arc<SourceMap>>,
+}
+
+impl LintResult {
+    pub fn new(file_path: PathBuf) -> Self {
+        Self {
+            file_path,
+            ..Default::default()
+        }
+    }
+
+    /// Record a diagnostic, counting it by severity
+    pub fn add(&mut self, diagnostic: Diagnostic) {
+        if diagnostic.fatal {
+            self.fatal_error_count += 1;
+        }
+
+        match diagnostic.severity {
+            Severity::Error => self.error_count += 1,
+            Severity::Warn => self.warning_count += 1,
+            Severity::Off => {}
+        }
+
+        self.messages.push(diagnostic);
+    }
+}
+
+impl fmt::Debug for LintResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LintResult")
+            .field("file_path", &self.file_path)
+            .field("messages", &self.messages)
+            .field("error_count", &self.error_count)
+            .field("fatal_error_count", &self.fatal_error_count)
+            .field("warning_count", &self.warning_count)
+            .field("output", &self.output)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use swc_common::{FileName, DUMMY_SP};
+
+    #[test]
+    fn add_counts_by_severity() {
+        let cm = SourceMap::default();
+        cm.new_source_file(FileName::Anon, String::from("a == b;"));
+
+        let mut result = LintResult::new(PathBuf::from("a.js"));
+        result.add(Diagnostic::new(
+            &cm,
+            DUMMY_SP,
+            Severity::Warn,
+            String::from("warning"),
+        ));
+        result.add(Diagnostic::fatal(String::from("Parsing error"), None));
+
+        assert_eq!(result.warning_count, 1);
+        assert_eq!(result.error_count, 1);
+        assert_eq!(result.fatal_error_count, 1);
+    }
+}
@@ -1,9 +1,16 @@
+use serde_json::{json, Value};
 use swc_ecma_ast::{Module, Script};
 use swc_ecma_lints::rule::Rule;
 use swc_ecma_visit::{Visit, VisitWith};
 
 use std::fmt::Debug;
 
+use super::{
+    config::{LintConfig, RuleSettings},
+    fix::FixType,
+    rules::LintContext,
+};
+
 #[derive(Debug)]
 struct VisitorRule<V>(V)
 where
@@ -24,7 +31,101 @@ where
 
 pub(crate) fn visitor_rule<V>(v: V) -> Box<dyn Rule>
 where
-    V: 'static + Send + Sync + Visit + Default + Debug,
+    V: 'static + Send + Sync + Visit + Debug,
 {
     Box::new(VisitorRule(v))
 }
+
+/// What a rule declares about itself, like ESLint's rule `meta`, and how to create it for a file
+#[derive(Debug)]
+pub struct RuleMeta {
+    pub id: &'static str,
+    /// One sentence, starting with a verb, e.g., "Disallow the use of `eval()`"
+    pub description: &'static str,
+    pub rule_type: RuleType,
+    /// Whether the rule is in `eslint:recommended`
+    pub recommended: bool,
+    pub fixable: Option<Fixable>,
+    pub has_suggestions: bool,
+    /// JSON schema of the options after the rule's severity
+    pub schema: fn() -> Value,
+    /// ESLint's documentation of the rule
+    pub docs_url: &'static str,
+    pub(crate) create: fn(&LintContext) -> Box<dyn Rule>,
+    /// The rule's severity and options in a [LintConfig]
+    pub(crate) config: fn(&LintConfig) -> &dyn RuleSettings,
+    pub(crate) config_mut: fn(&mut LintConfig) -> &mut dyn RuleSettings,
+}
+
+impl RuleMeta {
+    /// The metadata in ESLint's format, e.g., as printed by `--list-rules --format json`
+    pub fn to_json(&self) -> Value {
+        json!({
+            "type": self.rule_type.name(),
+            "docs": {
+                "description": self.description,
+                "recommended": self.recommended,
+                "url": self.docs_url,
+            },
+            "fixable": self.fixable.map(Fixable::name),
+            "hasSuggestions": self.has_suggestions,
+            "schema": (self.schema)(),
+        })
+    }
+}
+
+/// The category of a rule, as in ESLint's rule metadata, used to select fixes with `--fix-type`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleType {
+    /// Finds code that causes errors or confusing behaviour
+    Problem,
+    /// Suggests a better way of doing something
+    Suggestion,
+    /// Only cares about whitespace, semicolons, etc.
+    Layout,
+}
+
+impl RuleType {
+    pub fn name(self) -> &'static str {
+        match self {
+            RuleType::Problem => "problem",
+            RuleType::Suggestion => "suggestion",
+            RuleType::Layout => "layout",
+        }
+    }
+
+    /// The heading of the category in ESLint's documentation
+    pub fn title(self) -> &'static str {
+        match self {
+            RuleType::Problem => "Possible Problems",
+            RuleType::Suggestion => "Suggestions",
+            RuleType::Layout => "Layout & Formatting",
+        }
+    }
+}
+
+impl From<RuleType> for FixType {
+    fn from(rule_type: RuleType) -> Self {
+        match rule_type {
+            RuleType::Problem => FixType::Problem,
+            RuleType::Suggestion => FixType::Suggestion,
+            RuleType::Layout => FixType::Layout,
+        }
+    }
+}
+
+/// What a rule's fixes may change, as in ESLint's rule metadata
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fixable {
+    Code,
+    Whitespace,
+}
+
+impl Fixable {
+    pub fn name(self) -> &'static str {
+        match self {
+            Fixable::Code => "code",
+            Fixable::Whitespace => "whitespace",
+        }
+    }
+}
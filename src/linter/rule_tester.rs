@@ -0,0 +1,428 @@
+//! Run a rule on snippets of code and check its problems, like ESLint's `RuleTester`
+//!
+//! Tests are written in the same shape as ESLint's, so its rule tests can be ported by copying
+//! them into `json!`:
+//!
+//! ```
+//! use msrlint::linter::rule_tester::RuleTester;
+//! use serde_json::json;
+//!
+//! RuleTester::default().run(
+//!     "eqeqeq",
+//!     json!({
+//!         "valid": ["a === b", { "code": "a == null", "options": ["smart"] }],
+//!         "invalid": [{
+//!             "code": "typeof a == 'string'",
+//!             "output": "typeof a === 'string'",
+//!             "errors": [{ "messageId": "unexpected", "line": 1, "column": 1 }],
+//!         }],
+//!     }),
+//! );
+//! ```
+//!
+//! Any case which fails panics, naming the rule and the code of the case.
+
+use std::{collections::HashMap, path::Path};
+
+use serde::{Deserialize, Deserializer};
+use serde_json::{json, Value};
+
+use super::{
+    config::{GlobalAccess, LintConfig, ParserOptions},
+    diagnostic::Diagnostic,
+    fix::apply_fixes,
+    lint_text,
+    rules::{get_rule_meta, RULES},
+};
+
+/// The file name of cases without a `filename`, as in ESLint
+const DEFAULT_FILENAME: &str = "<input>";
+
+/// Runs the valid and invalid cases of rules, with configuration shared by every case
+#[derive(Debug, Clone, Default)]
+pub struct RuleTester {
+    config: TestConfig,
+}
+
+/// Configuration for a tester or a single case, in ESLint's format
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestConfig {
+    #[serde(default)]
+    parser_options: ParserOptions,
+    #[serde(default)]
+    env: HashMap<String, bool>,
+    #[serde(default)]
+    globals: HashMap<String, GlobalAccess>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuleTests {
+    #[serde(default)]
+    valid: Vec<TestCase>,
+    #[serde(default)]
+    invalid: Vec<TestCase>,
+}
+
+/// A valid or invalid case, which is either an object or, for valid cases, just the code
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestCase {
+    code: String,
+    /// Options for the rule, after its severity, e.g., `["smart"]` for eqeqeq
+    #[serde(default)]
+    options: Vec<Value>,
+    filename: Option<String>,
+    #[serde(flatten)]
+    config: TestConfig,
+    errors: Option<ExpectedErrors>,
+    /// The code after fixes, `Some(None)` for `null` when no fixes are expected
+    #[serde(default, deserialize_with = "deserialize_output")]
+    output: Option<Option<String>>,
+}
+
+/// Tell `"output": null` apart from an output that was left out
+fn deserialize_output<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Option<String>>, D::Error> {
+    Option::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ExpectedErrors {
+    Count(usize),
+    List(Vec<ExpectedError>),
+}
+
+/// An expected problem, either its message or the properties of it to check
+///
+/// Properties msrlint doesn't report, like the `type` of the node, are ignored.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ExpectedError {
+    Message(String),
+    Properties(ErrorProperties),
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorProperties {
+    message: Option<String>,
+    message_id: Option<String>,
+    line: Option<usize>,
+    column: Option<usize>,
+    end_line: Option<usize>,
+    end_column: Option<usize>,
+}
+
+impl RuleTester {
+    /// A tester with configuration for every case, e.g., `{"parserOptions": {"ecmaVersion": 6}}`
+    ///
+    /// Panics if the configuration is invalid.
+    pub fn new(config: Value) -> Self {
+        let config = serde_json::from_value(config)
+            .unwrap_or_else(|err| panic!("Invalid RuleTester configuration: {}", err));
+        Self { config }
+    }
+
+    /// Run a rule on `{"valid": [...], "invalid": [...]}` cases, panicking if any case fails
+    ///
+    /// Valid cases must produce no problems. Invalid cases must produce the problems in
+    /// `errors`, and fix the code to `output`, or not at all if `output` is null or left out.
+    pub fn run(&self, rule_id: &str, tests: Value) {
+        let tests: RuleTests = serde_json::from_value(
+            // Valid cases can be given as just their code
+            match tests {
+                Value::Object(mut tests) => {
+                    if let Some(Value::Array(valid)) = tests.get_mut("valid") {
+                        for case in valid.iter_mut().filter(|case| case.is_string()) {
+                            *case = json!({ "code": case.take() });
+                        }
+                    }
+                    Value::Object(tests)
+                }
+                tests => tests,
+            },
+        )
+        .unwrap_or_else(|err| panic!("Invalid tests for rule '{}': {}", rule_id, err));
+
+        for case in &tests.valid {
+            self.run_valid(rule_id, case);
+        }
+        for case in &tests.invalid {
+            self.run_invalid(rule_id, case);
+        }
+    }
+
+    fn run_valid(&self, rule_id: &str, case: &TestCase) {
+        let title = format!("{} valid case `{}`", rule_id, case.code);
+        assert!(
+            case.errors.is_none() && case.output.is_none(),
+            "{}: Valid cases must not have 'errors' or 'output' properties.",
+            title
+        );
+
+        let messages = self.lint(rule_id, case, &title);
+        assert!(
+            messages.is_empty(),
+            "{}: Should have no errors but had {}: {:#?}",
+            title,
+            messages.len(),
+            messages
+        );
+    }
+
+    fn run_invalid(&self, rule_id: &str, case: &TestCase) {
+        let title = format!("{} invalid case `{}`", rule_id, case.code);
+        let messages = self.lint(rule_id, case, &title);
+
+        match &case.errors {
+            None => panic!("{}: Invalid cases must have an 'errors' property.", title),
+            Some(ExpectedErrors::Count(0)) => {
+                panic!("{}: Invalid cases must have at least one error.", title)
+            }
+            Some(ExpectedErrors::List(errors)) if errors.is_empty() => {
+                panic!("{}: Invalid cases must have at least one error.", title)
+            }
+            Some(ExpectedErrors::Count(count)) => assert_eq!(
+                messages.len(),
+                *count,
+                "{}: Should have {} error(s) but had {}: {:#?}",
+                title,
+                count,
+                messages.len(),
+                messages
+            ),
+            Some(ExpectedErrors::List(errors)) => {
+                assert_eq!(
+                    messages.len(),
+                    errors.len(),
+                    "{}: Should have {} error(s) but had {}: {:#?}",
+                    title,
+                    errors.len(),
+                    messages.len(),
+                    messages
+                );
+                for (index, (message, error)) in messages.iter().zip(errors).enumerate() {
+                    check_error(&format!("{}, error {}", title, index), message, error);
+                }
+            }
+        }
+
+        // Like ESLint, fixes are applied in a single pass
+        let fixed = apply_fixes(&case.code, &messages, |_| true);
+        match &case.output {
+            Some(Some(output)) => {
+                assert_ne!(
+                    output, &case.code,
+                    "{}: Test property 'output' matches 'code'. If no autofix is expected, then omit the 'output' property or set it to null.",
+                    title
+                );
+                assert_eq!(&fixed.output, output, "{}: Output is incorrect.", title);
+            }
+            Some(None) => assert!(
+                !fixed.fixed,
+                "{}: Expected no autofixes to be suggested, but got {:?}.",
+                title, fixed.output
+            ),
+            None => assert!(
+                !fixed.fixed,
+                "{}: The rule fixed the code. Please add 'output' property with {:?}.",
+                title, fixed.output
+            ),
+        }
+    }
+
+    /// Lint a case with only the rule being tested, returning its problems
+    fn lint(&self, rule_id: &str, case: &TestCase, title: &str) -> Vec<Diagnostic> {
+        let mut lint_config = LintConfig {
+            rules: vec![String::from("eslint:all")],
+            ..Default::default()
+        };
+
+        if get_rule_meta(rule_id).is_none() {
+            panic!("Definition for rule '{}' was not found.", rule_id);
+        }
+        lint_config.disabled_rules = RULES.iter().map(|meta| meta.id.to_string()).collect();
+
+        let mut severity_and_options = vec![json!("error")];
+        severity_and_options.extend(case.options.iter().cloned());
+        lint_config
+            .set_rule(rule_id, &Value::Array(severity_and_options))
+            .unwrap_or_else(|err| panic!("{}: Invalid options: {}", title, err));
+
+        // Configuration of the case is applied over the tester's
+        for config in [&self.config, &case.config] {
+            lint_config.parser_options.merge(&config.parser_options);
+            for (name, enabled) in &config.env {
+                lint_config
+                    .set_env(name, *enabled)
+                    .unwrap_or_else(|err| panic!("{}: {}", title, err));
+            }
+            lint_config.globals.extend(config.globals.clone());
+        }
+
+        let filename = case.filename.as_deref().unwrap_or(DEFAULT_FILENAME);
+        let result = lint_text(&case.code, Path::new(filename), &lint_config);
+
+        if let Some(fatal) = result.messages.iter().find(|message| message.fatal) {
+            panic!(
+                "{}: A fatal parsing error occurred: {}",
+                title, fatal.message
+            );
+        }
+
+        result.messages
+    }
+}
+
+/// Check the properties of a problem which the case expects
+fn check_error(title: &str, message: &Diagnostic, error: &ExpectedError) {
+    let properties = match error {
+        ExpectedError::Message(expected) => {
+            assert_eq!(
+                &message.message, expected,
+                "{}: Error message is incorrect.",
+                title
+            );
+            return;
+        }
+        ExpectedError::Properties(properties) => properties,
+    };
+
+    if let Some(expected) = &properties.message {
+        assert_eq!(
+            &message.message, expected,
+            "{}: Error message is incorrect.",
+            title
+        );
+    }
+    if let Some(expected) = &properties.message_id {
+        assert_eq!(
+            message.message_id.as_ref(),
+            Some(expected),
+            "{}: Error messageId is incorrect.",
+            title
+        );
+    }
+
+    let locations = [
+        ("line", properties.line, message.line),
+        ("column", properties.column, message.column),
+        ("endLine", properties.end_line, message.end_line),
+        ("endColumn", properties.end_column, message.end_column),
+    ];
+    for (name, expected, actual) in locations {
+        if let Some(expected) = expected {
+            assert_eq!(actual, expected, "{}: Error {} is incorrect.", title, name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_valid_and_invalid_cases() {
+        RuleTester::default().run(
+            "eqeqeq",
+            json!({
+                "valid": [
+                    "a === b",
+                    { "code": "a == null", "options": ["always", { "null": "ignore" }] },
+                ],
+                "invalid": [
+                    {
+                        "code": "a == b",
+                        "errors": [{
+                            "message": "Expected \"===\" and instead saw \"==\".",
+                            "messageId": "unexpected",
+                            "type": "BinaryExpression",
+                            "line": 1,
+                            "column": 1,
+                            "endLine": 1,
+                            "endColumn": 7,
+                        }],
+                    },
+                    {
+                        "code": "typeof a != 'number'",
+                        "output": "typeof a !== 'number'",
+                        "errors": 1,
+                    },
+                    {
+                        "code": "a == b",
+                        "output": null,
+                        "errors": ["Expected \"===\" and instead saw \"==\"."],
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn applies_configuration_of_tester_and_cases() {
+        let tester = RuleTester::new(json!({ "parserOptions": { "ecmaVersion": 2015 } }));
+
+        tester.run(
+            "no-undef",
+            json!({
+                "valid": [
+                    { "code": "let a = b;", "globals": { "b": "readonly" } },
+                    { "code": "window.a;", "env": { "browser": true } },
+                    { "code": "<a />;", "filename": "a.jsx" },
+                ],
+                "invalid": [{
+                    "code": "let a = b;",
+                    "errors": [{ "message": "'b' is not defined.", "column": 9 }],
+                }],
+            }),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Should have no errors but had 1")]
+    fn fails_valid_case_with_problems() {
+        RuleTester::default().run("eqeqeq", json!({ "valid": ["a == b"] }));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error column is incorrect.")]
+    fn fails_invalid_case_with_wrong_location() {
+        RuleTester::default().run(
+            "eqeqeq",
+            json!({ "invalid": [{ "code": "a == b", "errors": [{ "column": 2 }] }] }),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The rule fixed the code.")]
+    fn fails_invalid_case_without_output() {
+        RuleTester::default().run(
+            "eqeqeq",
+            json!({ "invalid": [{ "code": "1 == 2", "errors": 1 }] }),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "A fatal parsing error occurred")]
+    fn fails_case_which_does_not_parse() {
+        RuleTester::default().run("eqeqeq", json!({ "valid": ["a =="] }));
+    }
+
+    #[test]
+    #[should_panic(expected = "Definition for rule 'no-var' was not found.")]
+    fn fails_unknown_rule() {
+        RuleTester::default().run("no-var", json!({ "valid": ["var a;"] }));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid options")]
+    fn fails_invalid_options() {
+        RuleTester::default().run(
+            "eqeqeq",
+            json!({ "valid": [{ "code": "a === b", "options": ["sometimes"] }] }),
+        );
+    }
+}
@@ -0,0 +1,516 @@
+use std::{fmt::Debug, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use swc_common::{BytePos, Span, Spanned};
+use swc_ecma_ast::{
+    ArrowExpr, BlockStmtOrExpr, ClassMethod, ClassProp, Constructor, Expr, FnDecl, FnExpr,
+    Function, GetterProp, KeyValueProp, Lit, MethodKind, MethodProp, PrivateMethod, PropName,
+    ReturnStmt, SetterProp, UnaryOp,
+};
+use swc_ecma_lints::rule::Rule;
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+
+use crate::linter::{
+    code_path::CodePathAnalysis,
+    config::{RuleConfig, RuleOptions},
+    diagnostic::Reporter,
+    rule::{visitor_rule, RuleMeta, RuleType},
+    rules::LintContext,
+};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsistentReturnConfig {
+    /// Treat `return undefined;` and `return void 0;` like `return;`
+    treat_undefined_as_unspecified: bool,
+}
+
+impl RuleOptions for ConsistentReturnConfig {
+    fn from_options(options: &[Value]) -> Result<Self, String> {
+        let object = options[0]
+            .as_object()
+            .ok_or_else(|| format!("Value {} should be object.", options[0]))?;
+
+        let mut config = Self::default();
+        for (key, value) in object {
+            match (key.as_str(), value) {
+                ("treatUndefinedAsUnspecified", Value::Bool(value)) => {
+                    config.treat_undefined_as_unspecified = *value
+                }
+                ("treatUndefinedAsUnspecified", value) => {
+                    return Err(format!("Value {} should be boolean.", value))
+                }
+                _ => {
+                    return Err(format!(
+                        "Value {} should NOT have additional properties.",
+                        options[0]
+                    ))
+                }
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+pub const META: RuleMeta = RuleMeta {
+    id: "consistent-return",
+    description: "Require `return` statements to either always or never specify values",
+    rule_type: RuleType::Problem,
+    recommended: false,
+    fixable: None,
+    has_suggestions: false,
+    schema,
+    docs_url: "https://eslint.org/docs/latest/rules/consistent-return",
+    create,
+    config: |config| &config.consistentreturn,
+    config_mut: |config| &mut config.consistentreturn,
+};
+
+fn schema() -> Value {
+    json!([{
+        "type": "object",
+        "properties": {
+            "treatUndefinedAsUnspecified": { "type": "boolean", "default": false },
+        },
+        "additionalProperties": false,
+    }])
+}
+
+fn create(context: &LintContext) -> Box<dyn Rule> {
+    consistentreturn(
+        context
+            .diagnostics
+            .reporter(META.id, context.lint_config.consistentreturn.severity()),
+        context.code_paths.clone(),
+        &context.lint_config.consistentreturn,
+    )
+}
+
+pub fn consistentreturn(
+    reporter: Reporter,
+    code_paths: Arc<CodePathAnalysis>,
+    config: &RuleConfig<ConsistentReturnConfig>,
+) -> Box<dyn Rule> {
+    visitor_rule(ConsistentReturn::new(reporter, code_paths, config))
+}
+
+/// A function which is being visited
+struct FunctionInfo {
+    /// How the function is described in messages, e.g., "method 'foo'"
+    name: String,
+    /// Whether the first `return` statement in the function has a value, if there is one
+    return_value: Option<bool>,
+}
+
+/// How a function is checked when it has been visited
+struct FunctionEnd {
+    /// The span its code path is found by
+    span: Span,
+    /// From the start of the function to its body, where a missing return is reported
+    head: Span,
+    /// Constructors don't need to return a value at the end
+    is_constructor: bool,
+}
+
+struct ConsistentReturn {
+    reporter: Reporter,
+    code_paths: Arc<CodePathAnalysis>,
+    treat_undefined_as_unspecified: bool,
+    functions: Vec<FunctionInfo>,
+}
+
+impl ConsistentReturn {
+    fn new(
+        reporter: Reporter,
+        code_paths: Arc<CodePathAnalysis>,
+        config: &RuleConfig<ConsistentReturnConfig>,
+    ) -> Self {
+        let consistentreturn_config = config.get_rule_config();
+        Self {
+            reporter,
+            code_paths,
+            treat_undefined_as_unspecified: consistentreturn_config.treat_undefined_as_unspecified,
+            functions: Vec::new(),
+        }
+    }
+
+    fn emit_error(&self, span: Span, msg: &str, message_id: &str) {
+        self.reporter.emit(
+            self.reporter
+                .diagnostic(span, msg)
+                .with_message_id(message_id),
+        );
+    }
+
+    /// Visit a function with `visit`, then report it if it returns a value but can also reach the
+    /// end of its body
+    fn check_function(&mut self, name: String, end: FunctionEnd, visit: impl FnOnce(&mut Self)) {
+        self.functions.push(FunctionInfo {
+            name,
+            return_value: None,
+        });
+        visit(self);
+        let function = self.functions.pop().unwrap();
+
+        if function.return_value != Some(true) || end.is_constructor {
+            return;
+        }
+
+        let falls_through = self
+            .code_paths
+            .code_path(end.span)
+            .is_some_and(|path| self.code_paths.falls_through(path));
+        if falls_through {
+            let msg = format!(
+                "Expected to return a value at the end of {}.",
+                function.name
+            );
+            self.emit_error(end.head, &msg, "missingReturn");
+        }
+    }
+
+    /// Visit a function, method, getter or setter which has a [Function] node
+    fn visit_function_with_name(
+        &mut self,
+        function: &Function,
+        name: String,
+        head_lo: BytePos,
+        is_constructor: bool,
+    ) {
+        let body = match &function.body {
+            Some(body) => body,
+            None => return function.visit_children_with(self),
+        };
+
+        let end = FunctionEnd {
+            span: function.span,
+            head: Span::new(head_lo, body.span.lo, Default::default()),
+            is_constructor,
+        };
+        self.check_function(name, end, |rule| function.visit_children_with(rule));
+    }
+
+    fn visit_arrow_with_name(&mut self, arrow: &ArrowExpr, name: Option<String>) {
+        let body = match &arrow.body {
+            BlockStmtOrExpr::BlockStmt(body) => body,
+            // An expression body has no `return` statements of its own
+            BlockStmtOrExpr::Expr(_) => return arrow.visit_children_with(self),
+        };
+
+        let mut modifiers = Vec::new();
+        if arrow.is_async {
+            modifiers.push("async");
+        }
+        let end = FunctionEnd {
+            span: arrow.span,
+            head: Span::new(arrow.span.lo, body.span.lo, Default::default()),
+            is_constructor: false,
+        };
+        let name = function_name(&modifiers, "arrow function", name);
+        self.check_function(name, end, |rule| arrow.visit_children_with(rule));
+    }
+
+    /// Whether a returned value counts as a value, rather than as `return;`
+    fn has_value(&self, arg: Option<&Expr>) -> bool {
+        match arg {
+            Some(Expr::Ident(ident)) if self.treat_undefined_as_unspecified => {
+                &*ident.sym != "undefined"
+            }
+            Some(Expr::Unary(unary)) if self.treat_undefined_as_unspecified => {
+                unary.op != UnaryOp::Void
+            }
+            Some(_) => true,
+            None => false,
+        }
+    }
+}
+
+impl Debug for ConsistentReturn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConsistentReturn")
+            .field(
+                "treat_undefined_as_unspecified",
+                &self.treat_undefined_as_unspecified,
+            )
+            .finish()
+    }
+}
+
+/// Describe a function the way ESLint does, e.g., "static async method 'foo'"
+fn function_name(modifiers: &[&str], kind: &str, name: Option<String>) -> String {
+    let mut tokens: Vec<String> = modifiers.iter().map(|token| token.to_string()).collect();
+    tokens.push(kind.to_string());
+    if let Some(name) = name {
+        tokens.push(name);
+    }
+    tokens.join(" ")
+}
+
+/// The modifiers of a function, e.g., `async function* () {}` is an "async generator function"
+fn function_modifiers(function: &Function) -> Vec<&'static str> {
+    let mut modifiers = Vec::new();
+    if function.is_async {
+        modifiers.push("async");
+    }
+    if function.is_generator {
+        modifiers.push("generator");
+    }
+    modifiers
+}
+
+/// The name of a property, quoted, if it is known without running the code
+fn prop_name(key: &PropName) -> Option<String> {
+    let name = match key {
+        PropName::Ident(ident) => ident.sym.to_string(),
+        PropName::Str(str) => str.value.to_string(),
+        PropName::Num(num) => num.value.to_string(),
+        PropName::BigInt(bigint) => bigint.value.to_string(),
+        PropName::Computed(computed) => match &*computed.expr {
+            Expr::Lit(Lit::Str(str)) => str.value.to_string(),
+            Expr::Lit(Lit::Num(num)) => num.value.to_string(),
+            _ => return None,
+        },
+    };
+    Some(format!("'{}'", name))
+}
+
+/// Capitalize the description of a function, to start a message with it
+fn upper_case_first(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+impl Visit for ConsistentReturn {
+    noop_visit_type!();
+
+    fn visit_fn_decl(&mut self, fn_decl: &FnDecl) {
+        let function = &fn_decl.function;
+        let name = function_name(
+            &function_modifiers(function),
+            "function",
+            Some(format!("'{}'", fn_decl.ident.sym)),
+        );
+
+        // A function named like `Foo` is a constructor in ES5
+        let is_constructor = fn_decl.ident.sym.starts_with(char::is_uppercase);
+        self.visit_function_with_name(function, name, function.span.lo, is_constructor);
+    }
+
+    fn visit_fn_expr(&mut self, fn_expr: &FnExpr) {
+        let function = &fn_expr.function;
+        let ident = fn_expr.ident.as_ref().map(|ident| &ident.sym);
+        let name = function_name(
+            &function_modifiers(function),
+            "function",
+            ident.map(|ident| format!("'{}'", ident)),
+        );
+
+        let is_constructor = ident.is_some_and(|ident| ident.starts_with(char::is_uppercase));
+        self.visit_function_with_name(function, name, function.span.lo, is_constructor);
+    }
+
+    fn visit_arrow_expr(&mut self, arrow: &ArrowExpr) {
+        self.visit_arrow_with_name(arrow, None);
+    }
+
+    fn visit_class_method(&mut self, method: &ClassMethod) {
+        method.key.visit_with(self);
+
+        let mut modifiers = Vec::new();
+        if method.is_static {
+            modifiers.push("static");
+        }
+        modifiers.extend(function_modifiers(&method.function));
+        let kind = match method.kind {
+            MethodKind::Method => "method",
+            MethodKind::Getter => "getter",
+            MethodKind::Setter => "setter",
+        };
+        let name = function_name(&modifiers, kind, prop_name(&method.key));
+        self.visit_function_with_name(&method.function, name, method.span.lo, false);
+    }
+
+    fn visit_private_method(&mut self, method: &PrivateMethod) {
+        let mut modifiers = Vec::new();
+        if method.is_static {
+            modifiers.push("static");
+        }
+        modifiers.push("private");
+        modifiers.extend(function_modifiers(&method.function));
+        let kind = match method.kind {
+            MethodKind::Method => "method",
+            MethodKind::Getter => "getter",
+            MethodKind::Setter => "setter",
+        };
+        let name = function_name(&modifiers, kind, Some(format!("#{}", method.key.id.sym)));
+        self.visit_function_with_name(&method.function, name, method.span.lo, false);
+    }
+
+    fn visit_class_prop(&mut self, prop: &ClassProp) {
+        prop.key.visit_with(self);
+        prop.decorators.visit_with(self);
+
+        let mut modifiers = Vec::new();
+        if prop.is_static {
+            modifiers.push("static");
+        }
+        match prop.value.as_deref() {
+            Some(Expr::Fn(FnExpr { function, .. })) => {
+                modifiers.extend(function_modifiers(function));
+                let name = function_name(&modifiers, "method", prop_name(&prop.key));
+                self.visit_function_with_name(function, name, prop.span.lo, false);
+            }
+            Some(Expr::Arrow(arrow)) => self.visit_arrow_with_name(arrow, prop_name(&prop.key)),
+            _ => prop.value.visit_with(self),
+        }
+    }
+
+    fn visit_constructor(&mut self, constructor: &Constructor) {
+        let body = match &constructor.body {
+            Some(body) => body,
+            None => return constructor.visit_children_with(self),
+        };
+
+        let end = FunctionEnd {
+            span: constructor.span,
+            head: Span::new(constructor.span.lo, body.span.lo, Default::default()),
+            is_constructor: true,
+        };
+        self.check_function("constructor".to_string(), end, |rule| {
+            constructor.visit_children_with(rule)
+        });
+    }
+
+    fn visit_method_prop(&mut self, method: &MethodProp) {
+        method.key.visit_with(self);
+
+        let name = function_name(
+            &function_modifiers(&method.function),
+            "method",
+            prop_name(&method.key),
+        );
+        self.visit_function_with_name(&method.function, name, method.key.span().lo, false);
+    }
+
+    fn visit_key_value_prop(&mut self, prop: &KeyValueProp) {
+        prop.key.visit_with(self);
+
+        match &*prop.value {
+            Expr::Fn(FnExpr { ident, function }) => {
+                let name = prop_name(&prop.key)
+                    .or_else(|| ident.as_ref().map(|ident| format!("'{}'", ident.sym)));
+                let name = function_name(&function_modifiers(function), "method", name);
+                self.visit_function_with_name(function, name, prop.key.span().lo, false);
+            }
+            Expr::Arrow(arrow) => self.visit_arrow_with_name(arrow, prop_name(&prop.key)),
+            value => value.visit_with(self),
+        }
+    }
+
+    fn visit_getter_prop(&mut self, getter: &GetterProp) {
+        getter.key.visit_with(self);
+
+        let body = match &getter.body {
+            Some(body) => body,
+            None => return,
+        };
+        let end = FunctionEnd {
+            span: getter.span,
+            head: Span::new(getter.span.lo, body.span.lo, Default::default()),
+            is_constructor: false,
+        };
+        let name = function_name(&[], "getter", prop_name(&getter.key));
+        self.check_function(name, end, |rule| body.visit_with(rule));
+    }
+
+    fn visit_setter_prop(&mut self, setter: &SetterProp) {
+        setter.key.visit_with(self);
+
+        let body = match &setter.body {
+            Some(body) => body,
+            None => return setter.param.visit_with(self),
+        };
+        let end = FunctionEnd {
+            span: setter.span,
+            head: Span::new(setter.span.lo, body.span.lo, Default::default()),
+            is_constructor: false,
+        };
+        let name = function_name(&[], "setter", prop_name(&setter.key));
+        self.check_function(name, end, |rule| {
+            setter.param.visit_with(rule);
+            body.visit_with(rule);
+        });
+    }
+
+    fn visit_return_stmt(&mut self, return_stmt: &ReturnStmt) {
+        return_stmt.visit_children_with(self);
+
+        let has_value = self.has_value(return_stmt.arg.as_deref());
+        let function = match self.functions.last_mut() {
+            Some(function) => function,
+            // `return` at the top level, e.g., in CommonJS
+            None => return,
+        };
+
+        match function.return_value {
+            None => function.return_value = Some(has_value),
+            Some(return_value) if return_value != has_value => {
+                let (msg, message_id) = if return_value {
+                    ("expected a return value", "missingReturnValue")
+                } else {
+                    ("expected no return value", "unexpectedReturnValue")
+                };
+                let msg = format!("{} {}.", upper_case_first(&function.name), msg);
+                self.emit_error(return_stmt.span, &msg, message_id);
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::linter::rule_tester::RuleTester;
+
+    #[test]
+    fn consistent_return() {
+        RuleTester::new(json!({ "parserOptions": { "ecmaVersion": 2015 } })).run(
+            "consistent-return",
+            json!({
+                "valid": [
+                    "function foo() { return; }",
+                    "function foo() { if (true) return; }",
+                    "function foo() { if (true) return; else return; }",
+                    "function foo() { if (true) return true; else return false; }",
+                    "f(function() { return; })",
+                    "var x = () => { return {}; };",
+                    "function foo() { if (true) { return; } }",
+                    { "code": "function foo() { if (true) return; else return undefined; }", "options": [{ "treatUndefinedAsUnspecified": true }] },
+                ],
+                "invalid": [
+                    {
+                        "code": "function f(a) { if (a) { return 1; } return; }\nconst g = () => { if (a) return; return b; };\nclass C { m() { if (a) return 1; } constructor() { if (a) return b; } }\nfunction i() { if (a) return; return undefined; }",
+                        "errors": [
+                            { "message": "Function 'f' expected a return value.", "messageId": "missingReturnValue", "line": 1, "column": 38 },
+                            { "message": "Arrow function expected no return value.", "messageId": "unexpectedReturnValue", "line": 2, "column": 34 },
+                            { "message": "Expected to return a value at the end of method 'm'.", "messageId": "missingReturn", "line": 3, "column": 11 },
+                            { "message": "Function 'i' expected no return value.", "line": 4, "column": 31 },
+                        ],
+                    },
+                    {
+                        "code": "function f(a) { if (a) { return 1; } return; }\nconst g = () => { if (a) return; return b; };\nclass C { m() { if (a) return 1; } constructor() { if (a) return b; } }\nfunction i() { if (a) return; return undefined; }",
+                        "options": [{ "treatUndefinedAsUnspecified": true }],
+                        "errors": 3,
+                    },
+                ],
+            }),
+        );
+    }
+}
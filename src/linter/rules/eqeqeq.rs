@@ -1,101 +1,530 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 use serde::{Deserialize, Serialize};
-use swc_common::Span;
+use serde_json::{json, Value};
+use swc_common::{Span, Spanned};
 use swc_ecma_ast::{
     BinExpr,
     BinaryOp::{EqEq, EqEqEq, NotEq, NotEqEq},
+    Expr, Lit, UnaryOp,
 };
 use swc_ecma_lints::rule::Rule;
-use swc_ecma_utils::HANDLER;
+use swc_ecma_parser::token::{BinOpToken, Token};
 use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
 
-use crate::linter::{config::RuleConfig, rule::visitor_rule};
+use crate::linter::{
+    config::{RuleConfig, RuleOptions},
+    diagnostic::Reporter,
+    rule::{visitor_rule, Fixable, RuleMeta, RuleType},
+    rules::LintContext,
+    tokens::Tokens,
+};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct EqeqeqConfig {
     mode: Mode,
+    /// How comparisons with `null` are checked in "always" mode
+    null: NullMode,
+}
+
+impl RuleOptions for EqeqeqConfig {
+    fn from_options(options: &[Value]) -> Result<Self, String> {
+        let mut config = match options[0].as_str() {
+            Some("always") => Self::default(),
+            Some("never") => Self {
+                mode: Mode::Never,
+                ..Default::default()
+            },
+            Some("smart") => Self {
+                mode: Mode::Smart,
+                ..Default::default()
+            },
+            // The option before `null` was added
+            Some("allow-null") => Self {
+                null: NullMode::Ignore,
+                ..Default::default()
+            },
+            _ => {
+                return Err(format!(
+                    "Value {} should be equal to one of the allowed values: \"always\", \"never\", \"smart\", \"allow-null\".",
+                    options[0]
+                ))
+            }
+        };
+
+        let object = match options.get(1) {
+            Some(value) if config.mode == Mode::Always => value
+                .as_object()
+                .ok_or_else(|| format!("Value {} should be object.", value))?,
+            Some(_) => {
+                return Err(format!(
+                    "Value {} should NOT have more than 1 items.",
+                    Value::from(options.to_vec())
+                ))
+            }
+            None => return Ok(config),
+        };
+
+        for (key, value) in object {
+            match key.as_str() {
+                "null" => {
+                    config.null = match value.as_str() {
+                        Some("always") => NullMode::Always,
+                        Some("never") => NullMode::Never,
+                        Some("ignore") => NullMode::Ignore,
+                        _ => return Err(format!(
+                            "Value {} should be equal to one of the allowed values: \"always\", \"never\", \"ignore\".",
+                            value
+                        )),
+                    }
+                }
+                _ => {
+                    return Err(format!(
+                        "Value {} should NOT have additional properties.",
+                        options[1]
+                    ))
+                }
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+pub const META: RuleMeta = RuleMeta {
+    id: "eqeqeq",
+    description: "Require the use of `===` and `!==`",
+    rule_type: RuleType::Suggestion,
+    recommended: false,
+    fixable: Some(Fixable::Code),
+    has_suggestions: false,
+    schema,
+    docs_url: "https://eslint.org/docs/latest/rules/eqeqeq",
+    create,
+    config: |config| &config.eqeqeq,
+    config_mut: |config| &mut config.eqeqeq,
+};
+
+fn schema() -> Value {
+    json!({
+        "anyOf": [
+            {
+                "type": "array",
+                "items": [
+                    { "enum": ["always"] },
+                    {
+                        "type": "object",
+                        "properties": { "null": { "enum": ["always", "never", "ignore"] } },
+                        "additionalProperties": false,
+                    },
+                ],
+                "additionalItems": false,
+            },
+            {
+                "type": "array",
+                "items": [{ "enum": ["never", "smart", "allow-null"] }],
+                "additionalItems": false,
+            },
+        ],
+    })
 }
 
-pub fn eqeqeq(config: &RuleConfig<EqeqeqConfig>) -> Box<dyn Rule> {
-    visitor_rule(Eqeqeq::new(config))
+fn create(context: &LintContext) -> Box<dyn Rule> {
+    eqeqeq(
+        context
+            .diagnostics
+            .reporter(META.id, context.lint_config.eqeqeq.severity()),
+        context.tokens.clone(),
+        &context.lint_config.eqeqeq,
+    )
+}
+
+pub fn eqeqeq(
+    reporter: Reporter,
+    tokens: Arc<Tokens>,
+    config: &RuleConfig<EqeqeqConfig>,
+) -> Box<dyn Rule> {
+    visitor_rule(Eqeqeq::new(reporter, tokens, config))
 }
 
-#[derive(Default)]
 struct Eqeqeq {
+    reporter: Reporter,
+    tokens: Arc<Tokens>,
     mode: Mode,
+    null: NullMode,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, std::cmp::PartialEq)]
+#[serde(rename_all = "lowercase")]
 enum Mode {
     Always,
     Never,
+    /// Like "always", except for comparisons which can't be wrong: `typeof` comparisons,
+    /// comparisons between literals, and comparisons with `null`
+    Smart,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for Mode {
     fn default() -> Self {
         Self::Always
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, std::cmp::PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum NullMode {
+    /// Comparisons with `null` must use `===` and `!==`
+    Always,
+    /// Comparisons with `null` must use `==` and `!=`, e.g., to also match `undefined`
+    Never,
+    /// Comparisons with `null` aren't checked
+    Ignore,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for NullMode {
+    fn default() -> Self {
+        Self::Always
+    }
+}
+
 impl Eqeqeq {
-    fn new(config: &RuleConfig<EqeqeqConfig>) -> Self {
+    fn new(reporter: Reporter, tokens: Arc<Tokens>, config: &RuleConfig<EqeqeqConfig>) -> Self {
         let eqeqeq_config = config.get_rule_config();
         Self {
+            reporter,
+            tokens,
             mode: eqeqeq_config.mode,
+            null: eqeqeq_config.null,
         }
     }
 
-    fn emit_error(&self, span: Span, found: &str, expected: &str) {
+    fn emit_error(&self, bin_expr: &BinExpr, found: &str, expected: &str) {
         let msg = format!("Expected \"{}\" and instead saw \"{}\".", expected, found);
-        HANDLER.with(|handler| {
-            handler.struct_span_err(span, &msg).emit();
-        });
+        let mut diagnostic = self
+            .reporter
+            .diagnostic(bin_expr.span, &msg)
+            .with_message_id("unexpected");
+
+        // Only fix comparisons where both sides have the same type, as they can't change
+        if is_typeof_string_comparison(bin_expr) || are_literals_of_same_type(bin_expr) {
+            if let Some(operator) = self.operator_span(bin_expr) {
+                diagnostic = diagnostic.with_fix(self.reporter.fix(operator, expected));
+            }
+        }
+
+        self.reporter.emit(diagnostic);
     }
-}
 
-impl Debug for Eqeqeq {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Eqeqeq").finish()
+    /// The span of the operator token between the operands of a comparison
+    fn operator_span(&self, bin_expr: &BinExpr) -> Option<Span> {
+        self.tokens
+            .token_after(bin_expr.left.span().hi)
+            .filter(|token| {
+                matches!(
+                    token.token,
+                    Token::BinOp(
+                        BinOpToken::EqEq
+                            | BinOpToken::NotEq
+                            | BinOpToken::EqEqEq
+                            | BinOpToken::NotEqEq
+                    )
+                )
+            })
+            .map(|token| token.span)
     }
-}
 
-impl Visit for Eqeqeq {
-    noop_visit_type!();
+    fn check_always(&self, bin_expr: &BinExpr) {
+        let is_null = is_null_comparison(bin_expr);
 
-    fn visit_bin_expr(&mut self, bin_expr: &BinExpr) {
-        let op = bin_expr.op;
-        let span = bin_expr.span;
-        let mode = self.mode;
-
-        match op {
-            // ==
-            EqEq => {
-                if mode == Mode::Always {
-                    self.emit_error(span, "==", "===");
+        match bin_expr.op {
+            EqEq | NotEq => {
+                if self.mode == Mode::Smart
+                    && (is_typeof_comparison(bin_expr)
+                        || are_literals_of_same_type(bin_expr)
+                        || is_null)
+                {
+                    return;
                 }
-            }
-            // \!=
-            NotEq => {
-                if mode == Mode::Always {
-                    self.emit_error(span, "!=", "===");
+                if is_null && self.null != NullMode::Always {
+                    return;
                 }
-            }
-            // ===
-            EqEqEq => {
-                if mode == Mode::Never {
-                    self.emit_error(span, "===", "==");
+
+                if bin_expr.op == EqEq {
+                    self.emit_error(bin_expr, "==", "===");
+                } else {
+                    self.emit_error(bin_expr, "!=", "!==");
                 }
             }
-            // \!==
-            NotEqEq => {
-                if mode == Mode::Never {
-                    self.emit_error(span, "!==", "!=");
+            EqEqEq | NotEqEq => {
+                if !is_null || self.null != NullMode::Never {
+                    return;
+                }
+
+                if bin_expr.op == EqEqEq {
+                    self.emit_error(bin_expr, "===", "==");
+                } else {
+                    self.emit_error(bin_expr, "!==", "!=");
                 }
             }
             _ => {}
         }
+    }
+}
+
+impl Debug for Eqeqeq {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Eqeqeq")
+            .field("mode", &self.mode)
+            .field("null", &self.null)
+            .finish()
+    }
+}
+
+fn is_typeof(expr: &Expr) -> bool {
+    matches!(expr, Expr::Unary(unary) if unary.op == UnaryOp::TypeOf)
+}
+
+/// Whether either side of a comparison is `typeof`, which is always a string
+fn is_typeof_comparison(bin_expr: &BinExpr) -> bool {
+    is_typeof(&bin_expr.left) || is_typeof(&bin_expr.right)
+}
+
+/// Whether `typeof` is compared to a string, so both sides are always strings
+fn is_typeof_string_comparison(bin_expr: &BinExpr) -> bool {
+    let is_string = |expr: &Expr| match expr {
+        Expr::Lit(Lit::Str(_)) => true,
+        Expr::Tpl(tpl) => tpl.exprs.is_empty(),
+        _ => is_typeof(expr),
+    };
+    (is_typeof(&bin_expr.left) && is_string(&bin_expr.right))
+        || (is_typeof(&bin_expr.right) && is_string(&bin_expr.left))
+}
+
+/// The result of `typeof` for a literal
+fn literal_type(expr: &Expr) -> Option<&'static str> {
+    match expr {
+        Expr::Lit(Lit::Str(_)) => Some("string"),
+        Expr::Lit(Lit::Num(_)) => Some("number"),
+        Expr::Lit(Lit::Bool(_)) => Some("boolean"),
+        Expr::Lit(Lit::BigInt(_)) => Some("bigint"),
+        Expr::Lit(Lit::Null(_) | Lit::Regex(_)) => Some("object"),
+        _ => None,
+    }
+}
+
+fn are_literals_of_same_type(bin_expr: &BinExpr) -> bool {
+    literal_type(&bin_expr.left).is_some_and(|left| literal_type(&bin_expr.right) == Some(left))
+}
+
+fn is_null_comparison(bin_expr: &BinExpr) -> bool {
+    let is_null = |expr: &Expr| matches!(expr, Expr::Lit(Lit::Null(_)));
+    is_null(&bin_expr.left) || is_null(&bin_expr.right)
+}
+
+impl Visit for Eqeqeq {
+    noop_visit_type!();
+
+    fn visit_bin_expr(&mut self, bin_expr: &BinExpr) {
+        match self.mode {
+            Mode::Always | Mode::Smart => self.check_always(bin_expr),
+            Mode::Never => match bin_expr.op {
+                EqEqEq => self.emit_error(bin_expr, "===", "=="),
+                NotEqEq => self.emit_error(bin_expr, "!==", "!="),
+                _ => {}
+            },
+        }
 
         bin_expr.visit_children_with(self);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::EqeqeqConfig;
+    use crate::linter::{config::RuleOptions, rule_tester::RuleTester};
+
+    #[test]
+    fn eqeqeq() {
+        RuleTester::default().run(
+            "eqeqeq",
+            json!({
+                "valid": [
+                    "a === b",
+                    "a !== b",
+                    { "code": "a === b", "options": ["always"] },
+                    { "code": "typeof a == 'number'", "options": ["smart"] },
+                    { "code": "'string' != typeof a", "options": ["smart"] },
+                    { "code": "'hello' != 'world'", "options": ["smart"] },
+                    { "code": "2 == 3", "options": ["smart"] },
+                    { "code": "true == true", "options": ["smart"] },
+                    { "code": "null == a", "options": ["smart"] },
+                    { "code": "a == null", "options": ["smart"] },
+                    { "code": "null == a", "options": ["allow-null"] },
+                    { "code": "a != null", "options": ["allow-null"] },
+                    { "code": "a == null", "options": ["always", { "null": "ignore" }] },
+                    { "code": "a != null", "options": ["always", { "null": "ignore" }] },
+                    { "code": "a === null", "options": ["always", { "null": "ignore" }] },
+                    { "code": "null == null", "options": ["always", { "null": "never" }] },
+                    { "code": "a != null", "options": ["always", { "null": "never" }] },
+                    { "code": "a == b", "options": ["never"] },
+                ],
+                "invalid": [
+                    {
+                        "code": "a == b",
+                        "errors": [{ "message": "Expected \"===\" and instead saw \"==\".", "messageId": "unexpected", "line": 1, "column": 1, "endColumn": 7 }],
+                    },
+                    {
+                        "code": "a != b",
+                        "errors": [{ "message": "Expected \"!==\" and instead saw \"!=\".", "messageId": "unexpected" }],
+                    },
+                    {
+                        "code": "typeof a == 'undefined'",
+                        "output": "typeof a === 'undefined'",
+                        "errors": ["Expected \"===\" and instead saw \"==\"."],
+                    },
+                    {
+                        "code": "'hello' != 'world'",
+                        "output": "'hello' !== 'world'",
+                        "errors": ["Expected \"!==\" and instead saw \"!=\"."],
+                    },
+                    {
+                        "code": "2 == 3",
+                        "output": "2 === 3",
+                        "errors": 1,
+                    },
+                    {
+                        "code": "true != true",
+                        "output": "true !== true",
+                        "errors": 1,
+                    },
+                    { "code": "a == null", "errors": 1 },
+                    { "code": "a == b", "options": ["smart"], "errors": 1 },
+                    { "code": "true == 1", "options": ["smart"], "errors": 1 },
+                    { "code": "a != b", "options": ["allow-null"], "errors": 1 },
+                    { "code": "a == b", "options": ["always", { "null": "ignore" }], "errors": 1 },
+                    {
+                        "code": "a === null",
+                        "output": null,
+                        "options": ["always", { "null": "never" }],
+                        "errors": [{ "message": "Expected \"==\" and instead saw \"===\".", "column": 1 }],
+                    },
+                    {
+                        "code": "null !== a",
+                        "options": ["always", { "null": "never" }],
+                        "errors": ["Expected \"!=\" and instead saw \"!==\"."],
+                    },
+                    {
+                        "code": "a == b && c === d",
+                        "options": ["always", { "null": "always" }],
+                        "errors": [{ "column": 1 }],
+                    },
+                    {
+                        "code": "if (a == b) {\n  c = d != e;\n}",
+                        "errors": [{ "line": 1, "column": 5 }, { "line": 2, "column": 7 }],
+                    },
+                    // Columns count UTF-16 code units, like ESLint, not bytes or display width
+                    {
+                        "code": "var s = \"日本語\"; if (s == 1) {}",
+                        "errors": [{ "line": 1, "column": 20, "endColumn": 26 }],
+                    },
+                    {
+                        "code": "var s = \"😀\"; if (s == 1) {}",
+                        "errors": [{ "line": 1, "column": 19, "endColumn": 25 }],
+                    },
+                    {
+                        "code": "a === b",
+                        "options": ["never"],
+                        "errors": ["Expected \"==\" and instead saw \"===\"."],
+                    },
+                    // Only comparisons where both sides have the same type are fixed
+                    {
+                        "code": "if (typeof a == \"string\" && b != null && 1 == 2 && a == b) {}\nif (c === null) {}\n",
+                        "output": "if (typeof a === \"string\" && b != null && 1 === 2 && a == b) {}\nif (c === null) {}\n",
+                        "errors": [
+                            { "line": 1, "column": 5 },
+                            { "message": "Expected \"!==\" and instead saw \"!=\".", "line": 1, "column": 29 },
+                            { "line": 1, "column": 42 },
+                            { "line": 1, "column": 52 },
+                        ],
+                    },
+                    {
+                        "code": "if (typeof a == \"string\" && b != null && 1 == 2 && a == b) {}\nif (c === null) {}\n",
+                        "options": ["smart"],
+                        "errors": [{ "line": 1, "column": 52 }],
+                    },
+                    {
+                        "code": "if (typeof a == \"string\" && b != null && 1 == 2 && a == b) {}\nif (c === null) {}\n",
+                        "output": "if (typeof a === \"string\" && b != null && 1 === 2 && a == b) {}\nif (c === null) {}\n",
+                        "options": ["always", { "null": "ignore" }],
+                        "errors": [{ "line": 1, "column": 5 }, { "line": 1, "column": 42 }, { "line": 1, "column": 52 }],
+                    },
+                    {
+                        "code": "if (typeof a == \"string\" && b != null && 1 == 2 && a == b) {}\nif (c === null) {}\n",
+                        "output": "if (typeof a === \"string\" && b != null && 1 === 2 && a == b) {}\nif (c === null) {}\n",
+                        "options": ["allow-null"],
+                        "errors": [{ "line": 1, "column": 5 }, { "line": 1, "column": 42 }, { "line": 1, "column": 52 }],
+                    },
+                    {
+                        "code": "if (typeof a == \"string\" && b != null && 1 == 2 && a == b) {}\nif (c === null) {}\n",
+                        "output": "if (typeof a === \"string\" && b != null && 1 === 2 && a == b) {}\nif (c === null) {}\n",
+                        "options": ["always", { "null": "never" }],
+                        "errors": [
+                            { "line": 1, "column": 5 },
+                            { "line": 1, "column": 42 },
+                            { "line": 1, "column": 52 },
+                            { "line": 2, "column": 5 },
+                        ],
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn fixes_typeof_compared_to_strings() {
+        RuleTester::new(json!({ "parserOptions": { "ecmaVersion": 2015 } })).run(
+            "eqeqeq",
+            json!({
+                "valid": [
+                    { "code": "typeof a == b", "options": ["smart"] },
+                ],
+                "invalid": [
+                    {
+                        "code": "typeof a == 'string'",
+                        "output": "typeof a === 'string'",
+                        "errors": [{ "messageId": "unexpected", "line": 1, "column": 1 }],
+                    },
+                    {
+                        "code": "'string' != typeof a",
+                        "output": "'string' !== typeof a",
+                        "errors": [{ "message": "Expected \"!==\" and instead saw \"!=\"." }],
+                    },
+                    {
+                        "code": "typeof a == typeof b",
+                        "output": "typeof a === typeof b",
+                        "errors": 1,
+                    },
+                    {
+                        "code": "typeof a == `string`",
+                        "output": "typeof a === `string`",
+                        "errors": 1,
+                    },
+                    // The other side could be anything, so `===` could change the result
+                    { "code": "typeof a == b", "output": null, "errors": 1 },
+                    { "code": "typeof a == `${b}`", "output": null, "errors": 1 },
+                    { "code": "typeof a == 1", "output": null, "errors": 1 },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn rejects_null_option_outside_always_mode() {
+        let options = [json!("smart"), json!({ "null": "ignore" })];
+        assert!(EqeqeqConfig::from_options(&options).is_err());
+    }
+}
@@ -1,7 +1,14 @@
 #[path = ""]
 pub(crate) mod lints {
+    pub mod consistent_return;
     pub mod eqeqeq;
     pub mod no_eval;
+    pub mod no_fallthrough;
+    pub mod no_implied_eval;
+    pub mod no_new_func;
+    pub mod no_undef;
+    pub mod no_unreachable;
+    pub mod no_unused_vars;
     pub mod quotes;
     pub mod semi;
 }
@@ -10,54 +17,73 @@ use std::{collections::HashMap, sync::Arc};
 
 use lints::*;
 
-use swc_common::SourceMap;
+use swc_common::{comments::Comment, SourceMap};
 use swc_ecma_ast::{EsVersion, Program};
 use swc_ecma_lints::rule::Rule;
 
-use super::config::LintConfig;
+use super::{
+    code_path::CodePathAnalysis,
+    config::{GlobalAccess, LintConfig},
+    diagnostic::DiagnosticCollector,
+    fix::FixType,
+    rule::RuleMeta,
+    scope::ScopeManager,
+    tokens::Tokens,
+};
 
 pub struct LintContext<'a> {
     pub program: &'a Program,
     pub lint_config: &'a LintConfig,
     pub es_version: EsVersion,
     pub source_map: Arc<SourceMap>,
+    /// Where rules report problems for the file being linted
+    pub diagnostics: DiagnosticCollector,
+    /// Global variables available to the file, from builtins, environments and configuration
+    pub globals: HashMap<String, GlobalAccess>,
+    /// Scopes, variables and references of the file, shared by rules
+    pub scope: Arc<ScopeManager>,
+    /// Code paths and their segments, to tell which code is reachable
+    pub code_paths: Arc<CodePathAnalysis>,
+    /// All comments in the file, in source order
+    pub comments: Arc<Vec<Comment>>,
+    /// All tokens in the file, in source order
+    pub tokens: Arc<Tokens>,
 }
 
-fn get_all_rules_raw<'a>(context: &'a LintContext) -> HashMap<&'a str, Box<dyn Rule>> {
-    let LintContext {
-        program: _,
-        lint_config,
-        es_version: _,
-        source_map,
-    } = context;
-
-    let mut rule_map = HashMap::new();
-    rule_map.insert("quotes", quotes::quotes(source_map, &lint_config.quotes));
-    rule_map.insert("semi", semi::semi(source_map, &lint_config.semi));
-    rule_map.insert("eqeqeq", eqeqeq::eqeqeq(&lint_config.eqeqeq));
-    rule_map.insert("noEval", no_eval::noeval(&lint_config.noeval));
-
-    rule_map
+/// Every rule msrlint implements, ordered by id
+pub static RULES: &[RuleMeta] = &[
+    consistent_return::META,
+    eqeqeq::META,
+    no_eval::META,
+    no_fallthrough::META,
+    no_implied_eval::META,
+    no_new_func::META,
+    no_undef::META,
+    no_unreachable::META,
+    no_unused_vars::META,
+    quotes::META,
+    semi::META,
+];
+
+/// The metadata of a rule by its id, or None for rules msrlint doesn't implement
+pub fn get_rule_meta(rule_id: &str) -> Option<&'static RuleMeta> {
+    RULES.iter().find(|meta| meta.id == rule_id)
+}
+
+/// The type of a rule, as in ESLint's rule metadata, used to select fixes with `--fix-type`
+pub fn get_rule_type(rule_id: &str) -> Option<FixType> {
+    get_rule_meta(rule_id).map(|meta| meta.rule_type.into())
 }
 
-pub fn get_all_rules(context: LintContext) -> Vec<Box<dyn Rule>> {
-    let mut rules = get_all_rules_raw(&context);
-
-    let LintContext {
-        program: _,
-        lint_config,
-        es_version: _,
-        source_map: _,
-    } = context;
-
-    if lint_config.rules.contains(&String::from("eslint:all")) {
-        // If assuming eslint:all, start with all rules and disable the rules specified
-        for rule in &lint_config.disabled_rules {
-            rules.remove(rule.as_str());
-        }
-
-        rules.into_values().collect()
-    } else {
-        todo!()
-    }
+/// The rules to run for a file, with their ids, ordered by id
+pub fn get_all_rules(context: &LintContext) -> Vec<(&'static str, Box<dyn Rule>)> {
+    let LintContext { lint_config, .. } = context;
+
+    // With eslint:all, start with all rules and disable the rules specified, otherwise only run
+    // the rules which are turned on
+    RULES
+        .iter()
+        .filter(|meta| lint_config.is_rule_enabled(meta.id))
+        .map(|meta| (meta.id, (meta.create)(context)))
+        .collect()
 }
@@ -1,52 +1,271 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 use swc_common::Span;
-use swc_ecma_ast::Ident;
+use swc_ecma_ast::{
+    CallExpr, Callee, Class, Expr, Function, GetterProp, Ident, Lit, MemberExpr, Module, Script,
+    SetterProp, Stmt,
+};
 use swc_ecma_lints::rule::Rule;
-use swc_ecma_utils::HANDLER;
 use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
 
-use crate::linter::{config::RuleConfig, rule::visitor_rule};
+use crate::linter::{
+    config::{RuleConfig, RuleOptions},
+    diagnostic::Reporter,
+    rule::{visitor_rule, RuleMeta, RuleType},
+    rules::LintContext,
+    scope::{static_property_name, ScopeManager},
+};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
-pub struct NoEvalConfig {}
+#[serde(rename_all = "camelCase")]
+pub struct NoEvalConfig {
+    /// Only report direct calls, e.g., allow `(0, eval)("a")` and `window.eval("a")`
+    allow_indirect: bool,
+}
+
+impl RuleOptions for NoEvalConfig {
+    fn from_options(options: &[Value]) -> Result<Self, String> {
+        let object = options[0]
+            .as_object()
+            .ok_or_else(|| format!("Value {} should be object.", options[0]))?;
+
+        let mut config = Self::default();
+        for (key, value) in object {
+            match (key.as_str(), value) {
+                ("allowIndirect", Value::Bool(value)) => config.allow_indirect = *value,
+                ("allowIndirect", value) => {
+                    return Err(format!("Value {} should be boolean.", value))
+                }
+                _ => {
+                    return Err(format!(
+                        "Value {} should NOT have additional properties.",
+                        options[0]
+                    ))
+                }
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+pub const META: RuleMeta = RuleMeta {
+    id: "no-eval",
+    description: "Disallow the use of `eval()`",
+    rule_type: RuleType::Suggestion,
+    recommended: false,
+    fixable: None,
+    has_suggestions: false,
+    schema,
+    docs_url: "https://eslint.org/docs/latest/rules/no-eval",
+    create,
+    config: |config| &config.noeval,
+    config_mut: |config| &mut config.noeval,
+};
+
+fn schema() -> Value {
+    json!([{
+        "type": "object",
+        "properties": { "allowIndirect": { "type": "boolean", "default": false } },
+        "additionalProperties": false,
+    }])
+}
 
-pub fn noeval(config: &RuleConfig<NoEvalConfig>) -> Box<dyn Rule> {
-    visitor_rule(NoEval::new(config))
+fn create(context: &LintContext) -> Box<dyn Rule> {
+    noeval(
+        context
+            .diagnostics
+            .reporter(META.id, context.lint_config.noeval.severity()),
+        context.scope.clone(),
+        &context.lint_config.noeval,
+    )
 }
 
-#[derive(Default)]
-struct NoEval {}
+pub fn noeval(
+    reporter: Reporter,
+    scope: Arc<ScopeManager>,
+    config: &RuleConfig<NoEvalConfig>,
+) -> Box<dyn Rule> {
+    visitor_rule(NoEval::new(reporter, scope, config))
+}
+
+struct NoEval {
+    reporter: Reporter,
+    scope: Arc<ScopeManager>,
+    allow_indirect: bool,
+    /// Whether `this` is the global object, i.e., at the top level of a sloppy mode script
+    global_this: bool,
+}
 
 impl NoEval {
-    fn new(config: &RuleConfig<NoEvalConfig>) -> Self {
-        let _noeval_config = config.get_rule_config();
-        Self {}
+    fn new(
+        reporter: Reporter,
+        scope: Arc<ScopeManager>,
+        config: &RuleConfig<NoEvalConfig>,
+    ) -> Self {
+        let noeval_config = config.get_rule_config();
+        Self {
+            reporter,
+            scope,
+            allow_indirect: noeval_config.allow_indirect,
+            global_this: false,
+        }
     }
 
     fn emit_error(&self, span: Span) {
         let msg = "The use of the eval function is forbidden.";
-        HANDLER.with(|handler| {
-            handler.struct_span_err(span, msg).emit();
-        });
+        self.reporter.emit(
+            self.reporter
+                .diagnostic(span, msg)
+                .with_message_id("unexpected"),
+        );
     }
 }
 
 impl Debug for NoEval {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("NoEval").finish()
+        f.debug_struct("NoEval")
+            .field("allow_indirect", &self.allow_indirect)
+            .finish()
     }
 }
 
+/// Whether a script starts with a "use strict" directive
+fn is_strict(script: &Script) -> bool {
+    script
+        .body
+        .iter()
+        .map_while(|stmt| match stmt {
+            Stmt::Expr(expr) => match &*expr.expr {
+                Expr::Lit(Lit::Str(directive)) => Some(directive),
+                _ => None,
+            },
+            _ => None,
+        })
+        .any(|directive| &*directive.value == "use strict")
+}
+
 impl Visit for NoEval {
     noop_visit_type!();
 
+    fn visit_module(&mut self, module: &Module) {
+        self.global_this = false;
+        module.visit_children_with(self);
+    }
+
+    fn visit_script(&mut self, script: &Script) {
+        self.global_this = !is_strict(script);
+        script.visit_children_with(self);
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        let global_this = std::mem::replace(&mut self.global_this, false);
+        function.visit_children_with(self);
+        self.global_this = global_this;
+    }
+
+    fn visit_getter_prop(&mut self, getter: &GetterProp) {
+        let global_this = std::mem::replace(&mut self.global_this, false);
+        getter.visit_children_with(self);
+        self.global_this = global_this;
+    }
+
+    fn visit_setter_prop(&mut self, setter: &SetterProp) {
+        let global_this = std::mem::replace(&mut self.global_this, false);
+        setter.visit_children_with(self);
+        self.global_this = global_this;
+    }
+
+    fn visit_class(&mut self, class: &Class) {
+        let global_this = std::mem::replace(&mut self.global_this, false);
+        class.visit_children_with(self);
+        self.global_this = global_this;
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if self.allow_indirect {
+            // Only a direct call evaluates code in the caller's scope
+            if let Callee::Expr(callee) = &call.callee {
+                let mut callee: &Expr = callee;
+                while let Expr::Paren(paren) = callee {
+                    callee = &paren.expr;
+                }
+                if let Expr::Ident(ident) = callee {
+                    if &*ident.sym == "eval" && self.scope.is_global_reference(ident) {
+                        self.emit_error(ident.span);
+                    }
+                }
+            }
+        }
+
+        call.visit_children_with(self);
+    }
+
+    fn visit_member_expr(&mut self, member: &MemberExpr) {
+        if !self.allow_indirect {
+            let name = match &*member.obj {
+                Expr::This(_) if self.global_this => static_property_name(&member.prop),
+                _ => self.scope.global_member_name(member),
+            };
+            if let Some((name, span)) = name {
+                if &*name == "eval" {
+                    self.emit_error(span);
+                }
+            }
+        }
+
+        member.visit_children_with(self);
+    }
+
     fn visit_ident(&mut self, ident: &Ident) {
-        if ident.sym.to_string().as_str() == "eval" {
+        // Only the global eval, not e.g., a parameter named eval or a property
+        if !self.allow_indirect && &*ident.sym == "eval" && self.scope.is_global_reference(ident) {
             self.emit_error(ident.span);
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::linter::rule_tester::RuleTester;
 
-        ident.visit_children_with(self);
+    #[test]
+    fn no_eval() {
+        let tester = RuleTester::new(json!({
+            "env": { "browser": true },
+            "globals": { "a": "readonly" },
+        }));
+        tester.run(
+            "no-eval",
+            json!({
+                "valid": [
+                    "Eval(a)",
+                    "foo.eval(a)",
+                    "function f(eval) { eval(a); }",
+                    { "code": "(0, eval)(a); window.eval(a); globalThis['eval'](a);", "options": [{ "allowIndirect": true }] },
+                ],
+                "invalid": [
+                    {
+                        "code": "(0, eval)(a); window.eval(a); globalThis['eval'](a); this.eval(a);\nvar f = function () { this.eval(a); }; eval(a);",
+                        "errors": [
+                            { "message": "The use of the eval function is forbidden.", "messageId": "unexpected", "line": 1, "column": 5 },
+                            { "line": 1, "column": 22 },
+                            { "line": 1, "column": 42 },
+                            { "line": 1, "column": 59 },
+                            { "line": 2, "column": 40 },
+                        ],
+                    },
+                    {
+                        "code": "(0, eval)(a); window.eval(a); globalThis['eval'](a); this.eval(a);\nvar f = function () { this.eval(a); }; eval(a);",
+                        "options": [{ "allowIndirect": true }],
+                        "errors": [{ "line": 2, "column": 40 }],
+                    },
+                ],
+            }),
+        );
     }
 }
@@ -0,0 +1,267 @@
+use std::{fmt::Debug, sync::Arc};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use swc_common::{comments::Comment, SourceMap, Span, Spanned};
+use swc_ecma_ast::{Stmt, SwitchCase, SwitchStmt};
+use swc_ecma_lints::rule::Rule;
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+
+use crate::linter::{
+    code_path::CodePathAnalysis,
+    config::{RuleConfig, RuleOptions},
+    diagnostic::Reporter,
+    rule::{visitor_rule, RuleMeta, RuleType},
+    rules::LintContext,
+};
+
+/// Comments which mark falling through as intended, e.g., `// falls through`
+const DEFAULT_COMMENT_PATTERN: &str = r"(?i)falls?\s?through";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NoFallthroughConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment_pattern: Option<String>,
+    allow_empty_case: bool,
+}
+
+impl RuleOptions for NoFallthroughConfig {
+    fn from_options(options: &[Value]) -> Result<Self, String> {
+        let object = options[0]
+            .as_object()
+            .ok_or_else(|| format!("Value {} should be object.", options[0]))?;
+
+        let mut config = Self::default();
+        for (key, value) in object {
+            match (key.as_str(), value) {
+                ("commentPattern", Value::String(pattern)) => {
+                    Regex::new(pattern).map_err(|err| {
+                        format!("Invalid regular expression /{}/: {}", pattern, err)
+                    })?;
+                    config.comment_pattern = Some(pattern.clone());
+                }
+                ("commentPattern", value) => {
+                    return Err(format!("Value {} should be string.", value))
+                }
+                ("allowEmptyCase", Value::Bool(value)) => config.allow_empty_case = *value,
+                ("allowEmptyCase", value) => {
+                    return Err(format!("Value {} should be boolean.", value))
+                }
+                _ => {
+                    return Err(format!(
+                        "Value {} should NOT have additional properties.",
+                        options[0]
+                    ))
+                }
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+pub const META: RuleMeta = RuleMeta {
+    id: "no-fallthrough",
+    description: "Disallow fallthrough of `case` statements",
+    rule_type: RuleType::Problem,
+    recommended: true,
+    fixable: None,
+    has_suggestions: false,
+    schema,
+    docs_url: "https://eslint.org/docs/latest/rules/no-fallthrough",
+    create,
+    config: |config| &config.nofallthrough,
+    config_mut: |config| &mut config.nofallthrough,
+};
+
+fn schema() -> Value {
+    json!([{
+        "type": "object",
+        "properties": {
+            "commentPattern": { "type": "string", "default": "" },
+            "allowEmptyCase": { "type": "boolean", "default": false },
+        },
+        "additionalProperties": false,
+    }])
+}
+
+fn create(context: &LintContext) -> Box<dyn Rule> {
+    nofallthrough(
+        context
+            .diagnostics
+            .reporter(META.id, context.lint_config.nofallthrough.severity()),
+        &context.source_map,
+        context.comments.clone(),
+        context.code_paths.clone(),
+        &context.lint_config.nofallthrough,
+    )
+}
+
+pub fn nofallthrough(
+    reporter: Reporter,
+    source_map: &Arc<SourceMap>,
+    comments: Arc<Vec<Comment>>,
+    code_paths: Arc<CodePathAnalysis>,
+    config: &RuleConfig<NoFallthroughConfig>,
+) -> Box<dyn Rule> {
+    visitor_rule(NoFallthrough::new(
+        reporter,
+        source_map.clone(),
+        comments,
+        code_paths,
+        config,
+    ))
+}
+
+struct NoFallthrough {
+    reporter: Reporter,
+    source_map: Arc<SourceMap>,
+    comments: Arc<Vec<Comment>>,
+    code_paths: Arc<CodePathAnalysis>,
+    comment_pattern: Regex,
+    allow_empty_case: bool,
+}
+
+impl NoFallthrough {
+    fn new(
+        reporter: Reporter,
+        source_map: Arc<SourceMap>,
+        comments: Arc<Vec<Comment>>,
+        code_paths: Arc<CodePathAnalysis>,
+        config: &RuleConfig<NoFallthroughConfig>,
+    ) -> Self {
+        let nofallthrough_config = config.get_rule_config();
+        let comment_pattern = nofallthrough_config
+            .comment_pattern
+            .as_deref()
+            .unwrap_or(DEFAULT_COMMENT_PATTERN);
+
+        Self {
+            reporter,
+            source_map,
+            comments,
+            code_paths,
+            comment_pattern: Regex::new(comment_pattern).unwrap(),
+            allow_empty_case: nofallthrough_config.allow_empty_case,
+        }
+    }
+
+    fn emit_error(&self, next: &SwitchCase) {
+        let (msg, message_id) = match next.test {
+            Some(_) => ("Expected a 'break' statement before 'case'.", "case"),
+            None => ("Expected a 'break' statement before 'default'.", "default"),
+        };
+        self.reporter.emit(
+            self.reporter
+                .diagnostic(next.span, msg)
+                .with_message_id(message_id),
+        );
+    }
+
+    fn is_fallthrough_comment(&self, comment: &Comment) -> bool {
+        let text = comment.text.trim();
+        !text.starts_with("eslint") && self.comment_pattern.is_match(text)
+    }
+
+    /// Whether the last comment in a range, if any, marks falling through as intended
+    fn has_fallthrough_comment(&self, range: Span) -> bool {
+        self.comments
+            .iter()
+            .rfind(|comment| range.contains(comment.span))
+            .is_some_and(|comment| self.is_fallthrough_comment(comment))
+    }
+
+    /// Whether a case which falls through is marked as intended by a comment, either at the end
+    /// of a block which is its only statement, or right before the next case
+    fn is_marked(&self, case: &SwitchCase, next: &SwitchCase) -> bool {
+        if let [Stmt::Block(block)] = &case.cons[..] {
+            let end = block
+                .stmts
+                .last()
+                .map_or(block.span.lo, |stmt| stmt.span().hi);
+            if self.has_fallthrough_comment(Span::new(end, block.span.hi, Default::default())) {
+                return true;
+            }
+        }
+
+        self.has_fallthrough_comment(Span::new(case.span.hi, next.span.lo, Default::default()))
+    }
+
+    /// Whether an empty case is written apart from the next case, e.g., with a blank line
+    fn is_separated(&self, case: &SwitchCase, next: &SwitchCase) -> bool {
+        let end = self.source_map.lookup_char_pos(case.span.hi).line;
+        let next = self.source_map.lookup_char_pos(next.span.lo).line;
+        next > end + 1
+    }
+}
+
+impl Debug for NoFallthrough {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NoFallthrough")
+            .field("comment_pattern", &self.comment_pattern)
+            .field("allow_empty_case", &self.allow_empty_case)
+            .finish()
+    }
+}
+
+impl Visit for NoFallthrough {
+    noop_visit_type!();
+
+    fn visit_switch_stmt(&mut self, switch: &SwitchStmt) {
+        for pair in switch.cases.windows(2) {
+            let (case, next) = (&pair[0], &pair[1]);
+            if !self.code_paths.completes_normally(case.span) {
+                continue;
+            }
+
+            let is_empty = case.cons.is_empty();
+            if (!is_empty || (!self.allow_empty_case && self.is_separated(case, next)))
+                && !self.is_marked(case, next)
+            {
+                self.emit_error(next);
+            }
+        }
+
+        switch.visit_children_with(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::linter::rule_tester::RuleTester;
+
+    #[test]
+    fn no_fallthrough() {
+        RuleTester::default().run(
+            "no-fallthrough",
+            json!({
+                "valid": [
+                    "switch (foo) { case 0: a(); break; case 1: b() }",
+                    "switch (foo) { case 0: case 1: a(); break; }",
+                    "switch (foo) { case 0: a();\n /* falls through */ case 1: b(); }",
+                    "function foo() { switch (foo) { case 0: a(); return; case 1: b(); } }",
+                    "switch (foo) { case 0: a(); throw 'foo'; case 1: b(); }",
+                    { "code": "switch (foo) { case 0: a();\n /* break omitted */ default: b(); }", "options": [{ "commentPattern": "break omitted" }] },
+                ],
+                "invalid": [
+                    {
+                        "code": "switch (a) {\ncase 1: b();\ncase 2: b(); // falls through\ncase 3:\ncase 4: b(); break;\ncase 5:\n\ndefault: { b(); /* fallthrough */ }\n}",
+                        "errors": [
+                            { "message": "Expected a 'break' statement before 'case'.", "messageId": "case", "line": 3, "column": 1 },
+                            { "message": "Expected a 'break' statement before 'default'.", "messageId": "default", "line": 8, "column": 1 },
+                        ],
+                    },
+                    {
+                        "code": "switch (a) {\ncase 1: b();\ncase 2: b(); // falls through\ncase 3:\ncase 4: b(); break;\ncase 5:\n\ndefault: { b(); /* fallthrough */ }\n}",
+                        "options": [{ "commentPattern": "break omitted", "allowEmptyCase": true }],
+                        "errors": [{ "line": 3 }, { "line": 4 }],
+                    },
+                ],
+            }),
+        );
+    }
+}
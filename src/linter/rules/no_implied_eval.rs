@@ -0,0 +1,169 @@
+use std::{fmt::Debug, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use swc_common::Span;
+use swc_ecma_ast::{BinExpr, BinaryOp, CallExpr, Callee, Expr, Lit};
+use swc_ecma_lints::rule::Rule;
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+
+use crate::linter::{
+    config::{RuleConfig, RuleOptions},
+    diagnostic::Reporter,
+    rule::{visitor_rule, RuleMeta, RuleType},
+    rules::LintContext,
+    scope::ScopeManager,
+};
+
+/// Globals which evaluate their first argument if it is a string
+const EVALUATING_FUNCTIONS: [&str; 3] = ["execScript", "setInterval", "setTimeout"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct NoImpliedEvalConfig {}
+
+impl RuleOptions for NoImpliedEvalConfig {
+    fn from_options(_options: &[Value]) -> Result<Self, String> {
+        Ok(Self {})
+    }
+}
+
+pub const META: RuleMeta = RuleMeta {
+    id: "no-implied-eval",
+    description: "Disallow the use of `eval()`-like methods",
+    rule_type: RuleType::Suggestion,
+    recommended: false,
+    fixable: None,
+    has_suggestions: false,
+    schema,
+    docs_url: "https://eslint.org/docs/latest/rules/no-implied-eval",
+    create,
+    config: |config| &config.noimpliedeval,
+    config_mut: |config| &mut config.noimpliedeval,
+};
+
+fn schema() -> Value {
+    json!([])
+}
+
+fn create(context: &LintContext) -> Box<dyn Rule> {
+    noimpliedeval(
+        context
+            .diagnostics
+            .reporter(META.id, context.lint_config.noimpliedeval.severity()),
+        context.scope.clone(),
+        &context.lint_config.noimpliedeval,
+    )
+}
+
+pub fn noimpliedeval(
+    reporter: Reporter,
+    scope: Arc<ScopeManager>,
+    config: &RuleConfig<NoImpliedEvalConfig>,
+) -> Box<dyn Rule> {
+    visitor_rule(NoImpliedEval::new(reporter, scope, config))
+}
+
+struct NoImpliedEval {
+    reporter: Reporter,
+    scope: Arc<ScopeManager>,
+}
+
+impl NoImpliedEval {
+    fn new(
+        reporter: Reporter,
+        scope: Arc<ScopeManager>,
+        config: &RuleConfig<NoImpliedEvalConfig>,
+    ) -> Self {
+        let _noimpliedeval_config = config.get_rule_config();
+        Self { reporter, scope }
+    }
+
+    fn emit_error(&self, span: Span) {
+        let msg = "Implied eval. Consider passing a function instead of a string.";
+        self.reporter.emit(
+            self.reporter
+                .diagnostic(span, msg)
+                .with_message_id("impliedEval"),
+        );
+    }
+}
+
+impl Debug for NoImpliedEval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NoImpliedEval").finish()
+    }
+}
+
+/// Whether an expression is certainly a string, e.g., `"a"`, `` `a${b}` `` or `"a" + b`
+fn is_evaluated_string(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(Lit::Str(_)) | Expr::Tpl(_) => true,
+        Expr::Bin(BinExpr {
+            op: BinaryOp::Add,
+            left,
+            right,
+            ..
+        }) => is_evaluated_string(left) || is_evaluated_string(right),
+        Expr::Paren(paren) => is_evaluated_string(&paren.expr),
+        _ => false,
+    }
+}
+
+impl Visit for NoImpliedEval {
+    noop_visit_type!();
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Callee::Expr(callee) = &call.callee {
+            let evaluates = self
+                .scope
+                .global_name(callee)
+                .is_some_and(|(name, _)| EVALUATING_FUNCTIONS.contains(&&*name));
+
+            match call.args.first() {
+                Some(arg)
+                    if evaluates && arg.spread.is_none() && is_evaluated_string(&arg.expr) =>
+                {
+                    self.emit_error(call.span)
+                }
+                _ => {}
+            }
+        }
+
+        call.visit_children_with(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::linter::rule_tester::RuleTester;
+
+    #[test]
+    fn no_implied_eval() {
+        let tester = RuleTester::new(json!({
+            "parserOptions": { "ecmaVersion": 2015 },
+            "env": { "browser": true },
+            "globals": { "a": "readonly" },
+        }));
+        tester.run(
+            "no-implied-eval",
+            json!({
+                "valid": [
+                    "setTimeout(function () {}, 1);",
+                    "setTimeout(a, 1);",
+                    "foo.setTimeout('a()', 1);",
+                ],
+                "invalid": [
+                    {
+                        "code": "setTimeout('a()', 1); window.setInterval(`${a}()`, 1); setTimeout(() => {}, 1);\nnew Function('a', 'return a'); Function.call(null, 'return 1'); new window.Function();\nfunction g(setTimeout, Function) { setTimeout('a'); new Function(); }",
+                        "errors": [
+                            { "message": "Implied eval. Consider passing a function instead of a string.", "messageId": "impliedEval", "line": 1, "column": 1 },
+                            { "message": "Implied eval. Consider passing a function instead of a string.", "line": 1, "column": 23 },
+                        ],
+                    },
+                ],
+            }),
+        );
+    }
+}
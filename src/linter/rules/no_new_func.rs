@@ -0,0 +1,169 @@
+use std::{fmt::Debug, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use swc_common::Span;
+use swc_ecma_ast::{CallExpr, Callee, Expr, NewExpr};
+use swc_ecma_lints::rule::Rule;
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+
+use crate::linter::{
+    config::{RuleConfig, RuleOptions},
+    diagnostic::Reporter,
+    rule::{visitor_rule, RuleMeta, RuleType},
+    rules::LintContext,
+    scope::{static_property_name, ScopeManager},
+};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct NoNewFuncConfig {}
+
+impl RuleOptions for NoNewFuncConfig {
+    fn from_options(_options: &[Value]) -> Result<Self, String> {
+        Ok(Self {})
+    }
+}
+
+pub const META: RuleMeta = RuleMeta {
+    id: "no-new-func",
+    description: "Disallow `new` operators with the `Function` object",
+    rule_type: RuleType::Suggestion,
+    recommended: false,
+    fixable: None,
+    has_suggestions: false,
+    schema,
+    docs_url: "https://eslint.org/docs/latest/rules/no-new-func",
+    create,
+    config: |config| &config.nonewfunc,
+    config_mut: |config| &mut config.nonewfunc,
+};
+
+fn schema() -> Value {
+    json!([])
+}
+
+fn create(context: &LintContext) -> Box<dyn Rule> {
+    nonewfunc(
+        context
+            .diagnostics
+            .reporter(META.id, context.lint_config.nonewfunc.severity()),
+        context.scope.clone(),
+        &context.lint_config.nonewfunc,
+    )
+}
+
+pub fn nonewfunc(
+    reporter: Reporter,
+    scope: Arc<ScopeManager>,
+    config: &RuleConfig<NoNewFuncConfig>,
+) -> Box<dyn Rule> {
+    visitor_rule(NoNewFunc::new(reporter, scope, config))
+}
+
+struct NoNewFunc {
+    reporter: Reporter,
+    scope: Arc<ScopeManager>,
+}
+
+impl NoNewFunc {
+    fn new(
+        reporter: Reporter,
+        scope: Arc<ScopeManager>,
+        config: &RuleConfig<NoNewFuncConfig>,
+    ) -> Self {
+        let _nonewfunc_config = config.get_rule_config();
+        Self { reporter, scope }
+    }
+
+    fn emit_error(&self, span: Span) {
+        let msg = "The Function constructor is eval.";
+        self.reporter.emit(
+            self.reporter
+                .diagnostic(span, msg)
+                .with_message_id("noFunctionConstructor"),
+        );
+    }
+
+    /// Whether an expression is the global `Function` constructor
+    fn is_function_constructor(&self, expr: &Expr) -> bool {
+        self.scope
+            .global_name(expr)
+            .is_some_and(|(name, _)| &*name == "Function")
+    }
+
+    /// Whether a callee creates a function from strings, e.g., `Function` or `Function.call`
+    fn creates_function(&self, callee: &Expr) -> bool {
+        match callee {
+            Expr::Member(member) if !self.is_function_constructor(callee) => {
+                let method = static_property_name(&member.prop);
+                self.is_function_constructor(&member.obj)
+                    && method.is_some_and(|(name, _)| matches!(&*name, "apply" | "bind" | "call"))
+            }
+            callee => self.is_function_constructor(callee),
+        }
+    }
+}
+
+impl Debug for NoNewFunc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NoNewFunc").finish()
+    }
+}
+
+impl Visit for NoNewFunc {
+    noop_visit_type!();
+
+    fn visit_new_expr(&mut self, new: &NewExpr) {
+        if self.is_function_constructor(&new.callee) {
+            self.emit_error(new.span);
+        }
+
+        new.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Callee::Expr(callee) = &call.callee {
+            if self.creates_function(callee) {
+                self.emit_error(call.span);
+            }
+        }
+
+        call.visit_children_with(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::linter::rule_tester::RuleTester;
+
+    #[test]
+    fn no_new_func() {
+        let tester = RuleTester::new(json!({
+            "parserOptions": { "ecmaVersion": 2015 },
+            "env": { "browser": true },
+            "globals": { "a": "readonly" },
+        }));
+        tester.run(
+            "no-new-func",
+            json!({
+                "valid": [
+                    "var a = new _function('b', 'c', 'return b+c');",
+                    "var a = _function('b', 'c', 'return b+c');",
+                    "function f(Function) { new Function(); }",
+                ],
+                "invalid": [
+                    {
+                        "code": "setTimeout('a()', 1); window.setInterval(`${a}()`, 1); setTimeout(() => {}, 1);\nnew Function('a', 'return a'); Function.call(null, 'return 1'); new window.Function();\nfunction g(setTimeout, Function) { setTimeout('a'); new Function(); }",
+                        "errors": [
+                            { "message": "The Function constructor is eval.", "messageId": "noFunctionConstructor", "line": 2, "column": 1 },
+                            { "line": 2, "column": 32 },
+                            { "line": 2, "column": 65 },
+                        ],
+                    },
+                ],
+            }),
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use std::{fmt::Debug, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use swc_common::Span;
+use swc_ecma_ast::{Expr, Ident, UnaryExpr, UnaryOp};
+use swc_ecma_lints::rule::Rule;
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+
+use crate::linter::{
+    config::{RuleConfig, RuleOptions},
+    diagnostic::Reporter,
+    rule::{visitor_rule, RuleMeta, RuleType},
+    rules::LintContext,
+    scope::ScopeManager,
+};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct NoUndefConfig {
+    /// Also report identifiers checked with `typeof`, e.g., `typeof a === "undefined"`
+    r#typeof: bool,
+}
+
+impl RuleOptions for NoUndefConfig {
+    fn from_options(options: &[Value]) -> Result<Self, String> {
+        let object = options[0]
+            .as_object()
+            .ok_or_else(|| format!("Value {} should be object.", options[0]))?;
+
+        let mut config = Self::default();
+        for (key, value) in object {
+            match (key.as_str(), value) {
+                ("typeof", Value::Bool(value)) => config.r#typeof = *value,
+                ("typeof", value) => return Err(format!("Value {} should be boolean.", value)),
+                _ => {
+                    return Err(format!(
+                        "Value {} should NOT have additional properties.",
+                        options[0]
+                    ))
+                }
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+pub const META: RuleMeta = RuleMeta {
+    id: "no-undef",
+    description:
+        "Disallow the use of undeclared variables unless mentioned in `/*global */` comments",
+    rule_type: RuleType::Problem,
+    recommended: true,
+    fixable: None,
+    has_suggestions: false,
+    schema,
+    docs_url: "https://eslint.org/docs/latest/rules/no-undef",
+    create,
+    config: |config| &config.noundef,
+    config_mut: |config| &mut config.noundef,
+};
+
+fn schema() -> Value {
+    json!([{
+        "type": "object",
+        "properties": { "typeof": { "type": "boolean", "default": false } },
+        "additionalProperties": false,
+    }])
+}
+
+fn create(context: &LintContext) -> Box<dyn Rule> {
+    noundef(
+        context
+            .diagnostics
+            .reporter(META.id, context.lint_config.noundef.severity()),
+        context.scope.clone(),
+        &context.lint_config.noundef,
+    )
+}
+
+pub fn noundef(
+    reporter: Reporter,
+    scope: Arc<ScopeManager>,
+    config: &RuleConfig<NoUndefConfig>,
+) -> Box<dyn Rule> {
+    visitor_rule(NoUndef::new(reporter, scope, config))
+}
+
+struct NoUndef {
+    reporter: Reporter,
+    scope: Arc<ScopeManager>,
+    check_typeof: bool,
+}
+
+impl NoUndef {
+    fn new(
+        reporter: Reporter,
+        scope: Arc<ScopeManager>,
+        config: &RuleConfig<NoUndefConfig>,
+    ) -> Self {
+        let noundef_config = config.get_rule_config();
+        Self {
+            reporter,
+            scope,
+            check_typeof: noundef_config.r#typeof,
+        }
+    }
+
+    fn emit_error(&self, span: Span, name: &str) {
+        let msg = format!("'{}' is not defined.", name);
+        self.reporter.emit(
+            self.reporter
+                .diagnostic(span, &msg)
+                .with_message_id("undef"),
+        );
+    }
+}
+
+impl Debug for NoUndef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NoUndef")
+            .field("check_typeof", &self.check_typeof)
+            .finish()
+    }
+}
+
+/// The identifier an expression is, ignoring parentheses
+fn unwrap_ident(expr: &Expr) -> Option<&Ident> {
+    match expr {
+        Expr::Ident(ident) => Some(ident),
+        Expr::Paren(paren) => unwrap_ident(&paren.expr),
+        _ => None,
+    }
+}
+
+impl Visit for NoUndef {
+    noop_visit_type!();
+
+    fn visit_unary_expr(&mut self, unary: &UnaryExpr) {
+        // `typeof a` is the usual way to check whether a global exists, so is allowed by default
+        if unary.op == UnaryOp::TypeOf && !self.check_typeof && unwrap_ident(&unary.arg).is_some() {
+            return;
+        }
+
+        unary.visit_children_with(self);
+    }
+
+    fn visit_ident(&mut self, ident: &Ident) {
+        if let Some(reference) = self.scope.reference(ident) {
+            if reference.resolved.is_none() {
+                self.emit_error(ident.span, &ident.sym);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::linter::rule_tester::RuleTester;
+
+    #[test]
+    fn no_undef() {
+        RuleTester::default().run(
+            "no-undef",
+            json!({
+                "valid": [
+                    "var a = 1, b = 2; a;",
+                    "/*global b*/ function f() { b; }",
+                    "/*global b:false*/ function f() { b; }",
+                    "function a(){}  a();",
+                    "function f(b) { b; }",
+                    "var a; a = 1; a++;",
+                    "var a; function f() { a = 1; }",
+                    "/*global b:true*/ b++;",
+                    "/*eslint-env browser*/ window;",
+                    "Object; isNaN(); Math.max(1);",
+                    "toString()",
+                    "hasOwnProperty()",
+                    "typeof a",
+                    "typeof (a)",
+                    "var b = typeof a",
+                    "typeof a === 'undefined'",
+                    "if (typeof a === 'undefined') {}",
+                    "function foo() { var [a, b=4] = [1, 2]; return {a, b}; }",
+                    { "code": "window;", "env": { "browser": true } },
+                    { "code": "require('a');", "env": { "node": true } },
+                    { "code": "a; b;", "globals": { "a": "readonly", "b": "writable" } },
+                    { "code": "Promise.resolve();", "env": { "es6": true } },
+                    { "code": "var [a, b] = c; a;", "globals": { "c": "readonly" } },
+                    { "code": "class A { m() { return new A(); } }", "parserOptions": { "ecmaVersion": 2015 } },
+                    { "code": "try {} catch (err) { err; }", "parserOptions": { "ecmaVersion": 2015 } },
+                    { "code": "typeof a", "options": [{ "typeof": false }] },
+                ],
+                "invalid": [
+                    {
+                        "code": "a = 1;",
+                        "errors": [{ "message": "'a' is not defined.", "messageId": "undef", "line": 1, "column": 1, "endColumn": 2 }],
+                    },
+                    {
+                        "code": "if (typeof anUndefinedVar === 'string') {}",
+                        "options": [{ "typeof": true }],
+                        "errors": [{ "message": "'anUndefinedVar' is not defined.", "column": 12 }],
+                    },
+                    {
+                        "code": "var a = b;",
+                        "errors": [{ "message": "'b' is not defined.", "line": 1, "column": 9 }],
+                    },
+                    {
+                        "code": "function f() { b; }",
+                        "errors": ["'b' is not defined."],
+                    },
+                    {
+                        "code": "window;",
+                        "errors": ["'window' is not defined."],
+                    },
+                    {
+                        "code": "require(\"a\");",
+                        "errors": ["'require' is not defined."],
+                    },
+                    {
+                        "code": "Promise.resolve();",
+                        "errors": ["'Promise' is not defined."],
+                    },
+                    {
+                        "code": "var a; a = 1;\nb++;",
+                        "errors": [{ "message": "'b' is not defined.", "line": 2, "column": 1 }],
+                    },
+                    {
+                        "code": "/*global b*/ a; b; c;",
+                        "errors": [
+                            { "message": "'a' is not defined.", "column": 14 },
+                            { "message": "'c' is not defined.", "column": 20 },
+                        ],
+                    },
+                    {
+                        "code": "if (typeof d === 'undefined') { d = e; }\nundefined; Math.max(a); f(); function f() {}",
+                        "globals": { "a": "readonly" },
+                        "errors": [
+                            { "message": "'d' is not defined.", "line": 1, "column": 33 },
+                            { "message": "'e' is not defined.", "line": 1, "column": 37 },
+                        ],
+                    },
+                    {
+                        "code": "if (typeof d === 'undefined') { d = e; }\nundefined; Math.max(a); f(); function f() {}",
+                        "options": [{ "typeof": true }],
+                        "globals": { "a": "readonly" },
+                        "errors": 3,
+                    },
+                ],
+            }),
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use std::{fmt::Debug, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use swc_common::{Span, Spanned};
+use swc_ecma_ast::{Decl, ModuleItem, Stmt, VarDeclKind};
+use swc_ecma_lints::rule::Rule;
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+
+use crate::linter::{
+    code_path::CodePathAnalysis,
+    config::{RuleConfig, RuleOptions},
+    diagnostic::Reporter,
+    rule::{visitor_rule, RuleMeta, RuleType},
+    rules::LintContext,
+};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct NoUnreachableConfig {}
+
+impl RuleOptions for NoUnreachableConfig {
+    fn from_options(_options: &[Value]) -> Result<Self, String> {
+        Ok(Self {})
+    }
+}
+
+pub const META: RuleMeta = RuleMeta {
+    id: "no-unreachable",
+    description:
+        "Disallow unreachable code after `return`, `throw`, `continue`, and `break` statements",
+    rule_type: RuleType::Problem,
+    recommended: true,
+    fixable: None,
+    has_suggestions: false,
+    schema,
+    docs_url: "https://eslint.org/docs/latest/rules/no-unreachable",
+    create,
+    config: |config| &config.nounreachable,
+    config_mut: |config| &mut config.nounreachable,
+};
+
+fn schema() -> Value {
+    json!([])
+}
+
+fn create(context: &LintContext) -> Box<dyn Rule> {
+    nounreachable(
+        context
+            .diagnostics
+            .reporter(META.id, context.lint_config.nounreachable.severity()),
+        context.code_paths.clone(),
+        &context.lint_config.nounreachable,
+    )
+}
+
+pub fn nounreachable(
+    reporter: Reporter,
+    code_paths: Arc<CodePathAnalysis>,
+    config: &RuleConfig<NoUnreachableConfig>,
+) -> Box<dyn Rule> {
+    visitor_rule(NoUnreachable::new(reporter, code_paths, config))
+}
+
+struct NoUnreachable {
+    reporter: Reporter,
+    code_paths: Arc<CodePathAnalysis>,
+}
+
+impl NoUnreachable {
+    fn new(
+        reporter: Reporter,
+        code_paths: Arc<CodePathAnalysis>,
+        config: &RuleConfig<NoUnreachableConfig>,
+    ) -> Self {
+        let _nounreachable_config = config.get_rule_config();
+        Self {
+            reporter,
+            code_paths,
+        }
+    }
+
+    fn emit_error(&self, span: Span) {
+        let msg = "Unreachable code.";
+        self.reporter.emit(
+            self.reporter
+                .diagnostic(span, msg)
+                .with_message_id("unreachableCode"),
+        );
+    }
+
+    /// Report each run of unreachable statements in a list once, and visit the others
+    fn check_stmts<'a>(&mut self, stmts: impl IntoIterator<Item = &'a Stmt>) {
+        let mut unreachable: Option<Span> = None;
+
+        for stmt in stmts {
+            // Functions declared after e.g., `return` can still be called
+            if is_hoisted(stmt) {
+                stmt.visit_with(self);
+                continue;
+            }
+
+            if self.code_paths.is_reachable(stmt.span()) {
+                if let Some(span) = unreachable.take() {
+                    self.emit_error(span);
+                }
+                stmt.visit_with(self);
+            } else {
+                unreachable = Some(match unreachable {
+                    Some(span) => span.to(stmt.span()),
+                    None => stmt.span(),
+                });
+            }
+        }
+
+        if let Some(span) = unreachable {
+            self.emit_error(span);
+        }
+    }
+}
+
+impl Debug for NoUnreachable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NoUnreachable").finish()
+    }
+}
+
+/// Whether a statement has no effect where it is written, like a function declaration or
+/// `var a;`, so it may come after e.g., `return`
+fn is_hoisted(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Empty(_) | Stmt::Decl(Decl::Fn(_) | Decl::TsInterface(_) | Decl::TsTypeAlias(_)) => {
+            true
+        }
+        Stmt::Decl(Decl::Var(var_decl)) => {
+            var_decl.kind == VarDeclKind::Var
+                && var_decl.decls.iter().all(|decl| decl.init.is_none())
+        }
+        _ => false,
+    }
+}
+
+impl Visit for NoUnreachable {
+    noop_visit_type!();
+
+    fn visit_module_items(&mut self, items: &[ModuleItem]) {
+        let mut stmts = Vec::new();
+        for item in items {
+            match item {
+                ModuleItem::Stmt(stmt) => stmts.push(stmt),
+                ModuleItem::ModuleDecl(decl) => decl.visit_with(self),
+            }
+        }
+
+        self.check_stmts(stmts);
+    }
+
+    fn visit_stmts(&mut self, stmts: &[Stmt]) {
+        self.check_stmts(stmts);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::linter::rule_tester::RuleTester;
+
+    #[test]
+    fn no_unreachable() {
+        RuleTester::default().run(
+            "no-unreachable",
+            json!({
+                "valid": [
+                    "function foo() { function bar() { return 1; } return bar(); }",
+                    "function foo() { return bar(); function bar() { return 1; } }",
+                    "function foo() { return x; var x; }",
+                    "while (true) { switch (foo) { case 1: x = 1; x = 2;} }",
+                    "while (true) { break; var x; }",
+                    "function f() { throw a; var b; function c() {} }",
+                ],
+                "invalid": [
+                    {
+                        "code": "function f() { return; a(); b(); }\nfunction g() { throw c; var d; function h() {} }\nwhile (true) {}\nf();",
+                        "errors": [
+                            { "message": "Unreachable code.", "messageId": "unreachableCode", "line": 1, "column": 24 },
+                            { "message": "Unreachable code.", "line": 4, "column": 1 },
+                        ],
+                    },
+                    {
+                        "code": "function foo() { return x; var x = 1; }",
+                        "errors": [{ "message": "Unreachable code.", "column": 28 }],
+                    },
+                    {
+                        "code": "while (true) { continue; var x = 1; }",
+                        "errors": ["Unreachable code."],
+                    },
+                ],
+            }),
+        );
+    }
+}
@@ -0,0 +1,512 @@
+use std::{collections::HashSet, fmt::Debug, sync::Arc};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use swc_common::{BytePos, Span, Spanned};
+use swc_ecma_ast::{
+    AssignOp, ClassMethod, Expr, ExprStmt, Ident, MethodKind, Module, ObjectPat, ObjectPatProp,
+    Pat, PatOrExpr, Script, SetterProp,
+};
+use swc_ecma_lints::rule::Rule;
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+
+use crate::linter::{
+    config::{RuleConfig, RuleOptions},
+    diagnostic::Reporter,
+    rule::{visitor_rule, RuleMeta, RuleType},
+    rules::LintContext,
+    scope::{DefinitionKind, ScopeKind, ScopeManager, Variable},
+};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NoUnusedVarsConfig {
+    vars: VarsMode,
+    args: ArgsMode,
+    ignore_rest_siblings: bool,
+    caught_errors: CaughtErrorsMode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vars_ignore_pattern: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    args_ignore_pattern: Option<String>,
+}
+
+/// Which variables are checked, other than parameters and caught errors
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum VarsMode {
+    #[default]
+    All,
+    /// Only variables which aren't in the global scope
+    Local,
+}
+
+/// Which parameters are checked
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+enum ArgsMode {
+    /// Only parameters after the last used parameter
+    #[default]
+    AfterUsed,
+    All,
+    None,
+}
+
+/// Whether the parameters of `catch` clauses are checked
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum CaughtErrorsMode {
+    #[default]
+    All,
+    None,
+}
+
+/// The options object, where every option is optional
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct RawOptions {
+    vars: Option<VarsMode>,
+    args: Option<ArgsMode>,
+    ignore_rest_siblings: Option<bool>,
+    caught_errors: Option<CaughtErrorsMode>,
+    vars_ignore_pattern: Option<String>,
+    args_ignore_pattern: Option<String>,
+}
+
+impl RuleOptions for NoUnusedVarsConfig {
+    fn from_options(options: &[Value]) -> Result<Self, String> {
+        let mut config = Self::default();
+
+        match &options[0] {
+            Value::String(vars) => {
+                config.vars = serde_json::from_value(options[0].clone()).map_err(|_| {
+                    format!(
+                        "Value \"{}\" should be equal to one of the allowed values: \"all\", \"local\".",
+                        vars
+                    )
+                })?;
+            }
+            Value::Object(_) => {
+                let raw: RawOptions = serde_json::from_value(options[0].clone())
+                    .map_err(|err| format!("Value {} is invalid: {}.", options[0], err))?;
+
+                for pattern in [&raw.vars_ignore_pattern, &raw.args_ignore_pattern]
+                    .into_iter()
+                    .flatten()
+                {
+                    Regex::new(pattern).map_err(|err| {
+                        format!("Invalid regular expression /{}/: {}", pattern, err)
+                    })?;
+                }
+
+                config.vars = raw.vars.unwrap_or(config.vars);
+                config.args = raw.args.unwrap_or(config.args);
+                config.ignore_rest_siblings = raw.ignore_rest_siblings.unwrap_or_default();
+                config.caught_errors = raw.caught_errors.unwrap_or(config.caught_errors);
+                config.vars_ignore_pattern = raw.vars_ignore_pattern;
+                config.args_ignore_pattern = raw.args_ignore_pattern;
+            }
+            value => {
+                return Err(format!(
+                    "Value {} should match exactly one schema in oneOf.",
+                    value
+                ))
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+pub const META: RuleMeta = RuleMeta {
+    id: "no-unused-vars",
+    description: "Disallow unused variables",
+    rule_type: RuleType::Problem,
+    recommended: true,
+    fixable: None,
+    has_suggestions: false,
+    schema,
+    docs_url: "https://eslint.org/docs/latest/rules/no-unused-vars",
+    create,
+    config: |config| &config.nounusedvars,
+    config_mut: |config| &mut config.nounusedvars,
+};
+
+fn schema() -> Value {
+    json!([{
+        "oneOf": [
+            { "enum": ["all", "local"] },
+            {
+                "type": "object",
+                "properties": {
+                    "vars": { "enum": ["all", "local"] },
+                    "varsIgnorePattern": { "type": "string" },
+                    "args": { "enum": ["all", "after-used", "none"] },
+                    "ignoreRestSiblings": { "type": "boolean" },
+                    "argsIgnorePattern": { "type": "string" },
+                    "caughtErrors": { "enum": ["all", "none"] },
+                },
+                "additionalProperties": false,
+            },
+        ],
+    }])
+}
+
+fn create(context: &LintContext) -> Box<dyn Rule> {
+    nounusedvars(
+        context
+            .diagnostics
+            .reporter(META.id, context.lint_config.nounusedvars.severity()),
+        context.scope.clone(),
+        &context.lint_config.exported,
+        &context.lint_config.nounusedvars,
+    )
+}
+
+pub fn nounusedvars(
+    reporter: Reporter,
+    scope: Arc<ScopeManager>,
+    exported: &[String],
+    config: &RuleConfig<NoUnusedVarsConfig>,
+) -> Box<dyn Rule> {
+    visitor_rule(NoUnusedVars::new(reporter, scope, exported, config))
+}
+
+struct NoUnusedVars {
+    reporter: Reporter,
+    scope: Arc<ScopeManager>,
+    /// Variables marked as used by `/* exported */` comments
+    exported: Vec<String>,
+    vars: VarsMode,
+    args: ArgsMode,
+    ignore_rest_siblings: bool,
+    caught_errors: CaughtErrorsMode,
+    vars_ignore_pattern: Option<Regex>,
+    args_ignore_pattern: Option<Regex>,
+    /// Identifiers in object patterns with a rest element, e.g., `a` in `const { a, ...b } = c`
+    rest_siblings: HashSet<BytePos>,
+    /// Parameters of setters, which can't be left out
+    setter_params: HashSet<BytePos>,
+    /// Identifiers which only update themselves, e.g., `a++;` or `a += 1;`
+    self_updates: HashSet<BytePos>,
+}
+
+impl NoUnusedVars {
+    fn new(
+        reporter: Reporter,
+        scope: Arc<ScopeManager>,
+        exported: &[String],
+        config: &RuleConfig<NoUnusedVarsConfig>,
+    ) -> Self {
+        let nounusedvars_config = config.get_rule_config();
+        let compile = |pattern: &Option<String>| {
+            pattern
+                .as_deref()
+                .map(|pattern| Regex::new(pattern).unwrap())
+        };
+
+        Self {
+            reporter,
+            scope,
+            exported: exported.to_vec(),
+            vars: nounusedvars_config.vars,
+            args: nounusedvars_config.args,
+            ignore_rest_siblings: nounusedvars_config.ignore_rest_siblings,
+            caught_errors: nounusedvars_config.caught_errors,
+            vars_ignore_pattern: compile(&nounusedvars_config.vars_ignore_pattern),
+            args_ignore_pattern: compile(&nounusedvars_config.args_ignore_pattern),
+            rest_siblings: HashSet::new(),
+            setter_params: HashSet::new(),
+            self_updates: HashSet::new(),
+        }
+    }
+
+    fn emit_error(&self, span: Span, msg: &str) {
+        self.reporter.emit(
+            self.reporter
+                .diagnostic(span, msg)
+                .with_message_id("unusedVar"),
+        );
+    }
+
+    /// Whether a variable is read, other than by itself, e.g., by a recursive call
+    fn is_used(&self, variable: &Variable) -> bool {
+        variable.references.iter().any(|id| {
+            let reference = &self.scope.references[*id];
+            let in_own_declaration = variable.defs.iter().any(|def| {
+                matches!(
+                    def.kind,
+                    DefinitionKind::FunctionName | DefinitionKind::ClassName
+                ) && def.node.contains(reference.span)
+            });
+
+            reference.is_read()
+                && !in_own_declaration
+                && !self.self_updates.contains(&reference.span.lo)
+        })
+    }
+
+    /// Whether a parameter comes after every parameter of its function which is used
+    fn is_after_last_used_param(&self, variable: &Variable) -> bool {
+        let position = variable.defs[0].span.lo;
+        !self.scope.scopes[variable.scope]
+            .variables
+            .iter()
+            .map(|id| &self.scope.variables[*id])
+            .filter(|param| {
+                param
+                    .defs
+                    .first()
+                    .is_some_and(|def| def.kind == DefinitionKind::Parameter)
+            })
+            .any(|param| param.defs[0].span.lo > position && self.is_used(param))
+    }
+
+    /// Whether the rule's options skip a variable, whether or not it is used
+    fn is_ignored(&self, variable: &Variable) -> bool {
+        let def = &variable.defs[0];
+        if def.kind == DefinitionKind::Parameter {
+            let is_simple = def.span.lo == def.node.lo;
+            return self.args == ArgsMode::None
+                || self.setter_params.contains(&def.node.lo)
+                || matches(&self.args_ignore_pattern, &variable.name)
+                || (self.args == ArgsMode::AfterUsed
+                    && is_simple
+                    && !self.is_after_last_used_param(variable));
+        }
+        if def.kind == DefinitionKind::CatchClause {
+            return self.caught_errors == CaughtErrorsMode::None;
+        }
+
+        // Variables in the global scope of a script may be used by other scripts
+        let is_global = variable.scope == 0;
+        (is_global
+            && (self.vars == VarsMode::Local
+                || self.exported.iter().any(|name| *name == *variable.name)))
+            || matches(&self.vars_ignore_pattern, &variable.name)
+            || (self.ignore_rest_siblings && self.rest_siblings.contains(&def.span.lo))
+    }
+
+    fn check_variables(&self) {
+        for variable in &self.scope.variables {
+            if variable.defs.is_empty() || variable.exported {
+                continue;
+            }
+            // Names of function and class expressions can only be used inside them
+            if matches!(
+                self.scope.scopes[variable.scope].kind,
+                ScopeKind::FunctionExpressionName | ScopeKind::Class
+            ) {
+                continue;
+            }
+            if self.is_ignored(variable) || self.is_used(variable) {
+                continue;
+            }
+
+            self.report(variable);
+        }
+    }
+
+    /// Report a variable at its last assignment in its own function, or its declaration
+    fn report(&self, variable: &Variable) {
+        let var_scope = self.scope.var_scope(variable.scope);
+        let writes: Vec<_> = variable
+            .references
+            .iter()
+            .map(|id| &self.scope.references[*id])
+            .filter(|reference| reference.is_write())
+            .collect();
+        let span = writes
+            .iter()
+            .rev()
+            .find(|reference| self.scope.var_scope(reference.scope) == var_scope)
+            .map_or(variable.defs[0].span, |reference| reference.span);
+
+        let is_param = variable.defs[0].kind == DefinitionKind::Parameter;
+        let mut msg = if writes.is_empty() {
+            format!("'{}' is defined but never used", variable.name)
+        } else {
+            format!("'{}' is assigned a value but never used", variable.name)
+        };
+        match (
+            is_param,
+            &self.args_ignore_pattern,
+            &self.vars_ignore_pattern,
+        ) {
+            (true, Some(pattern), _) if writes.is_empty() => {
+                msg.push_str(&format!(". Allowed unused args must match /{}/u", pattern))
+            }
+            (false, _, Some(pattern)) => {
+                msg.push_str(&format!(". Allowed unused vars must match /{}/u", pattern))
+            }
+            _ => {}
+        }
+        msg.push('.');
+
+        self.emit_error(span, &msg);
+    }
+}
+
+/// The variable updated by an expression whose result isn't used, e.g., `a` in `a++;`
+fn self_updated_ident(expr: &Expr) -> Option<&Ident> {
+    let target = match expr {
+        Expr::Update(update) => &*update.arg,
+        Expr::Assign(assign) if assign.op != AssignOp::Assign => match &assign.left {
+            PatOrExpr::Expr(expr) => expr,
+            PatOrExpr::Pat(pat) => match &**pat {
+                Pat::Ident(binding) => return Some(&binding.id),
+                Pat::Expr(expr) => expr,
+                _ => return None,
+            },
+        },
+        _ => return None,
+    };
+
+    match target {
+        Expr::Ident(ident) => Some(ident),
+        _ => None,
+    }
+}
+
+fn matches(pattern: &Option<Regex>, name: &str) -> bool {
+    pattern
+        .as_ref()
+        .is_some_and(|pattern| pattern.is_match(name))
+}
+
+impl Debug for NoUnusedVars {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NoUnusedVars")
+            .field("vars", &self.vars)
+            .field("args", &self.args)
+            .field("ignore_rest_siblings", &self.ignore_rest_siblings)
+            .field("caught_errors", &self.caught_errors)
+            .finish()
+    }
+}
+
+impl Visit for NoUnusedVars {
+    noop_visit_type!();
+
+    // Variables are checked once the whole file has been visited
+
+    fn visit_module(&mut self, module: &Module) {
+        module.visit_children_with(self);
+        self.check_variables();
+    }
+
+    fn visit_script(&mut self, script: &Script) {
+        script.visit_children_with(self);
+        self.check_variables();
+    }
+
+    fn visit_object_pat(&mut self, object: &ObjectPat) {
+        if object
+            .props
+            .iter()
+            .any(|prop| matches!(prop, ObjectPatProp::Rest(_)))
+        {
+            for prop in &object.props {
+                match prop {
+                    ObjectPatProp::KeyValue(prop) => {
+                        if let Pat::Ident(binding) = &*prop.value {
+                            self.rest_siblings.insert(binding.id.span.lo);
+                        }
+                    }
+                    ObjectPatProp::Assign(prop) => {
+                        self.rest_siblings.insert(prop.key.span.lo);
+                    }
+                    ObjectPatProp::Rest(_) => {}
+                }
+            }
+        }
+
+        object.visit_children_with(self);
+    }
+
+    fn visit_setter_prop(&mut self, setter: &SetterProp) {
+        self.setter_params.insert(setter.param.span().lo);
+        setter.visit_children_with(self);
+    }
+
+    fn visit_class_method(&mut self, method: &ClassMethod) {
+        if method.kind == MethodKind::Setter {
+            for param in &method.function.params {
+                self.setter_params.insert(param.pat.span().lo);
+            }
+        }
+        method.visit_children_with(self);
+    }
+
+    fn visit_expr_stmt(&mut self, stmt: &ExprStmt) {
+        if let Some(ident) = self_updated_ident(&stmt.expr) {
+            self.self_updates.insert(ident.span.lo);
+        }
+
+        stmt.visit_children_with(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::linter::rule_tester::RuleTester;
+
+    #[test]
+    fn no_unused_vars() {
+        RuleTester::new(json!({ "parserOptions": { "ecmaVersion": 2018 } })).run(
+            "no-unused-vars",
+            json!({
+                "valid": [
+                    "var a = 1; a;",
+                    "function f() {} f();",
+                    "function f(x, y) { return y; } f();",
+                    { "code": "export function f(a) { return a; }", "parserOptions": { "sourceType": "module" } },
+                    { "code": "/* exported f */\nfunction f() {}", "parserOptions": { "sourceType": "script" } },
+                    { "code": "var a = 1;", "options": ["local"] },
+                    { "code": "var _a = 1;", "options": [{ "varsIgnorePattern": "^_" }] },
+                ],
+                "invalid": [
+                    {
+                        "code": "var a = 1, b;\nfunction f(x, y, z) { return y; }\nfunction g() { g(); }\nlet { h, ...i } = c; i; try {} catch (err) {} let n = 0; n++;",
+                        "globals": { "c": "readonly" },
+                        "errors": [
+                            { "message": "'a' is assigned a value but never used.", "messageId": "unusedVar", "line": 1, "column": 5 },
+                            { "message": "'b' is defined but never used.", "line": 1, "column": 12 },
+                            { "line": 2, "column": 10 },
+                            { "line": 2, "column": 18 },
+                            { "line": 3, "column": 10 },
+                            { "line": 4, "column": 7 },
+                            { "line": 4, "column": 39 },
+                            { "line": 4, "column": 58 },
+                        ],
+                    },
+                    {
+                        "code": "export function f(a, b) { let _b; let c = 1; let { d, ...e } = a;\ntry {} catch (err) {} return e; }\nexport const g = 1; export default class H {}",
+                        "options": [{
+                            "vars": "local",
+                            "args": "none",
+                            "caughtErrors": "none",
+                            "ignoreRestSiblings": true,
+                            "varsIgnorePattern": "^_",
+                        }],
+                        "parserOptions": { "sourceType": "module" },
+                        "errors": [{
+                            "message": "'c' is assigned a value but never used. Allowed unused vars must match /^_/u.",
+                            "line": 1,
+                            "column": 39,
+                        }],
+                    },
+                    // Variables in the global scope of a script may be used by other scripts
+                    {
+                        "code": "/* exported f */\nfunction f() {}\nfunction g() {}",
+                        "parserOptions": { "sourceType": "script" },
+                        "errors": [{ "message": "'g' is defined but never used.", "line": 3, "column": 10 }],
+                    },
+                ],
+            }),
+        );
+    }
+}
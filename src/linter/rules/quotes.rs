@@ -1,32 +1,161 @@
-use std::{fmt::Debug, sync::Arc};
+use std::{collections::HashSet, fmt::Debug, sync::Arc};
 
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
-use swc_common::{errors::HANDLER, SourceMap, Span};
-use swc_ecma_ast::{Expr, Lit, Str, Tpl};
+use swc_common::{SourceMap, Span};
+use swc_ecma_ast::{
+    ArrowExpr, BlockStmtOrExpr, Constructor, EsVersion, ExportAll, Expr, ExprStmt, Function,
+    GetterProp, ImportDecl, JSXAttrValue, Lit, ModuleExportName, ModuleItem, NamedExport, PropName,
+    Script, SetterProp, Stmt, Str, TaggedTpl, Tpl,
+};
 use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
 
 use swc_ecma_lints::rule::Rule;
 
-use crate::linter::{config::RuleConfig, rule::visitor_rule};
+use crate::linter::{
+    config::{RuleConfig, RuleOptions},
+    diagnostic::Reporter,
+    rule::{visitor_rule, Fixable, RuleMeta, RuleType},
+    rules::LintContext,
+};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct QuotesConfig {
     #[serde(default)]
     prefer: QuotesType,
+    /// Allow the other quotes when a string contains the preferred quote, e.g., "a 'b'" with
+    /// "single", so it doesn't have to be escaped
+    #[serde(default)]
+    avoid_escape: bool,
+    /// Allow template literals without any of their features, e.g., `a` with "single"
+    #[serde(default)]
+    allow_template_literals: bool,
+}
+
+impl RuleOptions for QuotesConfig {
+    fn from_options(options: &[Value]) -> Result<Self, String> {
+        let prefer = match options[0].as_str() {
+            Some("single") => QuotesType::Single,
+            Some("double") => QuotesType::Double,
+            Some("backtick") => QuotesType::Backtick,
+            _ => {
+                return Err(format!(
+                    "Value {} should be equal to one of the allowed values: \"single\", \"double\", \"backtick\".",
+                    options[0]
+                ))
+            }
+        };
+
+        let mut config = Self {
+            prefer,
+            ..Default::default()
+        };
+        let object = match options.get(1) {
+            // The option before `avoidEscape` was added
+            Some(Value::String(option)) if option == "avoid-escape" => {
+                config.avoid_escape = true;
+                return Ok(config);
+            }
+            Some(value) => value.as_object().ok_or_else(|| {
+                format!(
+                    "Value {} should be equal to one of the allowed values: \"avoid-escape\", or should be object.",
+                    value
+                )
+            })?,
+            None => return Ok(config),
+        };
+
+        for (key, value) in object {
+            match (key.as_str(), value) {
+                ("avoidEscape", Value::Bool(value)) => config.avoid_escape = *value,
+                ("allowTemplateLiterals", Value::Bool(value)) => {
+                    config.allow_template_literals = *value
+                }
+                ("avoidEscape" | "allowTemplateLiterals", value) => {
+                    return Err(format!("Value {} should be boolean.", value))
+                }
+                _ => {
+                    return Err(format!(
+                        "Value {} should NOT have additional properties.",
+                        options[1]
+                    ))
+                }
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+pub const META: RuleMeta = RuleMeta {
+    id: "quotes",
+    description: "Enforce the consistent use of either backticks, double, or single quotes",
+    rule_type: RuleType::Layout,
+    recommended: false,
+    fixable: Some(Fixable::Code),
+    has_suggestions: false,
+    schema,
+    docs_url: "https://eslint.org/docs/latest/rules/quotes",
+    create,
+    config: |config| &config.quotes,
+    config_mut: |config| &mut config.quotes,
+};
+
+fn schema() -> Value {
+    json!([
+        { "enum": ["single", "double", "backtick"] },
+        {
+            "anyOf": [
+                { "enum": ["avoid-escape"] },
+                {
+                    "type": "object",
+                    "properties": {
+                        "avoidEscape": { "type": "boolean" },
+                        "allowTemplateLiterals": { "type": "boolean" },
+                    },
+                    "additionalProperties": false,
+                },
+            ],
+        },
+    ])
+}
+
+fn create(context: &LintContext) -> Box<dyn Rule> {
+    quotes(
+        context
+            .diagnostics
+            .reporter(META.id, context.lint_config.quotes.severity()),
+        &context.source_map,
+        context.es_version,
+        &context.lint_config.quotes,
+    )
 }
 
-pub fn quotes(source_map: &Arc<SourceMap>, config: &RuleConfig<QuotesConfig>) -> Box<dyn Rule> {
-    visitor_rule(Quotes::new(source_map.clone(), config))
+pub fn quotes(
+    reporter: Reporter,
+    source_map: &Arc<SourceMap>,
+    es_version: EsVersion,
+    config: &RuleConfig<QuotesConfig>,
+) -> Box<dyn Rule> {
+    visitor_rule(Quotes::new(
+        reporter,
+        source_map.clone(),
+        es_version,
+        config,
+    ))
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, std::cmp::PartialEq)]
+#[serde(rename_all = "lowercase")]
 enum QuotesType {
     Single,
     Double,
     Backtick,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for QuotesType {
     fn default() -> Self {
         Self::Double
@@ -34,107 +163,508 @@ impl Default for QuotesType {
 }
 
 impl QuotesType {
-    pub fn from_bytes(bytes: u8) -> Option<QuotesType> {
-        match bytes {
-            b'\'' => Some(QuotesType::Single),
-            b'"' => Some(QuotesType::Double),
-            b'`' => Some(QuotesType::Backtick),
-            _ => None,
+    fn quote(self) -> char {
+        match self {
+            QuotesType::Single => '\'',
+            QuotesType::Double => '"',
+            QuotesType::Backtick => '`',
+        }
+    }
+
+    /// The quote allowed by `avoidEscape` when a string contains this one
+    fn alternate_quote(self) -> char {
+        match self {
+            QuotesType::Double => '\'',
+            QuotesType::Single | QuotesType::Backtick => '"',
+        }
+    }
+
+    /// How the quotes are named in messages
+    fn description(self) -> &'static str {
+        match self {
+            QuotesType::Single => "singlequote",
+            QuotesType::Double => "doublequote",
+            QuotesType::Backtick => "backtick",
         }
     }
 }
 
-#[derive(Default)]
 struct Quotes {
+    reporter: Reporter,
     source_map: Arc<SourceMap>,
+    es_version: EsVersion,
     prefer: QuotesType,
+    avoid_escape: bool,
+    allow_template_literals: bool,
+    /// Strings in directive prologues, like "use strict", which can't be template literals
+    directives: HashSet<Span>,
+    /// Template literals which are whole statements at the top level of a program or function,
+    /// which fixing could turn into directives
+    top_level_templates: HashSet<Span>,
 }
 
 impl Quotes {
-    fn new(source_map: Arc<SourceMap>, config: &RuleConfig<QuotesConfig>) -> Self {
+    fn new(
+        reporter: Reporter,
+        source_map: Arc<SourceMap>,
+        es_version: EsVersion,
+        config: &RuleConfig<QuotesConfig>,
+    ) -> Self {
         let quotes_config = config.get_rule_config();
         Self {
+            reporter,
             source_map,
+            es_version,
             prefer: quotes_config.prefer,
+            avoid_escape: quotes_config.avoid_escape,
+            allow_template_literals: quotes_config.allow_template_literals,
+            directives: HashSet::new(),
+            top_level_templates: HashSet::new(),
         }
     }
 
-    // TODO: Get this from configuration
-    fn get_preferred_type(&self) -> QuotesType {
-        self.prefer
+    /// Template literals were introduced in ES2015
+    fn allows_template_literals(&self) -> bool {
+        self.es_version >= EsVersion::Es2015
     }
 
-    fn emit_error(&self, span: Span) {
-        HANDLER.with(|handler| {
-            handler
-                .struct_span_err(
-                    span,
-                    format!(
-                        "Incorrect quotes type. Expected {:?}.",
-                        self.get_preferred_type()
-                    )
-                    .as_str(),
-                )
-                .emit();
-        });
+    fn prefers_backtick(&self) -> bool {
+        self.prefer == QuotesType::Backtick
+    }
+
+    /// Report a string, fixing it with `replacement` if it can be fixed
+    fn emit_error(&self, span: Span, replacement: Option<String>) {
+        let msg = format!("Strings must use {}.", self.prefer.description());
+        let mut diagnostic = self
+            .reporter
+            .diagnostic(span, &msg)
+            .with_message_id("wrongQuotes");
+        if let Some(replacement) = replacement {
+            diagnostic = diagnostic.with_fix(self.reporter.fix(span, &replacement));
+        }
+
+        self.reporter.emit(diagnostic);
     }
 
     // Implementation for 'normal' strings - single and double quotes
     fn check_str(&self, str: &Str) {
-        // Get quote type as bytes, for comparison later
-        let quote = self.source_map.lookup_byte_offset(str.span.lo);
-        let quote_index = quote.pos.0;
-        let src = &quote.sf.src;
-        let bytes = src.as_bytes()[quote_index as usize];
-
-        // TODO: Get this from configuration
-        let expected_type = self.get_preferred_type();
-
-        // Output error if quotes type is not the same as expected
-        if let Some(quotes_type) = QuotesType::from_bytes(bytes) {
-            // If quotes type is as expected, ignore
-            if quotes_type == expected_type {
-                return;
-            }
+        // Strings can't be replaced by template literals before ES2015, so allow them
+        if self.prefers_backtick() && !self.allows_template_literals() {
+            return;
+        }
 
-            // If quotes type is not as expected, output warning
-            self.emit_error(str.span)
+        let raw = match self.source_map.span_to_snippet(str.span) {
+            Ok(raw) => raw,
+            Err(_) => return,
+        };
+
+        let quote = self.prefer.quote();
+        if is_surrounded_by(&raw, quote) {
+            return;
         }
+        if self.avoid_escape
+            && is_surrounded_by(&raw, self.prefer.alternate_quote())
+            && raw.contains(quote)
+        {
+            return;
+        }
+
+        // Escapes like `\1` are syntax errors in template literals
+        let fix = if self.prefers_backtick() && has_octal_escape(&raw) {
+            None
+        } else {
+            Some(convert(&raw, quote))
+        };
+        self.emit_error(str.span, fix);
     }
 
     // Implementation for template literal strings - backticks
     fn check_tpl(&self, tpl: &Tpl) {
         // If backticks are the preferred type, ignore
-        if let QuotesType::Backtick = self.get_preferred_type() {
+        if self.prefers_backtick() || self.allow_template_literals {
             return;
         }
 
-        // If the template literal contains a variable reference, allow it
-        if !tpl.exprs.is_empty() {
+        // If the template literal contains a variable reference or a line break, allow it
+        if !tpl.exprs.is_empty()
+            || tpl
+                .quasis
+                .iter()
+                .any(|quasi| has_unescaped_line_break(&quasi.raw))
+        {
             return;
         }
 
-        // Else, output error
-        self.emit_error(tpl.span)
+        let raw = match self.source_map.span_to_snippet(tpl.span) {
+            Ok(raw) => raw,
+            Err(_) => return,
+        };
+        let fix = if self.top_level_templates.contains(&tpl.span) {
+            None
+        } else {
+            Some(convert(&raw, self.prefer.quote()))
+        };
+        self.emit_error(tpl.span, fix);
+    }
+
+    /// Remember the directives and top level template literals in the body of a program or
+    /// function
+    fn check_body<'a>(&mut self, stmts: impl IntoIterator<Item = &'a Stmt>) {
+        let mut in_prologue = true;
+        for stmt in stmts {
+            let expr = match stmt {
+                Stmt::Expr(ExprStmt { expr, .. }) => &**expr,
+                _ => {
+                    in_prologue = false;
+                    continue;
+                }
+            };
+
+            match expr {
+                Expr::Lit(Lit::Str(str)) if in_prologue => {
+                    self.directives.insert(str.span);
+                }
+                Expr::Tpl(tpl) => {
+                    self.top_level_templates.insert(tpl.span);
+                    in_prologue = false;
+                }
+                _ => in_prologue = false,
+            }
+        }
     }
 }
 
 impl Debug for Quotes {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Quotes").finish()
+        f.debug_struct("Quotes")
+            .field("prefer", &self.prefer)
+            .field("avoid_escape", &self.avoid_escape)
+            .field("allow_template_literals", &self.allow_template_literals)
+            .finish()
     }
 }
 
+/// Whether a string's source starts and ends with a quote
+fn is_surrounded_by(raw: &str, quote: char) -> bool {
+    raw.len() >= 2 && raw.starts_with(quote) && raw.ends_with(quote)
+}
+
+/// Whether the source of a string has an escape like `\1` or `\08`
+fn has_octal_escape(raw: &str) -> bool {
+    let mut chars = raw.chars().peekable();
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            continue;
+        }
+        match chars.next() {
+            Some('1'..='9') => return true,
+            Some('0') if chars.peek().is_some_and(char::is_ascii_digit) => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Whether the source of a template literal spans lines, other than by escaped line breaks
+fn has_unescaped_line_break(raw: &str) -> bool {
+    let mut chars = raw.chars();
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' => {
+                chars.next();
+            }
+            '\r' | '\n' | '\u{2028}' | '\u{2029}' => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// The source of a string or template literal with the quotes replaced, re-escaping its content
+/// like ESLint, e.g., 'a "b"' is "a \"b\"" with double quotes
+fn convert(raw: &str, new_quote: char) -> String {
+    let old_quote = match raw.chars().next() {
+        Some(quote) if quote == new_quote => return raw.to_string(),
+        Some(quote) => quote,
+        None => return raw.to_string(),
+    };
+    let content = &raw[old_quote.len_utf8()..raw.len() - old_quote.len_utf8()];
+
+    let mut output = String::with_capacity(raw.len());
+    output.push(new_quote);
+
+    let mut chars = content.chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' => match chars.next() {
+                // Unescape the old quote, and `${` if it was a template literal
+                Some(escaped) if escaped == old_quote => output.push(escaped),
+                Some('$') if old_quote == '`' && chars.peek() == Some(&'{') => output.push('$'),
+                Some(escaped) => {
+                    output.push('\\');
+                    output.push(escaped);
+                }
+                None => output.push('\\'),
+            },
+            // Escape the new quote, and `${` if it will be a template literal
+            char if char == new_quote => {
+                output.push('\\');
+                output.push(char);
+            }
+            '$' if new_quote == '`' && chars.peek() == Some(&'{') => output.push_str("\\$"),
+            // Line breaks in template literals become escapes
+            '\r' if old_quote == '`' => {
+                chars.next_if_eq(&'\n');
+                output.push_str("\\n");
+            }
+            '\n' if old_quote == '`' => output.push_str("\\n"),
+            char => output.push(char),
+        }
+    }
+
+    output.push(new_quote);
+    output
+}
+
 impl Visit for Quotes {
     noop_visit_type!();
 
-    fn visit_expr(&mut self, expr: &Expr) {
-        match expr {
-            Expr::Tpl(tpl) => self.check_tpl(tpl),
-            Expr::Lit(Lit::Str(str)) => self.check_str(str),
-            _ => {}
+    fn visit_module_items(&mut self, items: &[ModuleItem]) {
+        self.check_body(items.iter().filter_map(|item| match item {
+            ModuleItem::Stmt(stmt) => Some(stmt),
+            ModuleItem::ModuleDecl(_) => None,
+        }));
+        for item in items {
+            item.visit_with(self);
+        }
+    }
+
+    fn visit_script(&mut self, script: &Script) {
+        self.check_body(&script.body);
+        script.visit_children_with(self);
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        if let Some(body) = &function.body {
+            self.check_body(&body.stmts);
+        }
+        function.visit_children_with(self);
+    }
+
+    fn visit_arrow_expr(&mut self, arrow: &ArrowExpr) {
+        if let BlockStmtOrExpr::BlockStmt(body) = &arrow.body {
+            self.check_body(&body.stmts);
+        }
+        arrow.visit_children_with(self);
+    }
+
+    fn visit_constructor(&mut self, constructor: &Constructor) {
+        if let Some(body) = &constructor.body {
+            self.check_body(&body.stmts);
+        }
+        constructor.visit_children_with(self);
+    }
+
+    fn visit_getter_prop(&mut self, getter: &GetterProp) {
+        if let Some(body) = &getter.body {
+            self.check_body(&body.stmts);
+        }
+        getter.visit_children_with(self);
+    }
+
+    fn visit_setter_prop(&mut self, setter: &SetterProp) {
+        if let Some(body) = &setter.body {
+            self.check_body(&body.stmts);
+        }
+        setter.visit_children_with(self);
+    }
+
+    fn visit_str(&mut self, str: &Str) {
+        // Directives can't be template literals
+        if self.prefers_backtick() && self.directives.contains(&str.span) {
+            return;
+        }
+        self.check_str(str);
+    }
+
+    fn visit_tpl(&mut self, tpl: &Tpl) {
+        self.check_tpl(tpl);
+        tpl.visit_children_with(self);
+    }
+
+    fn visit_tagged_tpl(&mut self, tagged_tpl: &TaggedTpl) {
+        // Tagged templates need backticks
+        tagged_tpl.tag.visit_with(self);
+        tagged_tpl.tpl.exprs.visit_with(self);
+    }
+
+    fn visit_jsx_attr_value(&mut self, value: &JSXAttrValue) {
+        // JSX attributes can't be template literals, and have their own rule in ESLint
+        if let JSXAttrValue::Lit(Lit::Str(_)) = value {
+            return;
+        }
+        value.visit_children_with(self);
+    }
+
+    fn visit_prop_name(&mut self, prop_name: &PropName) {
+        // Property keys can't be template literals, unless they are computed
+        if self.prefers_backtick() && matches!(prop_name, PropName::Str(_)) {
+            return;
+        }
+        prop_name.visit_children_with(self);
+    }
+
+    fn visit_module_export_name(&mut self, name: &ModuleExportName) {
+        // Names in imports and exports can't be template literals
+        if self.prefers_backtick() {
+            return;
         }
+        name.visit_children_with(self);
+    }
+
+    fn visit_import_decl(&mut self, import: &ImportDecl) {
+        // Module sources can't be template literals
+        if self.prefers_backtick() {
+            return import.specifiers.visit_with(self);
+        }
+        import.visit_children_with(self);
+    }
+
+    fn visit_export_all(&mut self, export: &ExportAll) {
+        if self.prefers_backtick() {
+            return;
+        }
+        export.visit_children_with(self);
+    }
+
+    fn visit_named_export(&mut self, export: &NamedExport) {
+        if self.prefers_backtick() {
+            return export.specifiers.visit_with(self);
+        }
+        export.visit_children_with(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::linter::rule_tester::RuleTester;
 
-        expr.visit_children_with(self);
+    #[test]
+    fn quotes() {
+        RuleTester::new(json!({ "parserOptions": { "ecmaVersion": 2015 } })).run(
+            "quotes",
+            json!({
+                "valid": [
+                    "var foo = \"bar\";",
+                    { "code": "var foo = 'bar';", "options": ["single"] },
+                    { "code": "var foo = \"bar\";", "options": ["double"] },
+                    { "code": "var foo = 1;", "options": ["single"] },
+                    { "code": "var foo = \"'\";", "options": ["single", { "avoidEscape": true }] },
+                    { "code": "var foo = '\"';", "options": ["double", { "avoidEscape": true }] },
+                    { "code": "var foo = '\"';", "options": ["double", "avoid-escape"] },
+                    { "code": "var foo = `bar`;", "options": ["backtick"] },
+                    { "code": "var foo = `bar ${baz}`;", "options": ["single"] },
+                    { "code": "var foo = `bar`;", "options": ["single", { "allowTemplateLiterals": true }] },
+                    { "code": "var foo = `bar`;", "options": ["double", { "allowTemplateLiterals": true }] },
+                    { "code": "var foo = tag`bar`;", "options": ["single"] },
+                    { "code": "var foo = `back\ntick`;", "options": ["single"] },
+                    { "code": "var foo = \"a string containing `backtick` quotes\";", "options": ["backtick", { "avoidEscape": true }] },
+                    { "code": "'use strict'; var foo = `bar`;", "options": ["backtick"] },
+                    { "code": "import a from 'a';", "options": ["backtick"], "parserOptions": { "sourceType": "module" } },
+                    { "code": "var obj = {'key0': 0};", "options": ["backtick"] },
+                ],
+                "invalid": [
+                    {
+                        "code": "var foo = 'bar';",
+                        "output": "var foo = \"bar\";",
+                        "errors": [{ "message": "Strings must use doublequote.", "messageId": "wrongQuotes", "line": 1, "column": 11, "endColumn": 16 }],
+                    },
+                    {
+                        "code": "var foo = \"bar\";",
+                        "output": "var foo = 'bar';",
+                        "options": ["single"],
+                        "errors": [{ "message": "Strings must use singlequote.", "column": 11 }],
+                    },
+                    {
+                        "code": "var foo = `bar`;",
+                        "output": "var foo = 'bar';",
+                        "options": ["single"],
+                        "errors": ["Strings must use singlequote."],
+                    },
+                    {
+                        "code": "var foo = 'don\\'t';",
+                        "output": "var foo = \"don't\";",
+                        "errors": ["Strings must use doublequote."],
+                    },
+                    {
+                        "code": "var msg = \"Plugin '\" + name + \"' not found\"",
+                        "output": "var msg = 'Plugin \\'' + name + '\\' not found'",
+                        "options": ["single"],
+                        "errors": [
+                            { "message": "Strings must use singlequote.", "column": 11 },
+                            { "message": "Strings must use singlequote.", "column": 31 },
+                        ],
+                    },
+                    {
+                        "code": "var foo = 'bar';",
+                        "output": "var foo = `bar`;",
+                        "options": ["backtick"],
+                        "errors": ["Strings must use backtick."],
+                    },
+                    {
+                        "code": "var foo = \"bar\";",
+                        "output": "var foo = `bar`;",
+                        "options": ["backtick", { "avoidEscape": true }],
+                        "errors": ["Strings must use backtick."],
+                    },
+                    {
+                        "code": "var foo = `bar`;",
+                        "output": "var foo = \"bar\";",
+                        "options": ["double", { "allowTemplateLiterals": false }],
+                        "errors": ["Strings must use doublequote."],
+                    },
+                    {
+                        "code": "var foo = 'a\\n\\'b';",
+                        "output": "var foo = `a\\n'b`;",
+                        "options": ["backtick"],
+                        "errors": ["Strings must use backtick."],
+                    },
+                    {
+                        "code": "'use strict';\nvar x = 'b', y = \"d\", z = `f`, w = tag`m`, v = `h${x}`, u = `j\nk`;\nvar t = 'o \"p\"';\n",
+                        "output": "\"use strict\";\nvar x = \"b\", y = \"d\", z = \"f\", w = tag`m`, v = `h${x}`, u = `j\nk`;\nvar t = \"o \\\"p\\\"\";\n",
+                        "errors": [
+                            { "message": "Strings must use doublequote.", "line": 1, "column": 1 },
+                            { "line": 2, "column": 9 },
+                            { "line": 2, "column": 27 },
+                            { "line": 4, "column": 9 },
+                        ],
+                    },
+                    {
+                        "code": "'use strict';\nvar x = 'b', y = \"d\", z = `f`, w = tag`m`, v = `h${x}`, u = `j\nk`;\nvar t = 'o \"p\"';\n",
+                        "output": "\"use strict\";\nvar x = \"b\", y = \"d\", z = `f`, w = tag`m`, v = `h${x}`, u = `j\nk`;\nvar t = 'o \"p\"';\n",
+                        "options": ["double", { "avoidEscape": true, "allowTemplateLiterals": true }],
+                        "errors": [{ "line": 1, "column": 1 }, { "line": 2, "column": 9 }],
+                    },
+                    // Directives, module sources and names, property keys and JSX attributes are allowed
+                    {
+                        "code": "'use strict';\nimport a from 'a';\nexport { b as 'c' } from 'd';\nvar e = { 'f': 'g', ['h']: 1 };\nvar i = <div j='k' />;\n",
+                        "output": "'use strict';\nimport a from 'a';\nexport { b as 'c' } from 'd';\nvar e = { 'f': `g`, [`h`]: 1 };\nvar i = <div j='k' />;\n",
+                        "options": ["backtick"],
+                        "parserOptions": { "ecmaVersion": 2022, "sourceType": "module", "ecmaFeatures": { "jsx": true } },
+                        "errors": [{ "line": 4, "column": 16 }, { "line": 4, "column": 22 }],
+                    },
+                    // Octal escapes can't be fixed, as they aren't allowed in template literals
+                    {
+                        "code": "var l = 'm\\1 ${n}';\nvar o = 'p ${q}';\n",
+                        "output": "var l = 'm\\1 ${n}';\nvar o = `p \\${q}`;\n",
+                        "options": ["backtick"],
+                        "parserOptions": { "sourceType": "script" },
+                        "errors": [{ "line": 1, "column": 9 }, { "line": 2, "column": 9 }],
+                    },
+                ],
+            }),
+        );
     }
 }
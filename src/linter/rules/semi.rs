@@ -1,84 +1,638 @@
 use serde::{Deserialize, Serialize};
-use swc_common::{SourceMap, Span};
-use swc_ecma_ast::{ExprStmt, ReturnStmt};
+use serde_json::{json, Value};
+use swc_common::{BytePos, SourceMap, Span, Spanned};
+use swc_ecma_ast::{
+    ArrowExpr, BlockStmt, BlockStmtOrExpr, ClassProp, Decl, ModuleDecl, PrivateProp, PropName, Stmt,
+};
 use swc_ecma_lints::rule::Rule;
-use swc_ecma_utils::HANDLER;
-use swc_ecma_visit::{noop_visit_type, Visit};
+use swc_ecma_parser::token::{BinOpToken, Keyword, Token, TokenAndSpan, Word};
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
 
-use std::{fmt::Debug, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    sync::Arc,
+};
 
-use crate::linter::{config::RuleConfig, rule::visitor_rule};
-
-use std::str;
+use crate::linter::{
+    config::{RuleConfig, RuleOptions},
+    diagnostic::Reporter,
+    rule::{visitor_rule, Fixable, RuleMeta, RuleType},
+    rules::LintContext,
+    tokens::{is_closing_brace, is_semi, Tokens},
+};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct SemiConfig {
-    /// A value of "true" means semis should be used instead of ASI.
-    /// A value of "false" means semis should not be used, preferring ASI.
-    prefer: bool,
+    prefer: SemiType,
+    /// With "always", leave out the semicolon of the last statement in a block on one line,
+    /// e.g., `if (a) { b() }`
+    omit_last_in_one_line_block: bool,
+    /// With "never", whether statements followed by a line starting with `[`, `(`, `/`, `+`,
+    /// `-` or a template literal end with a semicolon
+    before_statement_continuation_chars: ContinuationChars,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum SemiType {
+    /// Semicolons should be used instead of ASI
+    #[default]
+    Always,
+    /// Semicolons should not be used, preferring ASI
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum ContinuationChars {
+    /// Semicolons are required before such lines
+    Always,
+    /// Semicolons are allowed, but not required, before such lines
+    #[default]
+    Any,
+    /// Semicolons are only allowed before such lines when ASI would join the statements
+    Never,
+}
+
+impl RuleOptions for SemiConfig {
+    fn from_options(options: &[Value]) -> Result<Self, String> {
+        let prefer = match options[0].as_str() {
+            Some("always") => SemiType::Always,
+            Some("never") => SemiType::Never,
+            _ => {
+                return Err(format!(
+                    "Value {} should be equal to one of the allowed values: \"always\", \"never\".",
+                    options[0]
+                ))
+            }
+        };
+
+        let mut config = Self {
+            prefer,
+            ..Default::default()
+        };
+        let object = match options.get(1) {
+            Some(value) => value
+                .as_object()
+                .ok_or_else(|| format!("Value {} should be object.", value))?,
+            None => return Ok(config),
+        };
+
+        for (key, value) in object {
+            match (prefer, key.as_str(), value) {
+                (SemiType::Always, "omitLastInOneLineBlock", Value::Bool(value)) => {
+                    config.omit_last_in_one_line_block = *value
+                }
+                (SemiType::Always, "omitLastInOneLineBlock", value) => {
+                    return Err(format!("Value {} should be boolean.", value))
+                }
+                (SemiType::Never, "beforeStatementContinuationChars", value) => {
+                    config.before_statement_continuation_chars = match value.as_str() {
+                        Some("always") => ContinuationChars::Always,
+                        Some("any") => ContinuationChars::Any,
+                        Some("never") => ContinuationChars::Never,
+                        _ => return Err(format!(
+                            "Value {} should be equal to one of the allowed values: \"always\", \"any\", \"never\".",
+                            value
+                        )),
+                    }
+                }
+                _ => {
+                    return Err(format!(
+                        "Value {} should NOT have additional properties.",
+                        options[1]
+                    ))
+                }
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+pub const META: RuleMeta = RuleMeta {
+    id: "semi",
+    description: "Require or disallow semicolons instead of ASI",
+    rule_type: RuleType::Layout,
+    recommended: false,
+    fixable: Some(Fixable::Code),
+    has_suggestions: false,
+    schema,
+    docs_url: "https://eslint.org/docs/latest/rules/semi",
+    create,
+    config: |config| &config.semi,
+    config_mut: |config| &mut config.semi,
+};
+
+fn schema() -> Value {
+    json!({
+        "anyOf": [
+            {
+                "type": "array",
+                "items": [
+                    { "enum": ["never"] },
+                    {
+                        "type": "object",
+                        "properties": {
+                            "beforeStatementContinuationChars": {
+                                "enum": ["always", "any", "never"],
+                            },
+                        },
+                        "additionalProperties": false,
+                    },
+                ],
+                "minItems": 0,
+                "maxItems": 2,
+            },
+            {
+                "type": "array",
+                "items": [
+                    { "enum": ["always"] },
+                    {
+                        "type": "object",
+                        "properties": { "omitLastInOneLineBlock": { "type": "boolean" } },
+                        "additionalProperties": false,
+                    },
+                ],
+                "minItems": 0,
+                "maxItems": 2,
+            },
+        ],
+    })
 }
 
-pub fn semi(source_map: &Arc<SourceMap>, config: &RuleConfig<SemiConfig>) -> Box<dyn Rule> {
-    visitor_rule(Semi::new(source_map.clone(), config))
+fn create(context: &LintContext) -> Box<dyn Rule> {
+    semi(
+        context
+            .diagnostics
+            .reporter(META.id, context.lint_config.semi.severity()),
+        &context.source_map,
+        context.tokens.clone(),
+        &context.lint_config.semi,
+    )
+}
+
+pub fn semi(
+    reporter: Reporter,
+    source_map: &Arc<SourceMap>,
+    tokens: Arc<Tokens>,
+    config: &RuleConfig<SemiConfig>,
+) -> Box<dyn Rule> {
+    visitor_rule(Semi::new(reporter, source_map.clone(), tokens, config))
+}
+
+/// How a statement may be followed by the next line, which decides whether its semicolon can be
+/// left to ASI
+#[derive(Debug, Clone, Copy)]
+enum StatementKind {
+    /// The next line never continues the statement, e.g., `break`
+    Closed,
+    /// The next line may continue the statement, e.g., an expression
+    Open,
+    /// A class field, which is `unsafe_name` if it would join the next member without a
+    /// semicolon, e.g., `get` or `static` without a value
+    ClassField { unsafe_name: bool },
 }
 
-#[derive(Default)]
 struct Semi {
+    reporter: Reporter,
     source_map: Arc<SourceMap>,
-    prefer: bool,
+    tokens: Arc<Tokens>,
+    prefer: SemiType,
+    omit_last_in_one_line_block: bool,
+    before_statement_continuation_chars: ContinuationChars,
+    /// Ends of arrow functions' block bodies, after which ASI never joins the next line
+    arrow_block_ends: HashSet<BytePos>,
+    /// The spans of the statements which are last in a block, with the block's span
+    last_in_block: HashMap<Span, Span>,
 }
 
 impl Semi {
-    fn new(source_map: Arc<SourceMap>, config: &RuleConfig<SemiConfig>) -> Self {
+    fn new(
+        reporter: Reporter,
+        source_map: Arc<SourceMap>,
+        tokens: Arc<Tokens>,
+        config: &RuleConfig<SemiConfig>,
+    ) -> Self {
         let semi_config = config.get_rule_config();
         Self {
+            reporter,
             source_map,
+            tokens,
             prefer: semi_config.prefer,
+            omit_last_in_one_line_block: semi_config.omit_last_in_one_line_block,
+            before_statement_continuation_chars: semi_config.before_statement_continuation_chars,
+            arrow_block_ends: HashSet::new(),
+            last_in_block: HashMap::new(),
         }
     }
 
-    fn emit_error(&self, span: Span, is_missing: bool) {
-        let msg = if is_missing {
-            "Missing semicolon."
-        } else {
-            "Extra semicolon."
+    /// Report a missing semicolon right after the last token of a statement
+    fn emit_missing(&self, last: &TokenAndSpan) {
+        let span = last.span.with_lo(last.span.hi);
+        let fix = self.reporter.fix(span, ";");
+        self.reporter.emit(
+            self.reporter
+                .diagnostic(span, "Missing semicolon.")
+                .with_message_id("missingSemi")
+                .with_fix(fix),
+        );
+    }
+
+    /// Report the semicolon ending a statement, removing it
+    fn emit_extra(&self, semi: &TokenAndSpan) {
+        let fix = self.reporter.fix(semi.span, "");
+        self.reporter.emit(
+            self.reporter
+                .diagnostic(semi.span, "Extra semicolon.")
+                .with_message_id("extraSemi")
+                .with_fix(fix),
+        );
+    }
+
+    fn line(&self, pos: BytePos) -> usize {
+        self.source_map.lookup_char_pos(pos).line
+    }
+
+    fn check(&self, span: Span, kind: StatementKind) {
+        let last = match self.tokens.last_token(span) {
+            Some(last) => last,
+            None => return,
         };
+        let has_semi = is_semi(last);
+
+        match self.prefer {
+            SemiType::Always => {
+                let omit = self.omit_last_in_one_line_block && self.is_last_in_one_line_block(span);
+                if has_semi && omit {
+                    self.emit_extra(last);
+                } else if !has_semi && !omit {
+                    self.emit_missing(last);
+                }
+            }
+            SemiType::Never => {
+                if has_semi {
+                    if self.can_remove_semi(span, last, kind) {
+                        self.emit_extra(last);
+                    }
+                } else if self.before_statement_continuation_chars == ContinuationChars::Always
+                    && !matches!(kind, StatementKind::ClassField { .. })
+                    && self
+                        .tokens
+                        .token_after(span.hi)
+                        .is_some_and(starts_continuation)
+                {
+                    self.emit_missing(last);
+                }
+            }
+        }
+    }
 
-        HANDLER.with(|handler| {
-            handler.struct_span_err(span, msg).emit();
-        })
+    /// Whether a statement is the last in a block written on one line, e.g., `{ a() }`
+    fn is_last_in_one_line_block(&self, span: Span) -> bool {
+        let is_last = self
+            .tokens
+            .token_after(span.hi)
+            .is_some_and(is_closing_brace);
+
+        is_last
+            && self
+                .last_in_block
+                .get(&span)
+                .is_some_and(|block| self.line(block.lo) == self.line(block.hi))
     }
 
-    fn check_span(&self, span: Span) {
-        let x = self.source_map.lookup_byte_offset(span.hi);
-        // - 1 as pos.0 is the newline character
-        let i = x.pos.0 - 1;
-        let src = &x.sf.src;
-        let final_char = src.as_bytes()[i as usize];
-        let is_semi = final_char == b';';
+    /// Whether ASI would end a statement where its semicolon is, so the semicolon isn't needed
+    fn can_remove_semi(&self, span: Span, semi: &TokenAndSpan, kind: StatementKind) -> bool {
+        let next = match self.tokens.token_after(span.hi) {
+            Some(next) if !is_semi(next) && !is_closing_brace(next) => next,
+            // `;;` or `;}`, and the end of the file
+            _ => return true,
+        };
+
+        if let StatementKind::ClassField { unsafe_name } = kind {
+            if unsafe_name || is_unsafe_class_field_follower(next) {
+                return false;
+            }
+        }
+
+        // One statement after another on the same line
+        let prev = self.tokens.token_before(semi.span.lo);
+        if prev.is_some_and(|prev| self.line(prev.span.hi) == self.line(next.span.lo)) {
+            return false;
+        }
 
-        if self.prefer && !is_semi {
-            self.emit_error(span, true);
-        } else if !self.prefer && is_semi {
-            self.emit_error(span, false);
+        let hazard_after = match kind {
+            StatementKind::Closed => false,
+            StatementKind::Open => !prev.is_some_and(|prev| {
+                is_closing_brace(prev) && self.arrow_block_ends.contains(&prev.span.hi)
+            }),
+            StatementKind::ClassField { .. } => true,
+        };
+        if self.before_statement_continuation_chars == ContinuationChars::Never && !hazard_after {
+            return true;
         }
+
+        !starts_continuation(next)
     }
 }
 
 impl Debug for Semi {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Semi").finish()
+        f.debug_struct("Semi")
+            .field("prefer", &self.prefer)
+            .field(
+                "omit_last_in_one_line_block",
+                &self.omit_last_in_one_line_block,
+            )
+            .field(
+                "before_statement_continuation_chars",
+                &self.before_statement_continuation_chars,
+            )
+            .finish()
     }
 }
 
+/// Whether a token at the start of a line would continue the statement on the line before
+/// without a semicolon, e.g., `(`, `[` or a template literal
+fn starts_continuation(token: &TokenAndSpan) -> bool {
+    matches!(
+        token.token,
+        Token::LParen
+            | Token::LBracket
+            | Token::BackQuote
+            | Token::Regex(..)
+            | Token::BinOp(BinOpToken::Add | BinOpToken::Sub | BinOpToken::Div)
+            | Token::AssignOp(
+                swc_ecma_ast::AssignOp::AddAssign
+                    | swc_ecma_ast::AssignOp::SubAssign
+                    | swc_ecma_ast::AssignOp::DivAssign
+            )
+    )
+}
+
+/// Whether a token after a class field would join the next member to it without a semicolon,
+/// e.g., a generator method
+fn is_unsafe_class_field_follower(token: &TokenAndSpan) -> bool {
+    matches!(
+        token.token,
+        Token::BinOp(BinOpToken::Mul)
+            | Token::Word(Word::Keyword(Keyword::In | Keyword::InstanceOf))
+    )
+}
+
 impl Visit for Semi {
     noop_visit_type!();
 
-    fn visit_expr_stmt(&mut self, expr_stmt: &ExprStmt) {
-        self.check_span(expr_stmt.span);
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        // Children first, so arrow functions and blocks in the statement are known
+        stmt.visit_children_with(self);
+
+        match stmt {
+            Stmt::Expr(expr_stmt) => self.check(expr_stmt.span, StatementKind::Open),
+            Stmt::Return(return_stmt) => {
+                let kind = match return_stmt.arg {
+                    Some(_) => StatementKind::Open,
+                    None => StatementKind::Closed,
+                };
+                self.check(return_stmt.span, kind);
+            }
+            Stmt::Throw(throw_stmt) => self.check(throw_stmt.span, StatementKind::Open),
+            Stmt::DoWhile(do_while) => self.check(do_while.span, StatementKind::Closed),
+            Stmt::Break(break_stmt) => self.check(break_stmt.span, StatementKind::Closed),
+            Stmt::Continue(continue_stmt) => self.check(continue_stmt.span, StatementKind::Closed),
+            Stmt::Debugger(debugger) => self.check(debugger.span, StatementKind::Closed),
+            _ => {}
+        }
+    }
+
+    fn visit_decl(&mut self, decl: &Decl) {
+        decl.visit_children_with(self);
+
+        // Declarations in the head of a for loop aren't `Decl`s, so they aren't checked
+        if let Decl::Var(var_decl) = decl {
+            self.check(var_decl.span, StatementKind::Open);
+        }
+    }
+
+    fn visit_module_decl(&mut self, decl: &ModuleDecl) {
+        decl.visit_children_with(self);
+
+        match decl {
+            ModuleDecl::Import(import) => self.check(import.span, StatementKind::Closed),
+            ModuleDecl::ExportAll(export) => self.check(export.span, StatementKind::Closed),
+            ModuleDecl::ExportNamed(export) => self.check(export.span, StatementKind::Closed),
+            ModuleDecl::ExportDefaultExpr(export) => self.check(export.span, StatementKind::Open),
+            // Declarations like `export default function () {}` don't end with a semicolon
+            _ => {}
+        }
+    }
+
+    fn visit_class_prop(&mut self, prop: &ClassProp) {
+        prop.visit_children_with(self);
+
+        let unsafe_name = match &prop.key {
+            PropName::Ident(ident) if prop.value.is_none() => match &*ident.sym {
+                "get" | "set" => true,
+                "static" => !prop.is_static,
+                _ => false,
+            },
+            _ => false,
+        };
+        self.check(prop.span, StatementKind::ClassField { unsafe_name });
+    }
+
+    fn visit_private_prop(&mut self, prop: &PrivateProp) {
+        prop.visit_children_with(self);
+        self.check(prop.span, StatementKind::ClassField { unsafe_name: false });
+    }
+
+    fn visit_arrow_expr(&mut self, arrow: &ArrowExpr) {
+        if let BlockStmtOrExpr::BlockStmt(body) = &arrow.body {
+            self.arrow_block_ends.insert(body.span.hi);
+        }
+        arrow.visit_children_with(self);
+    }
+
+    fn visit_block_stmt(&mut self, block: &BlockStmt) {
+        if let Some(last) = block.stmts.last() {
+            self.last_in_block.insert(last.span(), block.span);
+        }
+        block.visit_children_with(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::SemiConfig;
+    use crate::linter::{config::RuleOptions, rule_tester::RuleTester};
+
+    #[test]
+    fn semi() {
+        RuleTester::new(json!({ "parserOptions": { "ecmaVersion": 2015 } })).run(
+            "semi",
+            json!({
+                "valid": [
+                    "var x = 5;",
+                    "var x =5, y;",
+                    "foo();",
+                    "x = foo();",
+                    "setTimeout(function() {foo = \"bar\"; });",
+                    "for (var a in b){}",
+                    "for (var i;;){}",
+                    "if (true) {}\n;[global, extended].forEach(function(){});",
+                    "throw new Error('foo');",
+                    "do {} while (true);",
+                    { "code": "var x = 5", "options": ["never"] },
+                    { "code": "foo()", "options": ["never"] },
+                    { "code": "for (;;){}", "options": ["never"] },
+                    { "code": "if (foo) { bar() }", "options": ["always", { "omitLastInOneLineBlock": true }] },
+                    { "code": "if (foo) { bar(); baz() }", "options": ["always", { "omitLastInOneLineBlock": true }] },
+                    { "code": "var a = 0\n;[b].forEach(c)", "options": ["never"] },
+                    { "code": "var a = 0\n;(b)()", "options": ["never", { "beforeStatementContinuationChars": "always" }] },
+                    { "code": "do {} while (a)\n[b].forEach(c)", "options": ["never", { "beforeStatementContinuationChars": "never" }] },
+                    { "code": "var a = 0;\n[b].forEach(c)", "options": ["never", { "beforeStatementContinuationChars": "never" }] },
+                    { "code": "var a = () => {};\n(a)()", "options": ["never"] },
+                    { "code": "import a from 'a'", "options": ["never"], "parserOptions": { "sourceType": "module" } },
+                    { "code": "export default function () {}", "parserOptions": { "sourceType": "module" } },
+                ],
+                "invalid": [
+                    {
+                        "code": "function foo() { return [] }",
+                        "output": "function foo() { return []; }",
+                        "errors": [{ "message": "Missing semicolon.", "messageId": "missingSemi", "line": 1, "column": 27, "endLine": 1, "endColumn": 27 }],
+                    },
+                    {
+                        "code": "while(true) { break }",
+                        "output": "while(true) { break; }",
+                        "errors": [{ "message": "Missing semicolon.", "column": 20 }],
+                    },
+                    {
+                        "code": "var x = 5",
+                        "output": "var x = 5;",
+                        "errors": [{ "message": "Missing semicolon.", "column": 10 }],
+                    },
+                    {
+                        "code": "foo()\nbar()",
+                        "output": "foo();\nbar();",
+                        "errors": [
+                            { "message": "Missing semicolon.", "line": 1, "column": 6 },
+                            { "message": "Missing semicolon.", "line": 2, "column": 6 },
+                        ],
+                    },
+                    {
+                        "code": "do{}while(true)",
+                        "output": "do{}while(true);",
+                        "errors": ["Missing semicolon."],
+                    },
+                    {
+                        "code": "import a from 'a'",
+                        "output": "import a from 'a';",
+                        "parserOptions": { "sourceType": "module" },
+                        "errors": ["Missing semicolon."],
+                    },
+                    {
+                        "code": "function foo() { return []; }",
+                        "output": "function foo() { return [] }",
+                        "options": ["never"],
+                        "errors": [{ "message": "Extra semicolon.", "messageId": "extraSemi", "line": 1, "column": 27, "endColumn": 28 }],
+                    },
+                    {
+                        "code": "var x = 5;",
+                        "output": "var x = 5",
+                        "options": ["never"],
+                        "errors": ["Extra semicolon."],
+                    },
+                    {
+                        "code": "if (foo) { bar(); }",
+                        "output": "if (foo) { bar() }",
+                        "options": ["always", { "omitLastInOneLineBlock": true }],
+                        "errors": ["Extra semicolon."],
+                    },
+                    {
+                        "code": "if (foo) {\n  bar()\n}",
+                        "output": "if (foo) {\n  bar();\n}",
+                        "options": ["always", { "omitLastInOneLineBlock": true }],
+                        "errors": [{ "message": "Missing semicolon.", "line": 2, "column": 8 }],
+                    },
+                    {
+                        "code": "var a = () => {};\n(a)()",
+                        "output": "var a = () => {}\n(a)()",
+                        "options": ["never", { "beforeStatementContinuationChars": "never" }],
+                        "errors": [{ "message": "Extra semicolon.", "line": 1, "column": 17 }],
+                    },
+                    {
+                        "code": "do {} while (a)\n[b].forEach(c)",
+                        "output": "do {} while (a);\n[b].forEach(c)",
+                        "options": ["never", { "beforeStatementContinuationChars": "always" }],
+                        "errors": ["Missing semicolon."],
+                    },
+                    {
+                        "code": "var a = 1\nlet b = () => {}; b()\nfor (let i = 0; i < 1; i++) { continue }\nclass C { x = 1\n  #y }\ndo {} while (a)\nexport { b, C }\n",
+                        "output": "var a = 1;\nlet b = () => {}; b();\nfor (let i = 0; i < 1; i++) { continue; }\nclass C { x = 1;\n  #y; }\ndo {} while (a);\nexport { b, C };\n",
+                        "parserOptions": { "ecmaVersion": 2022, "sourceType": "module" },
+                        "errors": [
+                            { "line": 1, "column": 10 },
+                            { "line": 2, "column": 22 },
+                            { "line": 3, "column": 39 },
+                            { "line": 4, "column": 16 },
+                            { "line": 5, "column": 5 },
+                            { "line": 6, "column": 16 },
+                            { "line": 7, "column": 16 },
+                        ],
+                    },
+                    {
+                        "code": "if (a) { b(); }\nif (a) {\n  b()\n}\n",
+                        "output": "if (a) { b() }\nif (a) {\n  b();\n}\n",
+                        "options": ["always", { "omitLastInOneLineBlock": true }],
+                        "errors": [
+                            { "message": "Extra semicolon.", "line": 1, "column": 13 },
+                            { "message": "Missing semicolon.", "line": 3, "column": 6 },
+                        ],
+                    },
+                    {
+                        "code": "a();\n[b].forEach(c);\nd();\nlabel: { break label; }\nlet e = () => {};\n(e)();\nclass F { get;\n  *g() {} }\n",
+                        "output": "a();\n[b].forEach(c)\nd()\nlabel: { break label }\nlet e = () => {};\n(e)()\nclass F { get;\n  *g() {} }\n",
+                        "options": ["never"],
+                        "parserOptions": { "ecmaVersion": 2022 },
+                        "errors": [
+                            { "line": 2, "column": 15 },
+                            { "line": 3, "column": 4 },
+                            { "line": 4, "column": 21 },
+                            { "line": 6, "column": 6 },
+                        ],
+                    },
+                    {
+                        "code": "a();\n[b].forEach(c);\nd();\nlabel: { break label; }\nlet e = () => {};\n(e)();\nclass F { get;\n  *g() {} }\n",
+                        "output": "a();\n[b].forEach(c)\nd()\nlabel: { break label }\nlet e = () => {}\n(e)()\nclass F { get;\n  *g() {} }\n",
+                        "options": ["never", { "beforeStatementContinuationChars": "never" }],
+                        "parserOptions": { "ecmaVersion": 2022 },
+                        "errors": [
+                            { "line": 2, "column": 15 },
+                            { "line": 3, "column": 4 },
+                            { "line": 4, "column": 21 },
+                            { "line": 5, "column": 17 },
+                            { "line": 6, "column": 6 },
+                        ],
+                    },
+                    {
+                        "code": "do {} while (a)\n[b].forEach(c)\n",
+                        "output": "do {} while (a);\n[b].forEach(c)\n",
+                        "options": ["never", { "beforeStatementContinuationChars": "always" }],
+                        "errors": [{ "message": "Missing semicolon.", "line": 1, "column": 16 }],
+                    },
+                ],
+            }),
+        );
     }
 
-    fn visit_return_stmt(&mut self, return_stmt: &ReturnStmt) {
-        self.check_span(return_stmt.span);
+    #[test]
+    fn rejects_options_of_other_modes() {
+        let options = [json!("never"), json!({ "omitLastInOneLineBlock": true })];
+        assert!(SemiConfig::from_options(&options).is_err());
     }
 }
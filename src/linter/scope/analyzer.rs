@@ -0,0 +1,678 @@
+use std::collections::HashMap;
+
+use swc_atoms::JsWord;
+use swc_common::{Span, Spanned};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+
+use super::{
+    Access, Definition, DefinitionKind, Reference, Scope, ScopeId, ScopeKind, ScopeManager,
+    Variable,
+};
+use crate::linter::config::GlobalAccess;
+
+/// Build the scopes of a program, then resolve every reference in them
+pub(super) fn analyze(program: &Program, globals: &HashMap<String, GlobalAccess>) -> ScopeManager {
+    let mut analyzer = Analyzer {
+        manager: ScopeManager::default(),
+        current: 0,
+    };
+
+    analyzer.push_scope(ScopeKind::Global, program.span());
+    for (name, access) in globals {
+        let variable = analyzer.add_variable(0, JsWord::from(name.as_str()));
+        analyzer.manager.variables[variable].global = Some(*access);
+    }
+
+    program.visit_with(&mut analyzer);
+
+    let mut manager = analyzer.manager;
+    resolve_references(&mut manager);
+    manager
+}
+
+/// Resolve references to the nearest variable with their name, recording the scopes they pass
+/// through on the way
+fn resolve_references(manager: &mut ScopeManager) {
+    for id in 0..manager.references.len() {
+        let mut scope_id = Some(manager.references[id].scope);
+
+        while let Some(current) = scope_id {
+            let scope = &mut manager.scopes[current];
+            if let Some(&variable) = scope.names.get(&manager.references[id].name) {
+                manager.references[id].resolved = Some(variable);
+                manager.variables[variable].references.push(id);
+                break;
+            }
+
+            scope.through.push(id);
+            scope_id = scope.parent;
+        }
+    }
+}
+
+struct Analyzer {
+    manager: ScopeManager,
+    current: ScopeId,
+}
+
+impl Analyzer {
+    /// Enter a new scope, returning the scope to go back to
+    fn push_scope(&mut self, kind: ScopeKind, span: Span) -> ScopeId {
+        let id = self.manager.scopes.len();
+        let parent = (id > 0).then_some(self.current);
+        if let Some(parent) = parent {
+            self.manager.scopes[parent].children.push(id);
+        }
+
+        self.manager.scopes.push(Scope {
+            kind,
+            span,
+            parent,
+            children: Vec::new(),
+            variables: Vec::new(),
+            references: Vec::new(),
+            through: Vec::new(),
+            names: HashMap::new(),
+        });
+
+        std::mem::replace(&mut self.current, id)
+    }
+
+    fn pop_scope(&mut self, previous: ScopeId) {
+        self.current = previous;
+    }
+
+    fn var_scope(&self) -> ScopeId {
+        self.manager.var_scope(self.current)
+    }
+
+    /// The variable with a name in a scope, adding it if it isn't declared yet
+    fn add_variable(&mut self, scope: ScopeId, name: JsWord) -> usize {
+        if let Some(&variable) = self.manager.scopes[scope].names.get(&name) {
+            return variable;
+        }
+
+        let id = self.manager.variables.len();
+        self.manager.variables.push(Variable {
+            name: name.clone(),
+            scope,
+            defs: Vec::new(),
+            references: Vec::new(),
+            global: None,
+            exported: false,
+        });
+
+        let scope = &mut self.manager.scopes[scope];
+        scope.names.insert(name, id);
+        scope.variables.push(id);
+        id
+    }
+
+    /// Declare a variable, where `node` is the span of the declaring node, e.g., a function
+    fn declare(&mut self, scope: ScopeId, ident: &Ident, kind: DefinitionKind, node: Span) {
+        let variable = self.add_variable(scope, ident.sym.clone());
+        self.manager.variables[variable].defs.push(Definition {
+            kind,
+            span: ident.span,
+            node,
+        });
+        self.manager.definition_at.insert(ident.span.lo, variable);
+    }
+
+    /// Mark the variable declared by an identifier as exported from the module
+    fn export(&mut self, ident: &Ident) {
+        if let Some(&variable) = self.manager.definition_at.get(&ident.span.lo) {
+            self.manager.variables[variable].exported = true;
+        }
+    }
+
+    fn reference(&mut self, ident: &Ident, access: Access, init: bool) {
+        let id = self.manager.references.len();
+        self.manager.references.push(Reference {
+            name: ident.sym.clone(),
+            span: ident.span,
+            scope: self.current,
+            access,
+            init,
+            resolved: None,
+        });
+
+        self.manager.scopes[self.current].references.push(id);
+        self.manager.reference_at.insert(ident.span.lo, id);
+    }
+
+    /// Declare the identifiers bound by a pattern, visiting default values and computed keys
+    ///
+    /// If `init` is true, the declared variables are also written to, e.g., by `let a = 1`.
+    fn declare_pat(
+        &mut self,
+        pat: &Pat,
+        scope: ScopeId,
+        kind: DefinitionKind,
+        node: Span,
+        init: bool,
+    ) {
+        match pat {
+            Pat::Ident(binding) => {
+                self.declare(scope, &binding.id, kind, node);
+                if init {
+                    self.reference(&binding.id, Access::Write, true);
+                }
+            }
+            Pat::Array(array) => {
+                for elem in array.elems.iter().flatten() {
+                    self.declare_pat(elem, scope, kind, node, init);
+                }
+            }
+            Pat::Object(object) => {
+                for prop in &object.props {
+                    match prop {
+                        ObjectPatProp::KeyValue(prop) => {
+                            prop.key.visit_with(self);
+                            self.declare_pat(&prop.value, scope, kind, node, init);
+                        }
+                        ObjectPatProp::Assign(prop) => {
+                            self.declare(scope, &prop.key, kind, node);
+                            if init || prop.value.is_some() {
+                                self.reference(&prop.key, Access::Write, true);
+                            }
+                            prop.value.visit_with(self);
+                        }
+                        ObjectPatProp::Rest(rest) => {
+                            self.declare_pat(&rest.arg, scope, kind, node, init)
+                        }
+                    }
+                }
+            }
+            Pat::Rest(rest) => self.declare_pat(&rest.arg, scope, kind, node, init),
+            // A default value initializes the pattern
+            Pat::Assign(assign) => {
+                self.declare_pat(&assign.left, scope, kind, node, true);
+                assign.right.visit_with(self);
+            }
+            Pat::Expr(expr) => expr.visit_with(self),
+            Pat::Invalid(_) => {}
+        }
+    }
+
+    /// Record the identifiers assigned to by the target of an assignment, e.g., `[a, b] = c`
+    fn assign_pat(&mut self, pat: &Pat, access: Access) {
+        match pat {
+            Pat::Ident(binding) => self.reference(&binding.id, access, false),
+            Pat::Array(array) => {
+                for elem in array.elems.iter().flatten() {
+                    self.assign_pat(elem, access);
+                }
+            }
+            Pat::Object(object) => {
+                for prop in &object.props {
+                    match prop {
+                        ObjectPatProp::KeyValue(prop) => {
+                            prop.key.visit_with(self);
+                            self.assign_pat(&prop.value, access);
+                        }
+                        ObjectPatProp::Assign(prop) => {
+                            self.reference(&prop.key, access, false);
+                            prop.value.visit_with(self);
+                        }
+                        ObjectPatProp::Rest(rest) => self.assign_pat(&rest.arg, access),
+                    }
+                }
+            }
+            Pat::Rest(rest) => self.assign_pat(&rest.arg, access),
+            Pat::Assign(assign) => {
+                self.assign_pat(&assign.left, access);
+                assign.right.visit_with(self);
+            }
+            Pat::Expr(expr) => self.assign_expr(expr, access),
+            Pat::Invalid(_) => {}
+        }
+    }
+
+    /// Record an identifier assigned to, or visit other targets like `a.b` normally
+    fn assign_expr(&mut self, expr: &Expr, access: Access) {
+        match expr {
+            Expr::Ident(ident) => self.reference(ident, access, false),
+            Expr::Paren(paren) => self.assign_expr(&paren.expr, access),
+            expr => expr.visit_with(self),
+        }
+    }
+
+    /// The scope a declaration's variables are declared in, and how they are declared
+    fn var_decl_target(&self, var_decl: &VarDecl) -> (ScopeId, DefinitionKind) {
+        match var_decl.kind {
+            VarDeclKind::Var => (self.var_scope(), DefinitionKind::Var),
+            VarDeclKind::Let => (self.current, DefinitionKind::Let),
+            VarDeclKind::Const => (self.current, DefinitionKind::Const),
+        }
+    }
+
+    fn declare_var_decl(&mut self, var_decl: &VarDecl) {
+        let (scope, kind) = self.var_decl_target(var_decl);
+        for declarator in &var_decl.decls {
+            let init = declarator.init.is_some();
+            self.declare_pat(&declarator.name, scope, kind, declarator.span, init);
+            declarator.init.visit_with(self);
+        }
+    }
+
+    /// Visit a function's parameters and body in a new function scope
+    fn visit_function_scope<'a, P>(&mut self, span: Span, params: P, body: Option<&BlockStmt>)
+    where
+        P: IntoIterator<Item = &'a Pat>,
+    {
+        let previous = self.push_scope(ScopeKind::Function, span);
+        self.add_variable(self.current, JsWord::from("arguments"));
+
+        for param in params {
+            let span = param.span();
+            self.declare_pat(param, self.current, DefinitionKind::Parameter, span, false);
+        }
+
+        // The body shares the function's scope, rather than being a block of its own
+        if let Some(body) = body {
+            body.stmts.visit_with(self);
+        }
+
+        self.pop_scope(previous);
+    }
+
+    /// Visit a class, with its name in the class's scope if it is a class expression
+    fn visit_class_scope(&mut self, span: Span, ident: Option<&Ident>, class: &Class) {
+        class.decorators.visit_with(self);
+        class.super_class.visit_with(self);
+
+        let previous = self.push_scope(ScopeKind::Class, span);
+        if let Some(ident) = ident {
+            self.declare(self.current, ident, DefinitionKind::ClassName, span);
+        }
+        class.body.visit_with(self);
+        self.pop_scope(previous);
+    }
+
+    /// Whether a `for` statement's declaration needs a scope of its own
+    fn is_lexical(var_decl: &VarDecl) -> bool {
+        var_decl.kind != VarDeclKind::Var
+    }
+
+    fn visit_for_in_or_of(&mut self, span: Span, left: &VarDeclOrPat, right: &Expr, body: &Stmt) {
+        let lexical = matches!(left, VarDeclOrPat::VarDecl(var_decl) if Self::is_lexical(var_decl));
+        let previous = lexical.then(|| self.push_scope(ScopeKind::For, span));
+
+        // Each iteration assigns to the left-hand side
+        match left {
+            VarDeclOrPat::VarDecl(var_decl) => {
+                let (scope, kind) = self.var_decl_target(var_decl);
+                for declarator in &var_decl.decls {
+                    self.declare_pat(&declarator.name, scope, kind, declarator.span, true);
+                }
+            }
+            VarDeclOrPat::Pat(pat) => self.assign_pat(pat, Access::Write),
+        }
+        right.visit_with(self);
+        body.visit_with(self);
+
+        if let Some(previous) = previous {
+            self.pop_scope(previous);
+        }
+    }
+}
+
+impl Visit for Analyzer {
+    noop_visit_type!();
+
+    fn visit_module(&mut self, module: &Module) {
+        let previous = self.push_scope(ScopeKind::Module, module.span);
+        module.body.visit_with(self);
+        self.pop_scope(previous);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Ident(ident) => self.reference(ident, Access::Read, false),
+            expr => expr.visit_children_with(self),
+        }
+    }
+
+    fn visit_prop(&mut self, prop: &Prop) {
+        match prop {
+            Prop::Shorthand(ident) => self.reference(ident, Access::Read, false),
+            prop => prop.visit_children_with(self),
+        }
+    }
+
+    fn visit_assign_expr(&mut self, assign: &AssignExpr) {
+        let access = match assign.op {
+            AssignOp::Assign => Access::Write,
+            _ => Access::ReadWrite,
+        };
+
+        match &assign.left {
+            PatOrExpr::Pat(pat) => self.assign_pat(pat, access),
+            PatOrExpr::Expr(expr) => self.assign_expr(expr, access),
+        }
+        assign.right.visit_with(self);
+    }
+
+    fn visit_update_expr(&mut self, update: &UpdateExpr) {
+        self.assign_expr(&update.arg, Access::ReadWrite);
+    }
+
+    // ~~~ Declarations
+
+    fn visit_decl(&mut self, decl: &Decl) {
+        match decl {
+            Decl::Class(class) => {
+                let span = class.class.span;
+                self.declare(self.current, &class.ident, DefinitionKind::ClassName, span);
+                self.visit_class_scope(class.class.span, None, &class.class);
+            }
+            Decl::Fn(function) => {
+                let span = function.function.span;
+                self.declare(
+                    self.current,
+                    &function.ident,
+                    DefinitionKind::FunctionName,
+                    span,
+                );
+                function.function.visit_with(self);
+            }
+            Decl::Var(var_decl) => self.declare_var_decl(var_decl),
+            Decl::TsEnum(enum_decl) => self.declare(
+                self.current,
+                &enum_decl.id,
+                DefinitionKind::Enum,
+                enum_decl.span,
+            ),
+            Decl::TsModule(module) => {
+                if let TsModuleName::Ident(ident) = &module.id {
+                    self.declare(self.current, ident, DefinitionKind::Namespace, module.span);
+                }
+
+                let previous = self.push_scope(ScopeKind::Function, module.span);
+                module.body.visit_with(self);
+                self.pop_scope(previous);
+            }
+            // Types can't refer to variables
+            Decl::TsInterface(_) | Decl::TsTypeAlias(_) => {}
+        }
+    }
+
+    fn visit_ts_namespace_decl(&mut self, namespace: &TsNamespaceDecl) {
+        self.declare(
+            self.current,
+            &namespace.id,
+            DefinitionKind::Namespace,
+            namespace.span,
+        );
+
+        let previous = self.push_scope(ScopeKind::Function, namespace.span);
+        namespace.body.visit_with(self);
+        self.pop_scope(previous);
+    }
+
+    fn visit_export_decl(&mut self, export: &ExportDecl) {
+        export.decl.visit_with(self);
+
+        // Only the declaration's own names, not those declared inside e.g., a function body
+        let span = export.span;
+        for &variable in &self.manager.scopes[self.current].variables {
+            let variable = &mut self.manager.variables[variable];
+            if variable
+                .defs
+                .iter()
+                .any(|def| span.lo <= def.span.lo && def.span.hi <= span.hi)
+            {
+                variable.exported = true;
+            }
+        }
+    }
+
+    fn visit_import_decl(&mut self, import: &ImportDecl) {
+        for specifier in &import.specifiers {
+            let local = match specifier {
+                ImportSpecifier::Named(named) => &named.local,
+                ImportSpecifier::Default(default) => &default.local,
+                ImportSpecifier::Namespace(namespace) => &namespace.local,
+            };
+            self.declare(
+                self.current,
+                local,
+                DefinitionKind::ImportBinding,
+                specifier.span(),
+            );
+        }
+    }
+
+    fn visit_named_export(&mut self, export: &NamedExport) {
+        // Re-exports from another module don't refer to local variables
+        if export.src.is_some() {
+            return;
+        }
+
+        for specifier in &export.specifiers {
+            if let ExportSpecifier::Named(ExportNamedSpecifier {
+                orig: ModuleExportName::Ident(ident),
+                ..
+            }) = specifier
+            {
+                self.reference(ident, Access::Read, false);
+            }
+        }
+    }
+
+    fn visit_export_default_decl(&mut self, export: &ExportDefaultDecl) {
+        // Unlike other function and class expressions, these names are declared in the module
+        match &export.decl {
+            DefaultDecl::Class(class) => {
+                if let Some(ident) = &class.ident {
+                    let span = class.class.span;
+                    self.declare(self.current, ident, DefinitionKind::ClassName, span);
+                    self.export(ident);
+                }
+                self.visit_class_scope(class.class.span, None, &class.class);
+            }
+            DefaultDecl::Fn(function) => {
+                if let Some(ident) = &function.ident {
+                    let span = function.function.span;
+                    self.declare(self.current, ident, DefinitionKind::FunctionName, span);
+                    self.export(ident);
+                }
+                function.function.visit_with(self);
+            }
+            DefaultDecl::TsInterfaceDecl(_) => {}
+        }
+    }
+
+    // ~~~ Functions and classes
+
+    fn visit_function(&mut self, function: &Function) {
+        function.decorators.visit_with(self);
+        self.visit_function_scope(
+            function.span,
+            function.params.iter().map(|param| &param.pat),
+            function.body.as_ref(),
+        );
+    }
+
+    fn visit_fn_expr(&mut self, function: &FnExpr) {
+        match &function.ident {
+            Some(ident) => {
+                let previous =
+                    self.push_scope(ScopeKind::FunctionExpressionName, function.function.span);
+                let span = function.function.span;
+                self.declare(self.current, ident, DefinitionKind::FunctionName, span);
+                function.function.visit_with(self);
+                self.pop_scope(previous);
+            }
+            None => function.function.visit_with(self),
+        }
+    }
+
+    fn visit_arrow_expr(&mut self, arrow: &ArrowExpr) {
+        let previous = self.push_scope(ScopeKind::Function, arrow.span);
+
+        for param in &arrow.params {
+            let span = param.span();
+            self.declare_pat(param, self.current, DefinitionKind::Parameter, span, false);
+        }
+        match &arrow.body {
+            BlockStmtOrExpr::BlockStmt(body) => body.stmts.visit_with(self),
+            BlockStmtOrExpr::Expr(expr) => expr.visit_with(self),
+        }
+
+        self.pop_scope(previous);
+    }
+
+    fn visit_constructor(&mut self, constructor: &Constructor) {
+        constructor.key.visit_with(self);
+
+        // Parameters may also be TypeScript parameter properties, so aren't all patterns
+        let previous = self.push_scope(ScopeKind::Function, constructor.span);
+        self.add_variable(self.current, JsWord::from("arguments"));
+
+        for param in &constructor.params {
+            match param {
+                ParamOrTsParamProp::Param(param) => {
+                    param.decorators.visit_with(self);
+                    let span = param.span;
+                    self.declare_pat(
+                        &param.pat,
+                        self.current,
+                        DefinitionKind::Parameter,
+                        span,
+                        false,
+                    );
+                }
+                ParamOrTsParamProp::TsParamProp(prop) => match &prop.param {
+                    TsParamPropParam::Ident(binding) => {
+                        let span = prop.span;
+                        self.declare(self.current, &binding.id, DefinitionKind::Parameter, span)
+                    }
+                    TsParamPropParam::Assign(assign) => {
+                        self.declare_pat(
+                            &assign.left,
+                            self.current,
+                            DefinitionKind::Parameter,
+                            prop.span,
+                            true,
+                        );
+                        assign.right.visit_with(self);
+                    }
+                },
+            }
+        }
+        if let Some(body) = &constructor.body {
+            body.stmts.visit_with(self);
+        }
+
+        self.pop_scope(previous);
+    }
+
+    fn visit_getter_prop(&mut self, getter: &GetterProp) {
+        getter.key.visit_with(self);
+        self.visit_function_scope(getter.span, [], getter.body.as_ref());
+    }
+
+    fn visit_setter_prop(&mut self, setter: &SetterProp) {
+        setter.key.visit_with(self);
+        self.visit_function_scope(setter.span, [&setter.param], setter.body.as_ref());
+    }
+
+    fn visit_class_expr(&mut self, class: &ClassExpr) {
+        self.visit_class_scope(class.class.span, class.ident.as_ref(), &class.class);
+    }
+
+    fn visit_static_block(&mut self, block: &StaticBlock) {
+        let previous = self.push_scope(ScopeKind::ClassStaticBlock, block.span);
+        block.body.stmts.visit_with(self);
+        self.pop_scope(previous);
+    }
+
+    // ~~~ Statements with scopes
+
+    fn visit_block_stmt(&mut self, block: &BlockStmt) {
+        let previous = self.push_scope(ScopeKind::Block, block.span);
+        block.stmts.visit_with(self);
+        self.pop_scope(previous);
+    }
+
+    fn visit_for_stmt(&mut self, for_stmt: &ForStmt) {
+        let lexical = matches!(&for_stmt.init, Some(VarDeclOrExpr::VarDecl(var_decl)) if Self::is_lexical(var_decl));
+        let previous = lexical.then(|| self.push_scope(ScopeKind::For, for_stmt.span));
+
+        for_stmt.init.visit_with(self);
+        for_stmt.test.visit_with(self);
+        for_stmt.update.visit_with(self);
+        for_stmt.body.visit_with(self);
+
+        if let Some(previous) = previous {
+            self.pop_scope(previous);
+        }
+    }
+
+    fn visit_for_in_stmt(&mut self, for_in: &ForInStmt) {
+        self.visit_for_in_or_of(for_in.span, &for_in.left, &for_in.right, &for_in.body);
+    }
+
+    fn visit_for_of_stmt(&mut self, for_of: &ForOfStmt) {
+        self.visit_for_in_or_of(for_of.span, &for_of.left, &for_of.right, &for_of.body);
+    }
+
+    fn visit_var_decl(&mut self, var_decl: &VarDecl) {
+        self.declare_var_decl(var_decl);
+    }
+
+    fn visit_catch_clause(&mut self, catch: &CatchClause) {
+        let previous = self.push_scope(ScopeKind::Catch, catch.span);
+        if let Some(param) = &catch.param {
+            let span = param.span();
+            self.declare_pat(
+                param,
+                self.current,
+                DefinitionKind::CatchClause,
+                span,
+                false,
+            );
+        }
+        catch.body.visit_with(self);
+        self.pop_scope(previous);
+    }
+
+    fn visit_switch_stmt(&mut self, switch: &SwitchStmt) {
+        switch.discriminant.visit_with(self);
+
+        let previous = self.push_scope(ScopeKind::Switch, switch.span);
+        switch.cases.visit_with(self);
+        self.pop_scope(previous);
+    }
+
+    fn visit_with_stmt(&mut self, with: &WithStmt) {
+        with.obj.visit_with(self);
+
+        let previous = self.push_scope(ScopeKind::With, with.span);
+        with.body.visit_with(self);
+        self.pop_scope(previous);
+    }
+
+    // ~~~ JSX
+
+    fn visit_jsx_element_name(&mut self, name: &JSXElementName) {
+        match name {
+            // Lowercase names are HTML elements, rather than components
+            JSXElementName::Ident(ident) if !ident.sym.starts_with(char::is_lowercase) => {
+                self.reference(ident, Access::Read, false)
+            }
+            JSXElementName::JSXMemberExpr(member) => member.obj.visit_with(self),
+            _ => {}
+        }
+    }
+
+    fn visit_jsx_object(&mut self, object: &JSXObject) {
+        match object {
+            JSXObject::Ident(ident) => self.reference(ident, Access::Read, false),
+            JSXObject::JSXMemberExpr(member) => member.obj.visit_with(self),
+        }
+    }
+}
@@ -0,0 +1,425 @@
+//! Scope analysis, shared with rules through [LintContext](super::rules::LintContext)
+//!
+//! Like eslint-scope, every identifier which declares or refers to a variable is recorded. Once a
+//! file has been visited, references are resolved to the nearest variable with the same name,
+//! so declarations are found however they are hoisted.
+
+use std::collections::HashMap;
+
+use swc_atoms::JsWord;
+use swc_common::{BytePos, Span};
+use swc_ecma_ast::{Expr, Ident, Lit, MemberExpr, MemberProp, Program};
+
+use super::config::GlobalAccess;
+
+mod analyzer;
+
+/// Globals which refer to the global object, so `window.eval` is the global `eval`
+const GLOBAL_OBJECTS: [&str; 3] = ["global", "globalThis", "window"];
+
+/// Index of a scope in [ScopeManager::scopes]
+pub type ScopeId = usize;
+/// Index of a variable in [ScopeManager::variables]
+pub type VariableId = usize;
+/// Index of a reference in [ScopeManager::references]
+pub type ReferenceId = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeKind {
+    /// The scope of the whole file, containing builtin and configured globals
+    Global,
+    /// The top level of an ES module
+    Module,
+    /// A function, arrow function, method, getter, setter or TypeScript namespace
+    Function,
+    /// Only contains the name of a named function expression, e.g., `(function foo() {})`
+    FunctionExpressionName,
+    Block,
+    /// A `for` statement declaring variables with `let` or `const`
+    For,
+    Catch,
+    Class,
+    ClassStaticBlock,
+    Switch,
+    With,
+}
+
+#[derive(Debug, Clone)]
+pub struct Scope {
+    pub kind: ScopeKind,
+    pub span: Span,
+    pub parent: Option<ScopeId>,
+    pub children: Vec<ScopeId>,
+    /// Variables declared in the scope, in the order they are declared
+    pub variables: Vec<VariableId>,
+    /// References made directly in the scope
+    pub references: Vec<ReferenceId>,
+    /// References made in the scope or its children which aren't resolved in the scope
+    pub through: Vec<ReferenceId>,
+    names: HashMap<JsWord, VariableId>,
+}
+
+impl Scope {
+    /// Whether `var` declarations in the scope are hoisted to it
+    pub fn is_var_scope(&self) -> bool {
+        matches!(
+            self.kind,
+            ScopeKind::Global
+                | ScopeKind::Module
+                | ScopeKind::Function
+                | ScopeKind::ClassStaticBlock
+        )
+    }
+
+    /// The variable declared in this scope with a name, if any
+    pub fn variable(&self, name: &str) -> Option<VariableId> {
+        self.names.get(&JsWord::from(name)).copied()
+    }
+}
+
+/// How a variable was declared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefinitionKind {
+    Var,
+    Let,
+    Const,
+    FunctionName,
+    ClassName,
+    Parameter,
+    CatchClause,
+    ImportBinding,
+    /// A TypeScript `enum`
+    Enum,
+    /// A TypeScript `namespace` or `module`
+    Namespace,
+}
+
+/// A declaration of a variable, e.g., `let a` or a parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Definition {
+    pub kind: DefinitionKind,
+    /// The span of the declared identifier
+    pub span: Span,
+    /// The span of the declaring node, e.g., the function, class, declarator or parameter
+    pub node: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct Variable {
+    pub name: JsWord,
+    pub scope: ScopeId,
+    /// Declarations of the variable, which are empty for globals and `arguments`
+    pub defs: Vec<Definition>,
+    /// References resolved to the variable
+    pub references: Vec<ReferenceId>,
+    /// Whether a global from builtins, environments or configuration can be assigned to
+    pub global: Option<GlobalAccess>,
+    /// Whether the variable is declared by an `export` declaration of a module
+    pub exported: bool,
+}
+
+/// Whether a reference reads from or writes to a variable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+    /// e.g., `a += 1` or `a++`
+    ReadWrite,
+}
+
+#[derive(Debug, Clone)]
+pub struct Reference {
+    pub name: JsWord,
+    /// The span of the referencing identifier
+    pub span: Span,
+    /// The scope the reference is made in
+    pub scope: ScopeId,
+    pub access: Access,
+    /// Whether the write initializes a declaration, e.g., `let a = 1`
+    pub init: bool,
+    /// The variable referred to, or None if it isn't declared or a known global
+    pub resolved: Option<VariableId>,
+}
+
+impl Reference {
+    pub fn is_read(&self) -> bool {
+        self.access != Access::Write
+    }
+
+    pub fn is_write(&self) -> bool {
+        self.access != Access::Read
+    }
+}
+
+/// The scopes, variables and references of a file
+#[derive(Debug, Clone, Default)]
+pub struct ScopeManager {
+    /// Every scope, starting with the global scope
+    pub scopes: Vec<Scope>,
+    pub variables: Vec<Variable>,
+    pub references: Vec<Reference>,
+    reference_at: HashMap<BytePos, ReferenceId>,
+    definition_at: HashMap<BytePos, VariableId>,
+}
+
+impl ScopeManager {
+    /// Analyze a file, with globals available to it, e.g., from
+    /// [LintConfig::resolved_globals](super::config::LintConfig::resolved_globals)
+    pub fn analyze(program: &Program, globals: &HashMap<String, GlobalAccess>) -> Self {
+        analyzer::analyze(program, globals)
+    }
+
+    pub fn global_scope(&self) -> &Scope {
+        &self.scopes[0]
+    }
+
+    /// The reference made by an identifier, if it refers to a variable
+    ///
+    /// Identifiers which aren't references, like property names and labels, return None.
+    pub fn reference(&self, ident: &Ident) -> Option<&Reference> {
+        self.reference_at
+            .get(&ident.span.lo)
+            .map(|id| &self.references[*id])
+    }
+
+    /// The variable declared by an identifier, if it is a declaration
+    pub fn declared_variable(&self, ident: &Ident) -> Option<&Variable> {
+        self.definition_at
+            .get(&ident.span.lo)
+            .map(|id| &self.variables[*id])
+    }
+
+    /// The variable an identifier refers to, if it is declared or a known global
+    pub fn resolve(&self, ident: &Ident) -> Option<&Variable> {
+        let resolved = self.reference(ident)?.resolved?;
+        Some(&self.variables[resolved])
+    }
+
+    /// Whether an identifier refers to a global variable, rather than a variable declared in the
+    /// file, e.g., to tell the global `eval` from a parameter named `eval`
+    pub fn is_global_reference(&self, ident: &Ident) -> bool {
+        match self.reference(ident) {
+            Some(reference) => match reference.resolved {
+                Some(resolved) => {
+                    let variable = &self.variables[resolved];
+                    variable.scope == 0 && variable.defs.is_empty()
+                }
+                None => true,
+            },
+            None => false,
+        }
+    }
+
+    /// The global variable an expression refers to, with the span of its name, either directly
+    /// like `eval`, or as a property of the global object like `window.eval` or
+    /// `globalThis["eval"]`
+    pub fn global_name(&self, expr: &Expr) -> Option<(JsWord, Span)> {
+        match expr {
+            Expr::Ident(ident) => self
+                .is_global_reference(ident)
+                .then(|| (ident.sym.clone(), ident.span)),
+            Expr::Paren(paren) => self.global_name(&paren.expr),
+            Expr::Member(member) => self.global_member_name(member),
+            _ => None,
+        }
+    }
+
+    /// The global variable a property of the global object is, like `window.eval`
+    pub fn global_member_name(&self, member: &MemberExpr) -> Option<(JsWord, Span)> {
+        let (object, _) = self.global_name(&member.obj)?;
+        if !GLOBAL_OBJECTS.contains(&&*object) {
+            return None;
+        }
+
+        static_property_name(&member.prop)
+    }
+
+    /// References to variables which aren't declared and aren't known globals
+    pub fn unresolved_references(&self) -> impl Iterator<Item = &Reference> {
+        self.global_scope()
+            .through
+            .iter()
+            .map(|id| &self.references[*id])
+    }
+
+    /// The innermost scope containing a position
+    pub fn scope_at(&self, pos: BytePos) -> ScopeId {
+        let mut scope_id = 0;
+        'descend: loop {
+            for child in &self.scopes[scope_id].children {
+                let span = self.scopes[*child].span;
+                if span.lo <= pos && pos < span.hi {
+                    scope_id = *child;
+                    continue 'descend;
+                }
+            }
+
+            return scope_id;
+        }
+    }
+
+    /// The nearest scope which `var` declarations are hoisted to, from a scope
+    pub fn var_scope(&self, mut scope_id: ScopeId) -> ScopeId {
+        while !self.scopes[scope_id].is_var_scope() {
+            scope_id = self.scopes[scope_id].parent.unwrap();
+        }
+        scope_id
+    }
+}
+
+/// The name of a property which doesn't depend on variables, e.g., `b` in `a.b`, `a["b"]` or
+/// ``a[`b`]``
+pub fn static_property_name(prop: &MemberProp) -> Option<(JsWord, Span)> {
+    match prop {
+        MemberProp::Ident(ident) => Some((ident.sym.clone(), ident.span)),
+        MemberProp::Computed(computed) => match &*computed.expr {
+            Expr::Lit(Lit::Str(str)) => Some((str.value.clone(), str.span)),
+            Expr::Tpl(tpl) if tpl.exprs.is_empty() => {
+                Some((tpl.quasis[0].cooked.clone()?, tpl.span))
+            }
+            _ => None,
+        },
+        MemberProp::PrivateName(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use swc_common::{FileName, SourceMap};
+    use swc_ecma_ast::EsVersion;
+    use swc_ecma_parser::{parse_file_as_program, Syntax};
+    use swc_ecma_visit::{Visit, VisitWith};
+
+    fn analyze(source: &str) -> (Program, ScopeManager) {
+        let cm: Arc<SourceMap> = Default::default();
+        let file = cm.new_source_file(FileName::Anon, source.to_string());
+        let program = parse_file_as_program(
+            &file,
+            Syntax::Es(Default::default()),
+            EsVersion::latest(),
+            None,
+            &mut Vec::new(),
+        )
+        .unwrap();
+
+        let globals = HashMap::from([(String::from("eval"), GlobalAccess::Readonly)]);
+        let scope = ScopeManager::analyze(&program, &globals);
+        (program, scope)
+    }
+
+    /// Every identifier in a program, in order
+    fn idents(program: &Program) -> Vec<Ident> {
+        struct Idents(Vec<Ident>);
+        impl Visit for Idents {
+            fn visit_ident(&mut self, ident: &Ident) {
+                self.0.push(ident.clone());
+            }
+        }
+
+        let mut collector = Idents(Vec::new());
+        program.visit_with(&mut collector);
+        collector.0
+    }
+
+    fn unresolved(scope: &ScopeManager) -> Vec<&str> {
+        scope
+            .unresolved_references()
+            .map(|reference| &*reference.name)
+            .collect()
+    }
+
+    #[test]
+    fn resolves_hoisted_declarations() {
+        let (_, scope) = analyze("f(a); var a = 1; function f(x) { return x + b; }");
+
+        assert_eq!(unresolved(&scope), ["b"]);
+        let a = &scope.variables[scope.global_scope().variable("a").unwrap()];
+        assert_eq!(a.defs[0].kind, DefinitionKind::Var);
+        assert_eq!(a.references.len(), 2);
+        assert!(scope.references[a.references[1]].init);
+    }
+
+    #[test]
+    fn block_scoping() {
+        let (_, scope) = analyze("{ let a; var b; } a; b; for (const c of []) {} c;");
+
+        assert_eq!(unresolved(&scope), ["a", "c"]);
+    }
+
+    #[test]
+    fn closures_and_shadowing() {
+        let (program, scope) = analyze("let a; function f(a) { return () => a; } a++;");
+        let idents = idents(&program);
+
+        // The arrow function's `a` is the parameter, and `a++` is the top-level variable
+        let param = scope.declared_variable(&idents[2]).unwrap();
+        let in_arrow = scope.resolve(&idents[3]).unwrap();
+        assert_eq!(param.defs, in_arrow.defs);
+        assert_eq!(scope.scopes[in_arrow.scope].kind, ScopeKind::Function);
+
+        let updated = scope.reference(&idents[4]).unwrap();
+        assert_eq!(updated.access, Access::ReadWrite);
+        assert_eq!(
+            scope.resolve(&idents[4]).unwrap().defs[0].kind,
+            DefinitionKind::Let
+        );
+    }
+
+    #[test]
+    fn global_references() {
+        let (program, scope) = analyze("eval(a); function f(eval) { eval(b); } obj.eval;");
+        let idents = idents(&program);
+
+        assert!(scope.is_global_reference(&idents[0]));
+        assert!(scope.is_global_reference(&idents[1]));
+        assert!(!scope.is_global_reference(&idents[4]));
+        // Property names aren't references
+        assert!(scope.reference(&idents[7]).is_none());
+        assert!(!scope.is_global_reference(&idents[7]));
+    }
+
+    #[test]
+    fn non_references() {
+        let (_, scope) = analyze(
+            "label: for (;;) { break label; } ({ key: 1, [computed]: 2, shorthand }); a.b.c; class C { method() {} }",
+        );
+
+        assert_eq!(unresolved(&scope), ["computed", "shorthand", "a"]);
+    }
+
+    #[test]
+    fn patterns() {
+        let (_, scope) = analyze(
+            "const { a, b: [c = d], ...e } = {}; [a, f.g, ...h] = []; try {} catch ({ message }) { message; }",
+        );
+
+        assert_eq!(unresolved(&scope), ["d", "f", "h"]);
+        let writes = scope
+            .references
+            .iter()
+            .filter(|reference| reference.is_write() && !reference.init)
+            .count();
+        assert_eq!(writes, 2);
+    }
+
+    #[test]
+    fn scopes() {
+        let (program, scope) = analyze("function f() { arguments; { let a; } } arguments;");
+        let idents = idents(&program);
+
+        let kinds: Vec<_> = scope.scopes.iter().map(|scope| scope.kind).collect();
+        assert_eq!(
+            kinds,
+            [ScopeKind::Global, ScopeKind::Function, ScopeKind::Block]
+        );
+        assert!(scope.resolve(&idents[1]).is_some());
+        assert_eq!(unresolved(&scope), ["arguments"]);
+
+        assert_eq!(scope.scope_at(idents[2].span.lo), 2);
+        assert_eq!(scope.var_scope(2), 1);
+    }
+}
@@ -0,0 +1,129 @@
+//! Tokens of a file, captured while parsing and shared with rules through
+//! [LintContext](super::rules::LintContext)
+//!
+//! Like ESLint's `SourceCode` token methods, tokens are found by position, e.g., the last token
+//! of a statement, or the token after it. Comments aren't tokens.
+
+use swc_common::{BytePos, Span};
+use swc_ecma_parser::token::{Token, TokenAndSpan};
+
+#[derive(Debug, Clone, Default)]
+pub struct Tokens {
+    /// Every token in the file, in source order
+    tokens: Vec<TokenAndSpan>,
+}
+
+impl Tokens {
+    pub fn new(tokens: Vec<TokenAndSpan>) -> Self {
+        Self { tokens }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// The last token of a node, by its span
+    pub fn last_token(&self, span: Span) -> Option<&TokenAndSpan> {
+        self.token_before(span.hi)
+            .filter(|token| token.span.lo >= span.lo)
+    }
+
+    /// The last token which ends at or before a position
+    pub fn token_before(&self, pos: BytePos) -> Option<&TokenAndSpan> {
+        let index = self.tokens.partition_point(|token| token.span.hi <= pos);
+        index.checked_sub(1).map(|index| &self.tokens[index])
+    }
+
+    /// The first token which starts at or after a position
+    pub fn token_after(&self, pos: BytePos) -> Option<&TokenAndSpan> {
+        let index = self.tokens.partition_point(|token| token.span.lo < pos);
+        self.tokens.get(index)
+    }
+}
+
+/// Whether a token is a semicolon
+pub fn is_semi(token: &TokenAndSpan) -> bool {
+    token.token == Token::Semi
+}
+
+/// Whether a token is a closing brace, `}`
+pub fn is_closing_brace(token: &TokenAndSpan) -> bool {
+    token.token == Token::RBrace
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use swc_common::{FileName, SourceMap, Spanned};
+    use swc_ecma_ast::{EsVersion, Program, Stmt};
+    use swc_ecma_parser::{lexer::Lexer, Capturing, Parser, StringInput, Syntax};
+
+    fn parse(source: &str) -> (Program, Tokens) {
+        let cm: Arc<SourceMap> = Default::default();
+        let file = cm.new_source_file(FileName::Anon, source.to_string());
+        let lexer = Lexer::new(
+            Syntax::Es(Default::default()),
+            EsVersion::latest(),
+            StringInput::from(&*file),
+            None,
+        );
+        let mut capturing = Capturing::new(lexer);
+        let program = Parser::new_from(capturing.clone()).parse_program().unwrap();
+
+        (program, Tokens::new(Capturing::take(&mut capturing)))
+    }
+
+    /// The source of the last token of each top level statement
+    fn last_tokens(source: &str) -> Vec<String> {
+        let (program, tokens) = parse(source);
+        let stmts: Vec<Stmt> = match program {
+            Program::Script(script) => script.body,
+            Program::Module(_) => unreachable!(),
+        };
+
+        stmts
+            .iter()
+            .map(|stmt| {
+                let span = tokens.last_token(stmt.span()).unwrap().span;
+                source[span.lo.0 as usize..span.hi.0 as usize].to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn last_token() {
+        assert_eq!(
+            last_tokens("a(); b() // c\nvar d = /e/\ndo {} while (f);"),
+            [";", ")", "/e/", ";"]
+        );
+        assert_eq!(last_tokens("break_: { break break_ }"), ["}"]);
+    }
+
+    #[test]
+    fn tokens_around() {
+        let (_, tokens) = parse("a /* b */ + c");
+        assert_eq!(tokens.len(), 3);
+
+        let before = tokens.token_before(BytePos(8)).unwrap();
+        assert_eq!(
+            before.span,
+            Span::new(BytePos(0), BytePos(1), Default::default())
+        );
+        let after = tokens.token_after(BytePos(2)).unwrap();
+        assert_eq!(
+            after.token,
+            Token::BinOp(swc_ecma_parser::token::BinOpToken::Add)
+        );
+        assert!(tokens
+            .token_after(BytePos(12))
+            .is_some_and(|token| !is_semi(token)));
+        assert!(tokens.token_after(BytePos(13)).is_none());
+    }
+}
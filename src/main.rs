@@ -1,40 +1,278 @@
-use std::time::Instant;
-
-use clap::Parser;
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process,
+    time::Instant,
+};
 
-use crate::{
-    cli::Cli,
-    files::get_all_files_to_lint,
-    linter::{config::LintConfig, lint_file},
+use msrlint::{
+    cache::{LintCache, DEFAULT_CACHE_LOCATION},
+    debug,
+    files::{find_config_file, get_all_files_to_lint},
+    formatters::{self, print_results},
+    linter::{
+        config::{GlobalAccess, LintConfig, ParserOptions},
+        fix::output_fixes,
+    },
+    LintResult, Linter,
 };
 
-extern crate swc_common;
+use crate::cli::{Cli, CommandFactory, ErrorKind, Parser};
+
 use colored::*;
+use serde_json::Value;
 
 mod cli;
-mod files;
-mod linter;
 
 fn main() {
     let cli = Cli::parse();
 
+    if cli.fix_type.is_some() && !cli.fix && !cli.fix_dry_run {
+        Cli::command()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "The --fix-type option requires either --fix or --fix-dry-run.",
+            )
+            .exit();
+    }
+
+    if cli.debug {
+        msrlint::debug::enable();
+    }
+
+    // --color and --no-color override each other, so at most one is set
+    let color_flag = match (cli.color, cli.no_color) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    };
+    formatters::set_color(formatters::use_color(color_flag));
+
+    if cli.env_info {
+        println!("{}", cli::env_info::env_info(&env::current_dir().unwrap()));
+        return;
+    }
+
+    if cli.list_rules {
+        println!("{}", cli::list_rules::list_rules(cli.format.as_deref()));
+        return;
+    }
+
+    if cli.init {
+        cli::init::run(&cli);
+        return;
+    }
+
+    // Print the configuration that would be used to lint a file, without linting anything
+    if let Some(file) = &cli.print_config {
+        let config_file = find_config_file(Path::new(file)).unwrap_or_default();
+        let mut lint_config = load_config(config_file);
+        apply_cli_options(&mut lint_config, &cli);
+
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&lint_config.to_json()).unwrap()
+        );
+        return;
+    }
+
     let start_time = Instant::now();
 
-    let input = get_all_files_to_lint(cli.files);
+    let input = get_all_files_to_lint(cli.files.clone());
+
+    let mut lint_config = load_config(input.config);
+    apply_cli_options(&mut lint_config, &cli);
+
+    if !lint_config.has_enabled_rules() {
+        Cli::command()
+            .error(
+                ErrorKind::InvalidValue,
+                "No rules are enabled. Consider adding 'eslint:all' to the 'extends' property of your .eslintrc.* file.",
+            )
+            .exit();
+    }
+
+    // Sort files so that results are output in a deterministic order
+    let mut files: Vec<_> = input.files.into_iter().collect();
+    files.sort();
+
+    debug!(
+        "cli",
+        "Linting {} file(s) with {} thread(s)",
+        files.len(),
+        match cli.threads {
+            0 => rayon::current_num_threads(),
+            threads => threads,
+        }
+    );
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(cli.threads)
+        .build()
+        .unwrap();
+
+    let fix_types = cli.fix_type.unwrap_or_default();
+    let linter = Linter::new(lint_config)
+        .with_fix(cli.fix || cli.fix_dry_run)
+        .with_fix_types(fix_types.into_iter().map(Into::into).collect());
+
+    let cache_location = PathBuf::from(
+        cli.cache_location
+            .as_deref()
+            .unwrap_or(DEFAULT_CACHE_LOCATION),
+    );
+    let mut cache = if cli.cache {
+        let strategy = cli.cache_strategy.unwrap_or_default();
+        Some(LintCache::load(&cache_location, strategy, linter.config()))
+    } else {
+        // Like ESLint, an old cache is removed when caching is turned off
+        if let Err(err) = LintCache::delete(&cache_location) {
+            panic!("Failed to delete cache: {}", err);
+        }
+        None
+    };
 
-    let lint_config = LintConfig::from(input.config);
+    // Replay results of unchanged files, unless they have problems which could be fixed
+    let mut cached: Vec<Option<LintResult>> = files
+        .iter()
+        .map(|file| {
+            let result = cache.as_ref()?.get(file)?;
+            (!linter.fixes() || result.messages.is_empty()).then_some(result)
+        })
+        .collect();
+    let to_lint: Vec<PathBuf> = files
+        .iter()
+        .zip(&cached)
+        .filter(|(_, cached)| cached.is_none())
+        .map(|(file, _)| file.clone())
+        .collect();
 
-    let mut num_errors = 0;
+    let linted = pool.install(|| linter.lint_paths(&to_lint));
 
-    for file in input.files {
-        num_errors += lint_file(file.as_path(), &lint_config);
+    if let Some(cache) = &mut cache {
+        for result in &linted {
+            cache.set(result);
+        }
+        if let Err(err) = cache.save() {
+            panic!("Failed to write cache: {}", err);
+        }
     }
 
-    if num_errors > 0 {
-        let err_msg = format!("Found {} errors.", num_errors);
-        println!("❌ {}", err_msg.bright_red().bold());
+    // Results are output in the same order as files, whether or not they were cached
+    let mut linted = linted.into_iter();
+    let results: Vec<LintResult> = cached
+        .iter_mut()
+        .map(|cached| cached.take().or_else(|| linted.next()).unwrap())
+        .collect();
+
+    // Only --fix saves changes, --fix-dry-run leaves them in the results
+    if cli.fix {
+        if let Err(err) = output_fixes(&results) {
+            panic!("Failed to write fixes: {}", err);
+        }
+    }
+
+    let num_errors: usize = results.iter().map(|result| result.error_count).sum();
+    let num_fatal_errors: usize = results.iter().map(|result| result.fatal_error_count).sum();
+
+    let num_warnings: usize = results.iter().map(|result| result.warning_count).sum();
+
+    print_results(cli.format.as_deref(), &results);
+
+    // Machine-readable formats are printed alone
+    if cli.format.as_deref() != Some("json") {
+        if num_errors > 0 {
+            let err_msg = format!("Found {} errors.", num_errors);
+            println!("❌ {}", err_msg.bright_red().bold());
+        }
+
+        if num_warnings > 0 {
+            let warn_msg = format!("Found {} warnings.", num_warnings);
+            println!("⚠️  {}", warn_msg.bright_yellow().bold());
+        }
+
+        let elapsed_time = start_time.elapsed().as_secs_f32();
+        println!("\n✨ Done in {:.2}s.", elapsed_time);
+    }
+
+    // Exit codes follow ESLint: 1 for linting errors, 2 for fatal errors when requested
+    if cli.exit_on_fatal_error && num_fatal_errors > 0 {
+        process::exit(2);
+    } else if num_errors > 0 {
+        process::exit(1);
+    }
+}
+
+/// Load a configuration file, exiting with exit code 2 like ESLint if it's invalid
+fn load_config(path: PathBuf) -> LintConfig {
+    LintConfig::try_from(path).unwrap_or_else(|err| {
+        eprintln!("{} {}", "error:".bright_red().bold(), err);
+        process::exit(2);
+    })
+}
+
+/// Apply options from the command line, which take priority over configuration files
+fn apply_cli_options(lint_config: &mut LintConfig, cli: &Cli) {
+    if let Some(parser_options) = &cli.parser_options {
+        match ParserOptions::from_cli(parser_options) {
+            Ok(parser_options) => lint_config.parser_options.merge(&parser_options),
+            Err(err) => Cli::command()
+                .error(
+                    ErrorKind::InvalidValue,
+                    format!(
+                        "Invalid value for '--parser-options <PARSER_OPTIONS>': {}",
+                        err
+                    ),
+                )
+                .exit(),
+        }
+    }
+
+    if cli.no_inline_config {
+        lint_config.no_inline_config = true;
+    }
+
+    for env in cli.env.iter().flatten() {
+        if let Err(err) = lint_config.set_env(env, true) {
+            Cli::command()
+                .error(
+                    ErrorKind::InvalidValue,
+                    format!("Invalid value for '--env <ENV>': {}", err),
+                )
+                .exit();
+        }
+    }
+
+    for global in cli.global.iter().flatten() {
+        let (name, access) = match global.split_once(':') {
+            Some((name, value)) => (name, GlobalAccess::from_value(&Value::from(value))),
+            None => (global.as_str(), Ok(GlobalAccess::Readonly)),
+        };
+        match access {
+            Ok(access) => {
+                lint_config.globals.insert(name.to_string(), access);
+            }
+            Err(err) => Cli::command()
+                .error(
+                    ErrorKind::InvalidValue,
+                    format!("Invalid value for '--global <GLOBAL>': {}", err),
+                )
+                .exit(),
+        }
     }
 
-    let elapsed_time = start_time.elapsed().as_secs_f32();
-    println!("\n✨ Done in {:.2}s.", elapsed_time);
+    for rule in cli.rule.iter().flatten() {
+        if let Err(err) = lint_config.apply_cli_rules(rule) {
+            Cli::command()
+                .error(
+                    ErrorKind::InvalidValue,
+                    format!("Invalid value for '--rule <RULE>': {}", err),
+                )
+                .exit();
+        }
+    }
+
+    if cli.report_unused_disable_directives {
+        lint_config.report_unused_disable_directives = true;
+    }
 }